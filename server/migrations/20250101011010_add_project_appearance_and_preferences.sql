@@ -0,0 +1,17 @@
+-- Per-project appearance shared by all members
+ALTER TABLE projects ADD COLUMN color TEXT; -- #rrggbb
+ALTER TABLE projects ADD COLUMN icon TEXT; -- emoji or short icon name
+
+-- Per-user arrangement of the project list
+CREATE TABLE IF NOT EXISTS project_user_preferences (
+  project_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  position INTEGER,
+  is_favorite INTEGER NOT NULL DEFAULT 0 CHECK (is_favorite IN (0, 1)),
+  updated_at DATETIME NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+  PRIMARY KEY(project_id, user_id),
+  FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_user_preferences_user_id ON project_user_preferences(user_id);
@@ -0,0 +1,16 @@
+-- Reusable project templates, stored apart from live projects
+CREATE TABLE IF NOT EXISTS project_templates (
+  id TEXT PRIMARY KEY,
+  owner_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  source_project_id TEXT, -- informational only; the template outlives its source
+  payload TEXT NOT NULL, -- JSON snapshot of tasks, saved views and recurring series
+  created_at DATETIME NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+  updated_at DATETIME NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+  FOREIGN KEY(owner_id) REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_templates_owner_id ON project_templates(owner_id);
+
+-- Ensure unique template name per owner (case-insensitive)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_project_templates_owner_name_unique ON project_templates(owner_id, LOWER(TRIM(name)));
@@ -0,0 +1,20 @@
+-- Ordered sections within a project
+CREATE TABLE IF NOT EXISTS sections (
+  id TEXT PRIMARY KEY,
+  project_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  position INTEGER NOT NULL,
+  created_at DATETIME NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+  updated_at DATETIME NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+  FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sections_project_position ON sections(project_id, position);
+
+-- Ensure unique section name per project (case-insensitive)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sections_project_name_unique ON sections(project_id, LOWER(TRIM(name)));
+
+-- Tasks without a section sit in the project's unsectioned list
+ALTER TABLE tasks ADD COLUMN section_id TEXT REFERENCES sections(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_section_id ON tasks(section_id);
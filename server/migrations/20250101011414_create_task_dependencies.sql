@@ -0,0 +1,17 @@
+-- "Blocked by" relations between tasks of the same project
+CREATE TABLE IF NOT EXISTS task_dependencies (
+  task_id TEXT NOT NULL,
+  blocked_by_task_id TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  PRIMARY KEY(task_id, blocked_by_task_id),
+  CHECK (task_id != blocked_by_task_id),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(blocked_by_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(created_by) REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_dependencies_blocked_by ON task_dependencies(blocked_by_task_id);
+
+-- When set, tasks with open blockers cannot be completed
+ALTER TABLE projects ADD COLUMN enforce_dependencies INTEGER NOT NULL DEFAULT 0 CHECK (enforce_dependencies IN (0, 1));
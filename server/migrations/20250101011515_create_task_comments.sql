@@ -0,0 +1,34 @@
+-- Discussion on tasks
+CREATE TABLE IF NOT EXISTS task_comments (
+  id TEXT PRIMARY KEY,
+  task_id TEXT NOT NULL,
+  author_id TEXT NOT NULL,
+  body TEXT NOT NULL,
+  edited_at TEXT,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(author_id) REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comments_task_created ON task_comments(task_id, created_at);
+
+-- updated_at trigger with millisecond precision
+CREATE TRIGGER IF NOT EXISTS task_comments_updated_at
+AFTER UPDATE ON task_comments
+FOR EACH ROW
+BEGIN
+  UPDATE task_comments SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
+
+-- Project members mentioned in a comment, kept for notifications
+CREATE TABLE IF NOT EXISTS task_comment_mentions (
+  comment_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  PRIMARY KEY(comment_id, user_id),
+  FOREIGN KEY(comment_id) REFERENCES task_comments(id) ON DELETE CASCADE,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_comment_mentions_user_id ON task_comment_mentions(user_id);
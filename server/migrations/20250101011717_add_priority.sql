@@ -0,0 +1,5 @@
+-- Priority from 0 (none) to 4 (urgent); stored as a number so it sorts directly
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 0 CHECK (priority BETWEEN 0 AND 4);
+ALTER TABLE recurring_series ADD COLUMN priority INTEGER NOT NULL DEFAULT 0 CHECK (priority BETWEEN 0 AND 4);
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_priority ON tasks(project_id, priority);
@@ -0,0 +1,5 @@
+-- Deleted tasks sit in their project's trash until restored or purged
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+ALTER TABLE tasks ADD COLUMN deleted_by TEXT REFERENCES users(id);
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_deleted_at ON tasks(project_id, deleted_at);
@@ -0,0 +1,18 @@
+-- Field-level change history of tasks
+CREATE TABLE IF NOT EXISTS task_activity (
+  id TEXT PRIMARY KEY,
+  task_id TEXT NOT NULL,
+  project_id TEXT NOT NULL,
+  actor_id TEXT NOT NULL,
+  action TEXT NOT NULL CHECK (action IN ('created', 'updated', 'completed', 'abandoned', 'restored', 'moved', 'deleted', 'undeleted')),
+  -- JSON array of {"field", "old", "new"}
+  changes TEXT NOT NULL DEFAULT '[]',
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE,
+  FOREIGN KEY(actor_id) REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_activity_task_created ON task_activity(task_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_task_activity_project_created ON task_activity(project_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_task_activity_actor_id ON task_activity(actor_id);
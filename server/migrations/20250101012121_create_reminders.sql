@@ -0,0 +1,56 @@
+-- Reminders on tasks: either an absolute time, or an offset before the task's scheduled
+-- time or deadline, interpreted in the timezone the reminder was set in
+CREATE TABLE IF NOT EXISTS task_reminders (
+  id TEXT PRIMARY KEY,
+  task_id TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN ('absolute', 'before_scheduled', 'before_deadline')),
+  -- UTC, absolute reminders only
+  remind_at TEXT,
+  offset_minutes INTEGER CHECK (offset_minutes IS NULL OR offset_minutes >= 0),
+  timezone TEXT NOT NULL DEFAULT 'UTC',
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  CHECK ((kind = 'absolute') = (remind_at IS NOT NULL)),
+  CHECK ((kind = 'absolute') = (offset_minutes IS NULL)),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(created_by) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_reminders_task_id ON task_reminders(task_id);
+
+-- Reminders copied onto every task a series generates
+CREATE TABLE IF NOT EXISTS series_reminders (
+  id TEXT PRIMARY KEY,
+  series_id TEXT NOT NULL,
+  created_by TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN ('before_scheduled', 'before_deadline')),
+  offset_minutes INTEGER NOT NULL CHECK (offset_minutes >= 0),
+  timezone TEXT NOT NULL DEFAULT 'UTC',
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  FOREIGN KEY(series_id) REFERENCES recurring_series(id) ON DELETE CASCADE,
+  FOREIGN KEY(created_by) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_series_reminders_series_id ON series_reminders(series_id);
+
+-- Reminders that have come due, waiting to be delivered. A reminder fires once per fire
+-- time, so rescheduling a task lets its reminders fire again at the new time.
+CREATE TABLE IF NOT EXISTS reminder_outbox (
+  id TEXT PRIMARY KEY,
+  reminder_id TEXT NOT NULL,
+  task_id TEXT NOT NULL,
+  recipient_id TEXT NOT NULL,
+  fire_at TEXT NOT NULL,
+  status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'sent', 'failed', 'cancelled')),
+  attempts INTEGER NOT NULL DEFAULT 0,
+  next_attempt_at TEXT NOT NULL,
+  last_error TEXT,
+  sent_at TEXT,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  UNIQUE(reminder_id, fire_at),
+  FOREIGN KEY(reminder_id) REFERENCES task_reminders(id) ON DELETE CASCADE,
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(recipient_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_reminder_outbox_status_next ON reminder_outbox(status, next_attempt_at);
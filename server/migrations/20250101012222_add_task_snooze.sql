@@ -0,0 +1,5 @@
+-- How often a task has been snoozed, so chronically postponed tasks can be found
+ALTER TABLE tasks ADD COLUMN snooze_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tasks ADD COLUMN last_snoozed_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_snooze_count ON tasks(project_id, snooze_count);
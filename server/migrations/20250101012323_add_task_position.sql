@@ -0,0 +1,35 @@
+-- Manual order of tasks within a project: a base62 rank compared as plain text. A moved
+-- task gets a rank between its new neighbours, so nothing else has to be rewritten.
+ALTER TABLE tasks ADD COLUMN position TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_position ON tasks(project_id, position);
+
+-- Existing tasks start out in the default schedule-based order. The updated_at trigger is
+-- lifted for the backfill so it doesn't look like every task was just edited.
+DROP TRIGGER IF EXISTS tasks_updated_at;
+
+WITH ranked AS (
+  SELECT id, ROW_NUMBER() OVER (
+    PARTITION BY project_id
+    ORDER BY
+      CASE WHEN scheduled_date IS NULL AND deadline_date IS NULL THEN title ELSE '' END ASC,
+      CASE WHEN scheduled_date IS NULL THEN 1 ELSE 0 END,
+      scheduled_date ASC,
+      CASE WHEN scheduled_time_minutes IS NULL THEN 1 ELSE 0 END,
+      scheduled_time_minutes ASC,
+      CASE WHEN deadline_date IS NULL THEN 1 ELSE 0 END,
+      deadline_date ASC,
+      CASE WHEN deadline_time_minutes IS NULL THEN 1 ELSE 0 END,
+      deadline_time_minutes ASC,
+      created_at ASC
+  ) AS rn
+  FROM tasks
+)
+UPDATE tasks SET position = (SELECT printf('%06dV', rn) FROM ranked WHERE ranked.id = tasks.id);
+
+CREATE TRIGGER IF NOT EXISTS tasks_updated_at
+AFTER UPDATE ON tasks
+FOR EACH ROW
+BEGIN
+  UPDATE tasks SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
@@ -0,0 +1,32 @@
+-- Tracked field values of a task as of each updated_at it has had, so a write made against
+-- an older version can be merged field by field with what changed since
+CREATE TABLE IF NOT EXISTS task_versions (
+  task_id TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  -- JSON object keyed by GraphQL field name, as in the activity log
+  fields TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  PRIMARY KEY (task_id, updated_at),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+-- Existing tasks start with their current version
+INSERT OR IGNORE INTO task_versions (task_id, updated_at, fields)
+SELECT t.id, t.updated_at, json_object(
+  'projectId', t.project_id,
+  'title', t.title,
+  'description', t.description,
+  'status', t.status,
+  'priority', CASE t.priority WHEN 1 THEN 'low' WHEN 2 THEN 'medium' WHEN 3 THEN 'high' WHEN 4 THEN 'urgent' ELSE 'none' END,
+  'estimateMinutes', t.estimate_minutes,
+  'assigneeId', t.assignee_id,
+  'sectionId', t.section_id,
+  'parentTaskId', t.parent_task_id,
+  'seriesId', t.series_id,
+  'scheduledDate', t.scheduled_date,
+  'scheduledTimeMinutes', t.scheduled_time_minutes,
+  'deadlineDate', t.deadline_date,
+  'deadlineTimeMinutes', t.deadline_time_minutes,
+  'tagIds', json((SELECT json_group_array(tag_id) FROM task_tags WHERE task_id = t.id))
+)
+FROM tasks t;
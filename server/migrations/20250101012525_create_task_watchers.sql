@@ -0,0 +1,35 @@
+-- Users following a task. Authors and assignees are added by the triggers below; anyone else
+-- in the project can add or remove themselves.
+CREATE TABLE IF NOT EXISTS task_watchers (
+  task_id TEXT NOT NULL,
+  user_id TEXT NOT NULL,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  PRIMARY KEY (task_id, user_id),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_watchers_user_id ON task_watchers(user_id, created_at);
+
+INSERT OR IGNORE INTO task_watchers (task_id, user_id)
+SELECT id, author_id FROM tasks
+UNION
+SELECT id, assignee_id FROM tasks WHERE assignee_id IS NOT NULL;
+
+CREATE TRIGGER IF NOT EXISTS tasks_watch_on_insert
+AFTER INSERT ON tasks
+FOR EACH ROW
+BEGIN
+  INSERT OR IGNORE INTO task_watchers (task_id, user_id) VALUES (NEW.id, NEW.author_id);
+  INSERT OR IGNORE INTO task_watchers (task_id, user_id)
+  SELECT NEW.id, NEW.assignee_id WHERE NEW.assignee_id IS NOT NULL;
+END;
+
+-- A new assignee starts watching, even if they had stopped before
+CREATE TRIGGER IF NOT EXISTS tasks_watch_on_assign
+AFTER UPDATE OF assignee_id ON tasks
+FOR EACH ROW
+WHEN NEW.assignee_id IS NOT NULL AND NEW.assignee_id IS NOT OLD.assignee_id
+BEGIN
+  INSERT OR IGNORE INTO task_watchers (task_id, user_id) VALUES (NEW.id, NEW.assignee_id);
+END;
@@ -0,0 +1,42 @@
+-- Typed fields a project owner defines for the project's tasks
+CREATE TABLE IF NOT EXISTS custom_fields (
+  id TEXT PRIMARY KEY,
+  project_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  kind TEXT NOT NULL CHECK (kind IN ('text', 'number', 'date', 'single_select', 'checkbox', 'url')),
+  -- JSON array of choices, for single_select only
+  options TEXT NULL,
+  -- Bounds for number fields
+  min_value REAL NULL,
+  max_value REAL NULL,
+  -- Longest value allowed for text fields
+  max_length INTEGER NULL,
+  position INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_custom_fields_project_name
+ON custom_fields(project_id, LOWER(TRIM(name)));
+
+CREATE TRIGGER IF NOT EXISTS custom_fields_updated_at
+AFTER UPDATE ON custom_fields
+FOR EACH ROW
+BEGIN
+  UPDATE custom_fields SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
+
+-- A task's value for a custom field. `value` has no declared type so each kind keeps its
+-- storage class: numbers as REAL, checkboxes as 0/1, everything else as TEXT. That makes
+-- comparisons and sorting behave per kind.
+CREATE TABLE IF NOT EXISTS task_custom_values (
+  task_id TEXT NOT NULL,
+  field_id TEXT NOT NULL,
+  value NOT NULL,
+  PRIMARY KEY (task_id, field_id),
+  FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+  FOREIGN KEY(field_id) REFERENCES custom_fields(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_custom_values_field ON task_custom_values(field_id, value);
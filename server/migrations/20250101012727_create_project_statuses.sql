@@ -0,0 +1,105 @@
+-- A project's ordered workflow. Each status belongs to a category, and a task's `status`
+-- column keeps holding its status's category ('todo' for open, 'done', 'abandoned' for
+-- cancelled), so everything that works on categories is unaffected.
+CREATE TABLE IF NOT EXISTS project_statuses (
+  id TEXT PRIMARY KEY,
+  project_id TEXT NOT NULL,
+  name TEXT NOT NULL,
+  category TEXT NOT NULL CHECK (category IN ('open', 'done', 'cancelled')),
+  position INTEGER NOT NULL DEFAULT 0,
+  created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f','now')),
+  FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_project_statuses_project_name
+ON project_statuses(project_id, LOWER(TRIM(name)));
+
+CREATE INDEX IF NOT EXISTS idx_project_statuses_project_position
+ON project_statuses(project_id, position);
+
+CREATE TRIGGER IF NOT EXISTS project_statuses_updated_at
+AFTER UPDATE ON project_statuses
+FOR EACH ROW
+BEGIN
+  UPDATE project_statuses SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
+
+-- Every project starts with one status per category
+INSERT INTO project_statuses (id, project_id, name, category, position)
+SELECT lower(hex(randomblob(16))), p.id, d.name, d.category, d.position
+FROM projects p
+CROSS JOIN (
+  SELECT 'To do' AS name, 'open' AS category, 0 AS position
+  UNION ALL SELECT 'Done', 'done', 1
+  UNION ALL SELECT 'Abandoned', 'cancelled', 2
+) d;
+
+CREATE TRIGGER IF NOT EXISTS projects_default_statuses
+AFTER INSERT ON projects
+FOR EACH ROW
+BEGIN
+  INSERT INTO project_statuses (id, project_id, name, category, position) VALUES
+    (lower(hex(randomblob(16))), NEW.id, 'To do', 'open', 0),
+    (lower(hex(randomblob(16))), NEW.id, 'Done', 'done', 1),
+    (lower(hex(randomblob(16))), NEW.id, 'Abandoned', 'cancelled', 2);
+END;
+
+ALTER TABLE tasks ADD COLUMN status_id TEXT REFERENCES project_statuses(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_status_id ON tasks(status_id);
+
+-- Existing tasks go to their category's default status. The updated_at trigger is lifted
+-- for the backfill so it doesn't look like every task was just edited.
+DROP TRIGGER IF EXISTS tasks_updated_at;
+
+UPDATE tasks SET status_id = (
+  SELECT s.id FROM project_statuses s
+  WHERE s.project_id = tasks.project_id
+    AND s.category = CASE tasks.status WHEN 'done' THEN 'done' WHEN 'abandoned' THEN 'cancelled' ELSE 'open' END
+  ORDER BY s.position ASC, s.created_at ASC LIMIT 1
+);
+
+CREATE TRIGGER IF NOT EXISTS tasks_updated_at
+AFTER UPDATE ON tasks
+FOR EACH ROW
+BEGIN
+  UPDATE tasks SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
+
+-- Anything that sets a task's category without picking a status (creating, completing,
+-- abandoning, restoring, moving to another project) lands it in the first status of that
+-- category in its project
+CREATE TRIGGER IF NOT EXISTS tasks_status_id_on_insert
+AFTER INSERT ON tasks
+FOR EACH ROW
+WHEN NOT EXISTS (
+  SELECT 1 FROM project_statuses s
+  WHERE s.id = NEW.status_id AND s.project_id = NEW.project_id
+    AND s.category = CASE NEW.status WHEN 'done' THEN 'done' WHEN 'abandoned' THEN 'cancelled' ELSE 'open' END
+)
+BEGIN
+  UPDATE tasks SET status_id = (
+    SELECT s.id FROM project_statuses s
+    WHERE s.project_id = NEW.project_id
+      AND s.category = CASE NEW.status WHEN 'done' THEN 'done' WHEN 'abandoned' THEN 'cancelled' ELSE 'open' END
+    ORDER BY s.position ASC, s.created_at ASC LIMIT 1
+  ) WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_status_id_on_update
+AFTER UPDATE OF status, project_id ON tasks
+FOR EACH ROW
+WHEN NOT EXISTS (
+  SELECT 1 FROM project_statuses s
+  WHERE s.id = NEW.status_id AND s.project_id = NEW.project_id
+    AND s.category = CASE NEW.status WHEN 'done' THEN 'done' WHEN 'abandoned' THEN 'cancelled' ELSE 'open' END
+)
+BEGIN
+  UPDATE tasks SET status_id = (
+    SELECT s.id FROM project_statuses s
+    WHERE s.project_id = NEW.project_id
+      AND s.category = CASE NEW.status WHEN 'done' THEN 'done' WHEN 'abandoned' THEN 'cancelled' ELSE 'open' END
+    ORDER BY s.position ASC, s.created_at ASC LIMIT 1
+  ) WHERE id = NEW.id;
+END;
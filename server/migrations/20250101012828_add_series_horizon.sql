@@ -0,0 +1,49 @@
+-- Recurring series are kept materialized a rolling horizon ahead. Occurrences are computed
+-- in the series' own timezone, which series created so far only passed along at creation;
+-- their default reminders recorded it, so use that where there is one.
+ALTER TABLE recurring_series ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
+
+UPDATE recurring_series SET timezone = (
+  SELECT sr.timezone FROM series_reminders sr
+  WHERE sr.series_id = recurring_series.id
+  ORDER BY sr.created_at ASC, sr.id ASC LIMIT 1
+)
+WHERE EXISTS (SELECT 1 FROM series_reminders sr WHERE sr.series_id = recurring_series.id);
+
+-- Latest occurrence date a task has been created for. Later runs only add occurrences after
+-- it, so deleting or moving a generated task doesn't bring it back.
+ALTER TABLE recurring_series ADD COLUMN materialized_through TEXT NULL;
+
+-- Occurrence (YYYY-MM-DD, in the series' timezone) a generated task stands for. It stays
+-- put when the task is rescheduled.
+ALTER TABLE tasks ADD COLUMN occurrence_date TEXT NULL;
+
+-- Existing generated tasks were created on their occurrence date. A series with two tasks
+-- on one date (one was rescheduled onto another) keeps the older as the occurrence.
+-- The updated_at trigger is lifted for the backfill so it doesn't look like every task was
+-- just edited.
+DROP TRIGGER IF EXISTS tasks_updated_at;
+
+WITH ranked AS (
+  SELECT id, ROW_NUMBER() OVER (
+    PARTITION BY series_id, scheduled_date ORDER BY created_at ASC, id ASC
+  ) AS rn
+  FROM tasks
+  WHERE series_id IS NOT NULL AND scheduled_date IS NOT NULL
+)
+UPDATE tasks SET occurrence_date = scheduled_date
+WHERE id IN (SELECT id FROM ranked WHERE rn = 1);
+
+CREATE TRIGGER IF NOT EXISTS tasks_updated_at
+AFTER UPDATE ON tasks
+FOR EACH ROW
+BEGIN
+  UPDATE tasks SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = NEW.id;
+END;
+
+UPDATE recurring_series SET materialized_through = (
+  SELECT MAX(t.occurrence_date) FROM tasks t WHERE t.series_id = recurring_series.id
+);
+
+-- One task per occurrence of a series
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_series_occurrence ON tasks(series_id, occurrence_date);
@@ -0,0 +1,8 @@
+-- Series that repeat a set number of days after the previous occurrence was done, instead
+-- of on the calendar. They keep exactly one open task, and the next one is created when
+-- that one is completed (or abandoned, if `advance_on_abandon` is set).
+ALTER TABLE recurring_series ADD COLUMN mode TEXT NOT NULL DEFAULT 'calendar' CHECK (mode IN ('calendar', 'after_completion'));
+
+ALTER TABLE recurring_series ADD COLUMN interval_days INTEGER NULL CHECK (interval_days IS NULL OR interval_days > 0);
+
+ALTER TABLE recurring_series ADD COLUMN advance_on_abandon INTEGER NOT NULL DEFAULT 0;
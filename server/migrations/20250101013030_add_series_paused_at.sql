@@ -0,0 +1,3 @@
+-- A paused series generates no tasks until it's resumed. Ending a series is recorded as an
+-- UNTIL in its rrule instead.
+ALTER TABLE recurring_series ADD COLUMN paused_at TEXT NULL;
@@ -6,6 +6,30 @@ pub const DB_PATH: &str = "./blobfishapp.sqlite";
 /// JWT secret key - should be changed in production
 pub const JWT_SECRET: &str = "CHANGE_ME_AT_DEPLOY";
 
+/// Largest single attachment upload, in bytes
+pub const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Total size of attachments a project may hold, in bytes
+pub const PROJECT_ATTACHMENT_QUOTA_BYTES: i64 = 500 * 1024 * 1024;
+
+/// Most tasks a single bulk update may touch
+pub const MAX_BULK_TASKS: usize = 100;
+
+/// Most reminders a task or recurring series may have
+pub const MAX_REMINDERS_PER_TASK: usize = 10;
+
+/// Days a deleted task stays in the trash before it is purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Versions of a task kept for merging writes made against an older version
+pub const TASK_VERSIONS_KEPT: i64 = 50;
+
+/// Upcoming occurrences of a recurring series that always exist as tasks
+pub const SERIES_HORIZON_OCCURRENCES: usize = 5;
+
+/// Days ahead through which every occurrence of a recurring series exists as a task
+pub const SERIES_HORIZON_DAYS: i64 = 14;
+
 use std::env;
 
 /// Get the path to the fullchain.pem file
@@ -20,3 +44,8 @@ pub fn key_path() -> String {
     env::var("TLS_KEY_PATH")
         .unwrap_or_else(|_| "/etc/letsencrypt/live/blobfishapp.duckdns.org/privkey.pem".to_string())
 }
+
+/// Get the directory task attachments are stored under
+pub fn attachments_dir() -> String {
+    env::var("ATTACHMENTS_DIR").unwrap_or_else(|_| "./attachments".to_string())
+}
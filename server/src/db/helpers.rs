@@ -51,6 +51,21 @@ pub fn normalize_project_name<S: AsRef<str>>(name: S) -> String {
     collapsed.trim().to_string()
 }
 
+/// Normalize a project colour according to database rules:
+/// - Trim leading/trailing whitespace
+/// - Accept `#rrggbb` or `rrggbb` hex notation
+/// - Lowercase for consistent storage
+///
+/// Returns None when the value is not a valid hex colour.
+pub fn normalize_project_color<S: AsRef<str>>(color: S) -> Option<String> {
+    let s = color.as_ref().trim();
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("#{}", hex.to_lowercase()))
+}
+
 /// Fetch a single row and convert it to the specified type
 pub async fn fetch_one<T>(pool: &SqlitePool, sql: &str, args: &[&str]) -> Result<T, sqlx::Error>
 where
@@ -74,7 +89,7 @@ pub async fn execute(pool: &SqlitePool, sql: &str, args: &[&str]) -> Result<u64,
 
 #[cfg(test)]
 mod tests {
-    use super::{normalize_project_name, normalize_tag_name};
+    use super::{normalize_project_color, normalize_project_name, normalize_tag_name};
 
     #[test]
     fn test_normalize_tag_name_basic() {
@@ -124,4 +139,19 @@ mod tests {
             "UPPERCASE project"
         );
     }
+
+    #[test]
+    fn test_normalize_project_color() {
+        assert_eq!(
+            normalize_project_color("#FF8800"),
+            Some("#ff8800".to_string())
+        );
+        assert_eq!(
+            normalize_project_color("  ff8800 "),
+            Some("#ff8800".to_string())
+        );
+        assert_eq!(normalize_project_color("#fff"), None);
+        assert_eq!(normalize_project_color("#gg0000"), None);
+        assert_eq!(normalize_project_color(""), None);
+    }
 }
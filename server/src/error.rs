@@ -23,6 +23,12 @@ impl IntoResponse for AppError {
         let status = match self.code {
             ErrorCode::InvalidCredentials => StatusCode::UNAUTHORIZED,
             ErrorCode::TokenExpired => StatusCode::UNAUTHORIZED,
+            ErrorCode::AuthenticationRequired => StatusCode::UNAUTHORIZED,
+            ErrorCode::ValidationFailed => StatusCode::BAD_REQUEST,
+            ErrorCode::PermissionDenied => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::ConflictStaleWrite => StatusCode::CONFLICT,
+            ErrorCode::QuotaExceeded => StatusCode::PAYLOAD_TOO_LARGE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
         let body = Json(ErrorBody {
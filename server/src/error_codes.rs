@@ -7,6 +7,8 @@ pub enum ErrorCode {
     Internal,
     PermissionDenied,
     ConflictStaleWrite,
+    AuthenticationRequired,
+    QuotaExceeded,
 }
 
 impl ErrorCode {
@@ -19,6 +21,8 @@ impl ErrorCode {
             Self::Internal => "INTERNAL_ERROR",
             Self::PermissionDenied => "PERMISSION_DENIED",
             Self::ConflictStaleWrite => "CONFLICT_STALE_WRITE",
+            Self::AuthenticationRequired => "AUTHENTICATION_REQUIRED",
+            Self::QuotaExceeded => "QUOTA_EXCEEDED",
         }
     }
 }
@@ -7,12 +7,22 @@ pub mod types;
 #[cfg(test)]
 pub mod tests;
 
+pub use queries::CustomFieldsQuery;
 pub use queries::HistoryQuery;
+pub use queries::ProjectActivityQuery;
 pub use queries::ProjectDefaultSavedViewQuery;
+pub use queries::ProjectStatusesQuery;
+pub use queries::ProjectTemplatesQuery;
 pub use queries::ProjectsQuery;
+pub use queries::QuickAddPreviewQuery;
 pub use queries::SavedViewsQuery;
+pub use queries::SectionsQuery;
 pub use queries::TagsQuery;
+pub use queries::TasksByStatusQuery;
 pub use queries::TasksQuery;
+pub use queries::TimeReportQuery;
+pub use queries::TrashQuery;
+pub use queries::WatchedTasksQuery;
 
 pub use mutations::create_recurring_series::CreateRecurringSeriesMutation;
 pub use mutations::create_saved_view::CreateSavedViewMutation;
@@ -21,18 +31,66 @@ pub use mutations::set_project_default_saved_view::SetProjectDefaultSavedViewMut
 pub use mutations::update_saved_view::UpdateSavedViewMutation;
 
 use mutations::abandon_task::AbandonTaskMutation;
+use mutations::add_comment::AddCommentMutation;
 use mutations::add_project_member_by_username::AddProjectMemberByUsernameMutation;
+use mutations::add_task_dependency::AddTaskDependencyMutation;
+use mutations::add_task_reminder::AddTaskReminderMutation;
+use mutations::add_time_entry::AddTimeEntryMutation;
 use mutations::archive_project::ArchiveProjectMutation;
+use mutations::bulk_update_tasks::BulkUpdateTasksMutation;
 use mutations::complete_task::CompleteTaskMutation;
+use mutations::create_custom_field::CreateCustomFieldMutation;
 use mutations::create_project::CreateProjectMutation;
+use mutations::create_project_from_template::CreateProjectFromTemplateMutation;
+use mutations::create_project_status::CreateProjectStatusMutation;
+use mutations::create_section::CreateSectionMutation;
 use mutations::create_tag::CreateTagMutation;
 use mutations::create_task::CreateTaskMutation;
+use mutations::delete_attachment::DeleteAttachmentMutation;
+use mutations::delete_comment::DeleteCommentMutation;
+use mutations::delete_custom_field::DeleteCustomFieldMutation;
+use mutations::delete_project_status::DeleteProjectStatusMutation;
+use mutations::delete_project_template::DeleteProjectTemplateMutation;
+use mutations::delete_section::DeleteSectionMutation;
+use mutations::delete_series::DeleteSeriesMutation;
 use mutations::delete_tag::DeleteTagMutation;
+use mutations::delete_task::DeleteTaskMutation;
+use mutations::delete_time_entry::DeleteTimeEntryMutation;
+use mutations::duplicate_project::DuplicateProjectMutation;
+use mutations::edit_comment::EditCommentMutation;
+use mutations::end_series::EndSeriesMutation;
+use mutations::move_task::MoveTaskMutation;
+use mutations::move_task_after::MoveTaskAfterMutation;
+use mutations::move_task_before::MoveTaskBeforeMutation;
+use mutations::move_task_to_status::MoveTaskToStatusMutation;
+use mutations::pause_series::PauseSeriesMutation;
+use mutations::quick_add_task::QuickAddTaskMutation;
+use mutations::remove_task_dependency::RemoveTaskDependencyMutation;
+use mutations::remove_task_reminder::RemoveTaskReminderMutation;
 use mutations::rename_project::RenameProjectMutation;
+use mutations::rename_project_status::RenameProjectStatusMutation;
+use mutations::rename_section::RenameSectionMutation;
 use mutations::rename_tag::RenameTagMutation;
+use mutations::reorder_project_statuses::ReorderProjectStatusesMutation;
+use mutations::reorder_projects::ReorderProjectsMutation;
+use mutations::reorder_sections::ReorderSectionsMutation;
+use mutations::restore_deleted_task::RestoreDeletedTaskMutation;
 use mutations::restore_task::RestoreTaskMutation;
+use mutations::resume_series::ResumeSeriesMutation;
+use mutations::save_project_as_template::SaveProjectAsTemplateMutation;
+use mutations::set_project_dependency_enforcement::SetProjectDependencyEnforcementMutation;
+use mutations::set_project_favorite::SetProjectFavoriteMutation;
+use mutations::snooze_task::SnoozeTaskMutation;
+use mutations::start_timer::StartTimerMutation;
+use mutations::stop_timer::StopTimerMutation;
 use mutations::unarchive_project::UnarchiveProjectMutation;
+use mutations::unwatch_task::UnwatchTaskMutation;
+use mutations::update_custom_field::UpdateCustomFieldMutation;
+use mutations::update_project_appearance::UpdateProjectAppearanceMutation;
+use mutations::update_recurring_series::UpdateRecurringSeriesMutation;
 use mutations::update_task::UpdateTaskMutation;
+use mutations::update_time_entry::UpdateTimeEntryMutation;
+use mutations::watch_task::WatchTaskMutation;
 
 #[derive(MergedObject, Default)]
 pub struct TakenlijstQuery(
@@ -42,6 +100,16 @@ pub struct TakenlijstQuery(
     HistoryQuery,
     SavedViewsQuery,
     ProjectDefaultSavedViewQuery,
+    ProjectTemplatesQuery,
+    SectionsQuery,
+    TrashQuery,
+    ProjectActivityQuery,
+    TimeReportQuery,
+    QuickAddPreviewQuery,
+    WatchedTasksQuery,
+    CustomFieldsQuery,
+    ProjectStatusesQuery,
+    TasksByStatusQuery,
 );
 
 #[derive(MergedObject, Default)]
@@ -51,23 +119,117 @@ pub struct ProjectsMutation(
     ArchiveProjectMutation,
     UnarchiveProjectMutation,
     AddProjectMemberByUsernameMutation,
+    UpdateProjectAppearanceMutation,
+    SetProjectFavoriteMutation,
+    ReorderProjectsMutation,
+    SetProjectDependencyEnforcementMutation,
 );
 
+#[derive(MergedObject, Default)]
+pub struct ProjectTemplatesMutation(
+    DuplicateProjectMutation,
+    SaveProjectAsTemplateMutation,
+    CreateProjectFromTemplateMutation,
+    DeleteProjectTemplateMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct SectionsMutation(
+    CreateSectionMutation,
+    RenameSectionMutation,
+    ReorderSectionsMutation,
+    DeleteSectionMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct CustomFieldsMutation(
+    CreateCustomFieldMutation,
+    UpdateCustomFieldMutation,
+    DeleteCustomFieldMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct WorkflowMutation(
+    CreateProjectStatusMutation,
+    RenameProjectStatusMutation,
+    ReorderProjectStatusesMutation,
+    DeleteProjectStatusMutation,
+    MoveTaskToStatusMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct SeriesMutation(
+    CreateRecurringSeriesMutation,
+    UpdateRecurringSeriesMutation,
+    PauseSeriesMutation,
+    ResumeSeriesMutation,
+    EndSeriesMutation,
+    DeleteSeriesMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct CommentsMutation(
+    AddCommentMutation,
+    EditCommentMutation,
+    DeleteCommentMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct TasksMutation(
+    CreateTaskMutation,
+    QuickAddTaskMutation,
+    UpdateTaskMutation,
+    CompleteTaskMutation,
+    AbandonTaskMutation,
+    RestoreTaskMutation,
+    DeleteTaskMutation,
+    RestoreDeletedTaskMutation,
+    BulkUpdateTasksMutation,
+    MoveTaskMutation,
+    SnoozeTaskMutation,
+    AddTaskDependencyMutation,
+    RemoveTaskDependencyMutation,
+    DeleteAttachmentMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct TimeTrackingMutation(
+    StartTimerMutation,
+    StopTimerMutation,
+    AddTimeEntryMutation,
+    UpdateTimeEntryMutation,
+    DeleteTimeEntryMutation,
+);
+
+#[derive(MergedObject, Default)]
+pub struct RemindersMutation(AddTaskReminderMutation, RemoveTaskReminderMutation);
+
+#[derive(MergedObject, Default)]
+pub struct TaskOrderMutation(MoveTaskBeforeMutation, MoveTaskAfterMutation);
+
+#[derive(MergedObject, Default)]
+pub struct WatchersMutation(WatchTaskMutation, UnwatchTaskMutation);
+
 #[derive(MergedObject, Default)]
 pub struct TagsMutation(CreateTagMutation, RenameTagMutation, DeleteTagMutation);
 
 #[derive(MergedObject, Default)]
 pub struct TakenlijstMutation(
     ProjectsMutation,
+    ProjectTemplatesMutation,
+    SectionsMutation,
+    CustomFieldsMutation,
+    WorkflowMutation,
+    CommentsMutation,
     TagsMutation,
-    CreateRecurringSeriesMutation,
+    SeriesMutation,
     CreateSavedViewMutation,
     UpdateSavedViewMutation,
     DeleteSavedViewMutation,
     SetProjectDefaultSavedViewMutation,
-    CreateTaskMutation,
-    UpdateTaskMutation,
-    CompleteTaskMutation,
-    AbandonTaskMutation,
-    RestoreTaskMutation,
+    TasksMutation,
+    TimeTrackingMutation,
+    RemindersMutation,
+    TaskOrderMutation,
+    WatchersMutation,
 );
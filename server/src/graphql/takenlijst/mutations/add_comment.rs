@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Comment;
+use crate::graphql::takenlijst::types::comment::fetch_comment;
+use crate::tasks::mentions::store_mentions;
+
+#[derive(Default)]
+pub struct AddCommentMutation;
+
+#[Object]
+impl AddCommentMutation {
+    /// Comment on a task. `@username` mentions of project members are recorded.
+    async fn add_comment(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        body: String,
+    ) -> async_graphql::Result<Comment> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // current user id
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Load task's project
+        let project_id = sqlx::query_as::<_, (String,)>(
+            "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&task_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?
+        .0;
+
+        // membership
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        // validate
+        let body = body.trim().to_string();
+        if body.is_empty() || body.len() > 5000 {
+            let error =
+                async_graphql::Error::new("Comment is required and must be <= 5000 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO task_comments (id, task_id, author_id, body) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(&id)
+        .bind(&task_id)
+        .bind(&user_id)
+        .bind(&body)
+        .execute(&mut *tx)
+        .await?;
+        store_mentions(&mut tx, &id, &project_id, &body).await?;
+        tx.commit().await?;
+
+        Ok(fetch_comment(pool, &id).await?)
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::dependencies::would_create_cycle;
+use crate::tasks::{fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct AddTaskDependencyMutation;
+
+#[Object]
+impl AddTaskDependencyMutation {
+    /// Mark `taskId` as blocked by `blockedByTaskId`. Both tasks must be in the same project
+    /// and the new relation must not close a loop.
+    async fn add_task_dependency(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        blocked_by_task_id: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Load both tasks
+        let mut project_ids = Vec::new();
+        for id in [&task_id, &blocked_by_task_id] {
+            let project_id = sqlx::query_as::<_, (String,)>(
+                "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+            )
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Task not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?
+            .0;
+            project_ids.push(project_id);
+        }
+        let project_id = project_ids[0].clone();
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        if project_ids[1] != project_id {
+            let error =
+                async_graphql::Error::new("Dependencies must be between tasks in the same project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if would_create_cycle(pool, &task_id, &blocked_by_task_id).await? {
+            let error = async_graphql::Error::new("Dependency would create a cycle")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query(
+            "INSERT OR IGNORE INTO task_dependencies (task_id, blocked_by_task_id, created_by) VALUES (?1, ?2, ?3)",
+        )
+        .bind(&task_id)
+        .bind(&blocked_by_task_id)
+        .bind(&user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_task(pool, &task_id, tz).await?)
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::config;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::ReminderInput;
+use crate::tasks::TaskTimes;
+use crate::tasks::reminders::{self, REMINDER_COLUMNS, Reminder};
+use crate::tasks::time_utils;
+
+#[derive(Default)]
+pub struct AddTaskReminderMutation;
+
+#[Object]
+impl AddTaskReminderMutation {
+    /// Add a reminder to a task. It goes to the task's assignee, or to whoever set it while
+    /// the task is unassigned. Relative reminders count back from the task's dates in
+    /// `timezone`.
+    async fn add_task_reminder(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        reminder: ReminderInput,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Reminder> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task = sqlx::query_as::<_, (String, Option<String>, Option<i32>, Option<String>, Option<i32>)>(
+            "SELECT project_id, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes \
+             FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&task_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id = task.0.clone();
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let (remind_at, offset_minutes) = reminders::validate(
+            reminder.kind,
+            reminder.remind_at.as_deref(),
+            reminder.offset_minutes,
+        )
+        .map_err(|message| {
+            async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+        })?;
+
+        let count =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM task_reminders WHERE task_id = ?1")
+                .bind(&task_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+        if count as usize >= config::MAX_REMINDERS_PER_TASK {
+            let error = async_graphql::Error::new(format!(
+                "A task can have at most {} reminders",
+                config::MAX_REMINDERS_PER_TASK
+            ))
+            .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO task_reminders (id, task_id, created_by, kind, remind_at, offset_minutes, timezone) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )
+        .bind(&id)
+        .bind(&task_id)
+        .bind(&user_id)
+        .bind(reminder.kind.as_db())
+        .bind(&remind_at)
+        .bind(offset_minutes)
+        .bind(tz.name())
+        .execute(pool)
+        .await?;
+
+        let times = TaskTimes {
+            scheduled_date: task.1,
+            scheduled_time_minutes: task.2,
+            deadline_date: task.3,
+            deadline_time_minutes: task.4,
+        };
+        let sql = format!(
+            "SELECT {} FROM task_reminders WHERE id = ?1",
+            REMINDER_COLUMNS
+        );
+        let row = sqlx::query(&sql).bind(&id).fetch_one(pool).await?;
+        Ok(Reminder::from_row(&row, &times))
+    }
+}
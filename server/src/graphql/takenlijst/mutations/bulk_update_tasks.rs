@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::{is_member, require_member};
+use crate::config;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::{
+    BulkTaskActionInput, BulkTaskActionKind, BulkTaskItemInput, BulkTaskResult,
+};
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::attachments::project_usage_bytes;
+use crate::tasks::dependencies::open_blocker_ids;
+use crate::tasks::moving::{move_refusal, move_subtree, subtree_attachment_bytes};
+use crate::tasks::series;
+use crate::tasks::subtasks::open_descendant_ids;
+use crate::tasks::{SeriesMovePolicy, fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct BulkUpdateTasksMutation;
+
+/// Why a task in the batch was left unchanged
+struct Skipped(ErrorCode, String);
+
+fn skipped(code: ErrorCode, message: &str) -> Result<(), Skipped> {
+    Err(Skipped(code, message.to_string()))
+}
+
+fn validation_error(message: &str) -> async_graphql::Error {
+    async_graphql::Error::new(message)
+        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+}
+
+struct ProjectAccess {
+    member: bool,
+    archived: bool,
+    enforce_dependencies: bool,
+}
+
+#[Object]
+impl BulkUpdateTasksMutation {
+    /// Apply one action to several tasks at once. Tasks that fail their checks are reported
+    /// and left alone; the rest are changed together in a single transaction.
+    async fn bulk_update_tasks(
+        &self,
+        ctx: &Context<'_>,
+        items: Vec<BulkTaskItemInput>,
+        action: BulkTaskActionInput,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Vec<BulkTaskResult>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        if items.is_empty() {
+            return Err(validation_error("At least one task is required"));
+        }
+        if items.len() > config::MAX_BULK_TASKS {
+            return Err(validation_error(&format!(
+                "At most {} tasks can be updated at once",
+                config::MAX_BULK_TASKS
+            )));
+        }
+
+        // Checks on the action itself fail the whole request
+        let tag_ids = action.tag_ids.clone().unwrap_or_default();
+        match action.kind {
+            BulkTaskActionKind::Reschedule => {
+                if action.scheduled_time_minutes.is_some() && action.scheduled_date.is_none() {
+                    return Err(validation_error(
+                        "scheduledTimeMinutes requires scheduledDate",
+                    ));
+                }
+                if let Some(m) = action.scheduled_time_minutes {
+                    if !(0..=1439).contains(&m) {
+                        return Err(validation_error("scheduledTimeMinutes out of range"));
+                    }
+                }
+                if let Some(date) = &action.scheduled_date {
+                    if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+                        return Err(validation_error("Invalid scheduledDate format"));
+                    }
+                }
+            }
+            BulkTaskActionKind::AddTags | BulkTaskActionKind::RemoveTags => {
+                if tag_ids.is_empty() {
+                    return Err(validation_error("tagIds is required"));
+                }
+                let placeholders = vec!["?"; tag_ids.len()].join(",");
+                let sql = format!(
+                    "SELECT COUNT(DISTINCT id) FROM tags WHERE id IN ({})",
+                    placeholders
+                );
+                let mut q = sqlx::query_as::<_, (i64,)>(&sql);
+                for tag_id in &tag_ids {
+                    q = q.bind(tag_id);
+                }
+                let found = q.fetch_one(pool).await?.0;
+                let wanted = tag_ids.iter().collect::<HashSet<_>>().len() as i64;
+                if found != wanted {
+                    let error = async_graphql::Error::new("One or more tags not found")
+                        .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                    return Err(error);
+                }
+            }
+            BulkTaskActionKind::MoveToProject => {
+                let Some(target) = &action.project_id else {
+                    return Err(validation_error("projectId is required"));
+                };
+                require_member(pool, &user_id, target).await?;
+                let archived = sqlx::query_as::<_, (Option<String>,)>(
+                    "SELECT archived_at FROM projects WHERE id = ?1",
+                )
+                .bind(target)
+                .fetch_one(pool)
+                .await?;
+                if archived.0.is_some() {
+                    let error =
+                        async_graphql::Error::new("Project is archived; tasks are read-only")
+                            .extend_with(|_, e| {
+                                e.set("code", ErrorCode::PermissionDenied.as_str())
+                            });
+                    return Err(error);
+                }
+            }
+            BulkTaskActionKind::Complete
+            | BulkTaskActionKind::Abandon
+            | BulkTaskActionKind::Restore
+            | BulkTaskActionKind::Reassign => {}
+        }
+
+        let mut projects: HashMap<String, ProjectAccess> = HashMap::new();
+        let mut outcomes: Vec<Result<(), Skipped>> = Vec::with_capacity(items.len());
+        // Open subtasks and blockers of each task being completed
+        let mut waiting_on: Vec<(usize, Vec<String>, Vec<String>)> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut moved_bytes = 0;
+        let target_usage = match &action.project_id {
+            Some(target) if action.kind == BulkTaskActionKind::MoveToProject => {
+                project_usage_bytes(&mut *pool.acquire().await?, target).await?
+            }
+            _ => 0,
+        };
+
+        for (index, item) in items.iter().enumerate() {
+            if !seen.insert(item.id.as_str()) {
+                outcomes.push(skipped(
+                    ErrorCode::ValidationFailed,
+                    "Task is listed more than once",
+                ));
+                continue;
+            }
+
+            let Some(task_row) = sqlx::query(
+                "SELECT project_id, updated_at, status FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+            )
+            .bind(&item.id)
+            .fetch_optional(pool)
+            .await?
+            else {
+                outcomes.push(skipped(ErrorCode::NotFound, "Task not found"));
+                continue;
+            };
+            let project_id: String = task_row.get("project_id");
+            let current_updated_at: String = task_row.get("updated_at");
+            let status_str: String = task_row.get("status");
+
+            if !projects.contains_key(&project_id) {
+                let (archived, enforce_dependencies) = sqlx::query_as::<_, (bool, bool)>(
+                    "SELECT archived_at IS NOT NULL, enforce_dependencies FROM projects WHERE id = ?1",
+                )
+                .bind(&project_id)
+                .fetch_one(pool)
+                .await?;
+                let member = is_member(pool, &user_id, &project_id).await?;
+                projects.insert(
+                    project_id.clone(),
+                    ProjectAccess {
+                        member,
+                        archived,
+                        enforce_dependencies,
+                    },
+                );
+            }
+            let access = &projects[&project_id];
+
+            if !access.member {
+                outcomes.push(skipped(
+                    ErrorCode::PermissionDenied,
+                    "Project not found or access denied",
+                ));
+                continue;
+            }
+            if access.archived {
+                outcomes.push(skipped(
+                    ErrorCode::PermissionDenied,
+                    "Project is archived; tasks are read-only",
+                ));
+                continue;
+            }
+            if current_updated_at != item.last_known_updated_at {
+                outcomes.push(skipped(
+                    ErrorCode::ConflictStaleWrite,
+                    "Task has been modified by another user",
+                ));
+                continue;
+            }
+
+            let outcome = match action.kind {
+                BulkTaskActionKind::Complete if status_str == "abandoned" => skipped(
+                    ErrorCode::ValidationFailed,
+                    "Cannot complete an abandoned task",
+                ),
+                BulkTaskActionKind::Complete => {
+                    let subtasks = open_descendant_ids(pool, &item.id).await?;
+                    let blockers = if access.enforce_dependencies {
+                        open_blocker_ids(pool, &item.id).await?
+                    } else {
+                        Vec::new()
+                    };
+                    waiting_on.push((index, subtasks, blockers));
+                    Ok(())
+                }
+                BulkTaskActionKind::Abandon if status_str == "done" => skipped(
+                    ErrorCode::ValidationFailed,
+                    "Cannot abandon a completed task",
+                ),
+                BulkTaskActionKind::Restore if status_str != "abandoned" => skipped(
+                    ErrorCode::ValidationFailed,
+                    "Only abandoned tasks can be restored",
+                ),
+                BulkTaskActionKind::Reassign => match &action.assignee_id {
+                    Some(assignee_id) if !is_member(pool, assignee_id, &project_id).await? => {
+                        skipped(
+                            ErrorCode::ValidationFailed,
+                            "Assignee must be a project member",
+                        )
+                    }
+                    _ => Ok(()),
+                },
+                BulkTaskActionKind::MoveToProject => {
+                    if action.project_id.as_deref() == Some(project_id.as_str()) {
+                        skipped(
+                            ErrorCode::ValidationFailed,
+                            "Task is already in this project",
+                        )
+                    } else if let Some(reason) =
+                        move_refusal(pool, &item.id, SeriesMovePolicy::Refuse).await?
+                    {
+                        skipped(ErrorCode::ValidationFailed, reason)
+                    } else {
+                        let bytes = subtree_attachment_bytes(pool, &item.id).await?;
+                        if target_usage + moved_bytes + bytes
+                            > config::PROJECT_ATTACHMENT_QUOTA_BYTES
+                        {
+                            skipped(
+                                ErrorCode::QuotaExceeded,
+                                "Project attachment storage is full",
+                            )
+                        } else {
+                            moved_bytes += bytes;
+                            Ok(())
+                        }
+                    }
+                }
+                _ => Ok(()),
+            };
+            outcomes.push(outcome);
+        }
+
+        // A task can be completed once everything it waits on is completed in this batch
+        // too; skipping one task may hold up others, so repeat until nothing changes
+        loop {
+            let completing: HashSet<&str> = items
+                .iter()
+                .zip(&outcomes)
+                .filter(|(_, outcome)| outcome.is_ok())
+                .map(|(item, _)| item.id.as_str())
+                .collect();
+            let mut held_up = Vec::new();
+            for (index, subtasks, blockers) in &waiting_on {
+                if outcomes[*index].is_err() {
+                    continue;
+                }
+                if subtasks.iter().any(|id| !completing.contains(id.as_str())) {
+                    held_up.push((*index, "Task has open subtasks"));
+                } else if blockers.iter().any(|id| !completing.contains(id.as_str())) {
+                    held_up.push((*index, "Task is blocked by open tasks"));
+                }
+            }
+            if held_up.is_empty() {
+                break;
+            }
+            for (index, message) in held_up {
+                outcomes[index] = skipped(ErrorCode::ValidationFailed, message);
+            }
+        }
+
+        let mut tx = pool.begin().await?;
+        for (item, outcome) in items.iter().zip(outcomes.iter_mut()) {
+            if outcome.is_err() {
+                continue;
+            }
+
+            // The task may have changed since it was checked above
+            let (updated_at,) =
+                sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = ?1")
+                    .bind(&item.id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+            if updated_at != item.last_known_updated_at {
+                *outcome = skipped(
+                    ErrorCode::ConflictStaleWrite,
+                    "Task has been modified by another user",
+                );
+                continue;
+            }
+
+            let before = activity::snapshot(&mut *tx, &item.id).await?;
+            match action.kind {
+                BulkTaskActionKind::Complete => {
+                    sqlx::query("UPDATE tasks SET status = 'done', completed_at = (strftime('%Y-%m-%d %H:%M:%f','now')), completed_by = ?1 WHERE id = ?2")
+                        .bind(&user_id)
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::Abandon => {
+                    sqlx::query("UPDATE tasks SET status = 'abandoned', abandoned_at = (strftime('%Y-%m-%d %H:%M:%f','now')), abandoned_by = ?1 WHERE id = ?2")
+                        .bind(&user_id)
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::Restore => {
+                    sqlx::query("UPDATE tasks SET status = 'todo', abandoned_at = NULL, abandoned_by = NULL WHERE id = ?1")
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::Reschedule => {
+                    sqlx::query("UPDATE tasks SET scheduled_date = ?1, scheduled_time_minutes = ?2 WHERE id = ?3")
+                        .bind(&action.scheduled_date)
+                        .bind(action.scheduled_time_minutes)
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::Reassign => {
+                    sqlx::query("UPDATE tasks SET assignee_id = ?1 WHERE id = ?2")
+                        .bind(&action.assignee_id)
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::AddTags | BulkTaskActionKind::RemoveTags => {
+                    for tag_id in &tag_ids {
+                        let sql = if action.kind == BulkTaskActionKind::AddTags {
+                            "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)"
+                        } else {
+                            "DELETE FROM task_tags WHERE task_id = ?1 AND tag_id = ?2"
+                        };
+                        sqlx::query(sql)
+                            .bind(&item.id)
+                            .bind(tag_id)
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                    // Tags live in their own table, so touch the task for other writers
+                    sqlx::query("UPDATE tasks SET updated_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = ?1")
+                        .bind(&item.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                BulkTaskActionKind::MoveToProject => {
+                    let target = action.project_id.as_deref().unwrap_or_default();
+                    move_subtree(&mut tx, &item.id, target).await?;
+                }
+            }
+            let logged_as = match action.kind {
+                BulkTaskActionKind::Complete => ActivityAction::Completed,
+                BulkTaskActionKind::Abandon => ActivityAction::Abandoned,
+                BulkTaskActionKind::Restore => ActivityAction::Restored,
+                BulkTaskActionKind::MoveToProject => ActivityAction::Moved,
+                _ => ActivityAction::Updated,
+            };
+            activity::record(&mut *tx, &item.id, &user_id, logged_as, &before).await?;
+            if matches!(
+                action.kind,
+                BulkTaskActionKind::Complete | BulkTaskActionKind::Abandon
+            ) {
+                series::advance_after_completion(&mut tx, &item.id).await?;
+            }
+        }
+        tx.commit().await?;
+
+        let mut results = Vec::with_capacity(items.len());
+        for (item, outcome) in items.into_iter().zip(outcomes) {
+            results.push(match outcome {
+                Ok(()) => BulkTaskResult {
+                    task: Some(fetch_task(pool, &item.id, tz).await?),
+                    id: item.id,
+                    ok: true,
+                    code: None,
+                    message: None,
+                },
+                Err(Skipped(code, message)) => BulkTaskResult {
+                    id: item.id,
+                    ok: false,
+                    code: Some(code.as_str().to_string()),
+                    message: Some(message),
+                    task: None,
+                },
+            });
+        }
+        Ok(results)
+    }
+}
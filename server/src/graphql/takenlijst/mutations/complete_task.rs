@@ -7,7 +7,11 @@ use crate::auth::Claims;
 use crate::auth::guard::require_member;
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::Task;
-use crate::tasks::{TaskStatus, time_utils};
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::dependencies::open_blockers;
+use crate::tasks::series;
+use crate::tasks::subtasks::{open_descendant_count, subtree_ids};
+use crate::tasks::{SubtaskCompletion, blocked_completion_warning, fetch_task, time_utils};
 
 #[derive(Default)]
 pub struct CompleteTaskMutation;
@@ -20,6 +24,7 @@ impl CompleteTaskMutation {
         id: String,
         last_known_updated_at: String,
         #[graphql(default = "UTC")] timezone: String,
+        #[graphql(default_with = "SubtaskCompletion::Refuse")] subtasks: SubtaskCompletion,
     ) -> async_graphql::Result<Task> {
         let claims = match ctx.data_opt::<Arc<Claims>>() {
             Some(claims) => claims,
@@ -39,7 +44,7 @@ impl CompleteTaskMutation {
 
         // Load task
         let task_row =
-            sqlx::query("SELECT id, project_id, updated_at, status FROM tasks WHERE id = ?1")
+            sqlx::query("SELECT id, project_id, updated_at, status FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
                 .bind(&id)
                 .fetch_one(pool)
                 .await
@@ -78,66 +83,63 @@ impl CompleteTaskMutation {
             return Err(error);
         }
 
-        sqlx::query("UPDATE tasks SET status = 'done', completed_at = (strftime('%Y-%m-%d %H:%M:%f','now')), completed_by = ?1 WHERE id = ?2")
-            .bind(&user_id)
-            .bind(&id)
-            .execute(pool)
-            .await?;
+        let open_subtasks = open_descendant_count(pool, &id).await?;
+        if open_subtasks > 0 && subtasks == SubtaskCompletion::Refuse {
+            let error = async_graphql::Error::new("Task has open subtasks")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
 
-        // Fetch full row
-        let row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1")
-            .bind(&id)
-            .fetch_one(pool)
-            .await?;
-
-        let status: String = row.get("status");
-        let status = match status.as_str() {
-            "todo" => TaskStatus::Todo,
-            "done" => TaskStatus::Done,
-            "abandoned" => TaskStatus::Abandoned,
-            _ => TaskStatus::Todo,
+        // The task and, when asked, its open subtasks complete together
+        let to_complete = if open_subtasks > 0 {
+            subtree_ids(pool, &id).await?
+        } else {
+            vec![id.clone()]
         };
-        let scheduled_date: Option<String> = row.get("scheduled_date");
-        let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-        let deadline_date: Option<String> = row.get("deadline_date");
-        let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
-
-        let is_overdue = time_utils::is_task_overdue(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-        let bucket = time_utils::get_task_bucket(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-
-        Ok(Task {
-            id: row.get("id"),
-            project_id: row.get("project_id"),
-            author_id: row.get("author_id"),
-            assignee_id: row.get("assignee_id"),
-            series_id: row.get("series_id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status,
-            scheduled_date,
-            scheduled_time_minutes,
-            deadline_date,
-            deadline_time_minutes,
-            completed_at: row.get("completed_at"),
-            completed_by: row.get("completed_by"),
-            abandoned_at: row.get("abandoned_at"),
-            abandoned_by: row.get("abandoned_by"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_overdue,
-            bucket,
-        })
+
+        // Open blockers only stop completion when the project enforces dependencies;
+        // otherwise the task completes with a warning naming them
+        let enforce_dependencies =
+            sqlx::query_as::<_, (bool,)>("SELECT enforce_dependencies FROM projects WHERE id = ?1")
+                .bind(&project_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+        let blocker_ids = open_blockers(pool, &to_complete).await?;
+        if enforce_dependencies && !blocker_ids.is_empty() {
+            let error = async_graphql::Error::new("Task is blocked by open tasks")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+        for task_id in &to_complete {
+            let before = activity::snapshot(&mut *tx, task_id).await?;
+            sqlx::query("UPDATE tasks SET status = 'done', completed_at = (strftime('%Y-%m-%d %H:%M:%f','now')), completed_by = ?1 WHERE id = ?2 AND (id = ?3 OR status = 'todo')")
+                .bind(&user_id)
+                .bind(task_id)
+                .bind(&id)
+                .execute(&mut *tx)
+                .await?;
+            if *task_id == id || before.get("status").and_then(|s| s.as_str()) == Some("todo") {
+                activity::record(
+                    &mut *tx,
+                    task_id,
+                    &user_id,
+                    ActivityAction::Completed,
+                    &before,
+                )
+                .await?;
+            }
+            // A series that repeats after completion schedules its next occurrence now
+            series::advance_after_completion(&mut tx, task_id).await?;
+        }
+        tx.commit().await?;
+
+        let mut task = fetch_task(pool, &id, tz).await?;
+        if !blocker_ids.is_empty() {
+            task.warnings.push(blocked_completion_warning(&blocker_ids));
+        }
+        Ok(task)
     }
 }
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::CustomFieldInput;
+use crate::tasks::custom_fields::{CustomField, check_rules, clean_options, fetch_field};
+
+#[derive(Default)]
+pub struct CreateCustomFieldMutation;
+
+#[Object]
+impl CreateCustomFieldMutation {
+    /// Add a custom field to the end of a project's field list. Only the owner can define
+    /// fields.
+    async fn create_custom_field(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        input: CustomFieldInput,
+    ) -> async_graphql::Result<CustomField> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_owner(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; fields are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let name = normalize_project_name(&input.name);
+        if name.is_empty() || name.chars().count() > 60 {
+            let error =
+                async_graphql::Error::new("Field name is required and must be <= 60 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let options = clean_options(input.options.as_deref().unwrap_or_default());
+        check_rules(
+            input.kind,
+            &options,
+            input.min_value,
+            input.max_value,
+            input.max_length,
+        )
+        .map_err(|message| {
+            async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+        })?;
+
+        let existing = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM custom_fields WHERE project_id = ?1 AND LOWER(TRIM(name)) = LOWER(TRIM(?2))",
+        )
+        .bind(&project_id)
+        .bind(&name)
+        .fetch_one(pool)
+        .await?
+        .0;
+        if existing > 0 {
+            let error =
+                async_graphql::Error::new("A field with this name already exists in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let options = (!options.is_empty())
+            .then(|| serde_json::to_string(&options))
+            .transpose()?;
+        sqlx::query(
+            "INSERT INTO custom_fields (id, project_id, name, kind, options, min_value, max_value, max_length, position) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, (SELECT COALESCE(MAX(position) + 1, 0) FROM custom_fields WHERE project_id = ?2))",
+        )
+        .bind(&id)
+        .bind(&project_id)
+        .bind(&name)
+        .bind(input.kind.as_db())
+        .bind(options)
+        .bind(input.min_value)
+        .bind(input.max_value)
+        .bind(input.max_length)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_field(&mut *pool.acquire().await?, &id).await?)
+    }
+}
@@ -7,6 +7,7 @@ use crate::auth::Claims;
 use crate::db::helpers::normalize_project_name;
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
 
 #[derive(Default)]
 pub struct CreateProjectMutation;
@@ -59,20 +60,6 @@ impl CreateProjectMutation {
             .await?;
 
         // Fetch the created project
-        let project = sqlx::query_as::<_, (String, String, String, Option<String>, String, String)>(
-            "SELECT id, name, owner_id, archived_at, created_at, updated_at FROM projects WHERE id = ?1",
-        )
-        .bind(&id)
-        .fetch_one(pool)
-        .await?;
-
-        Ok(Project {
-            id: project.0,
-            name: project.1,
-            owner_id: project.2,
-            archived_at: project.3,
-            created_at: project.4,
-            updated_at: project.5,
-        })
+        Ok(fetch_project(pool, &id, &user_id).await?)
     }
 }
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
+use crate::tasks::template::ProjectSnapshot;
+
+#[derive(Default)]
+pub struct CreateProjectFromTemplateMutation;
+
+#[Object]
+impl CreateProjectFromTemplateMutation {
+    /// Create a new project from a saved template. With `anchorDate`, all dates shift so the
+    /// template's earliest date lands on it.
+    async fn create_project_from_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: String,
+        name: String,
+        anchor_date: Option<String>,
+    ) -> async_graphql::Result<Project> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let normalized_name = normalize_project_name(&name);
+
+        // Validate name
+        if normalized_name.is_empty() {
+            let error = async_graphql::Error::new("Project name cannot be empty")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        if normalized_name.len() > 60 {
+            let error = async_graphql::Error::new("Project name cannot exceed 60 characters")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let anchor = match &anchor_date {
+            Some(date) => match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(d) => Some(d),
+                Err(_) => {
+                    let error = async_graphql::Error::new("Invalid anchorDate format")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+            },
+            None => None,
+        };
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Templates are private to their owner
+        let payload = sqlx::query_as::<_, (String,)>(
+            "SELECT payload FROM project_templates WHERE id = ?1 AND owner_id = ?2",
+        )
+        .bind(&template_id)
+        .bind(&user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Template not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?
+        .0;
+
+        let mut snapshot: ProjectSnapshot = serde_json::from_str(&payload).map_err(|_| {
+            async_graphql::Error::new("Invalid template payload")
+                .extend_with(|_, e| e.set("code", ErrorCode::Internal.as_str()))
+        })?;
+        if let Some(anchor) = anchor {
+            snapshot.shift_to(anchor);
+        }
+
+        let mut tx = pool.begin().await?;
+        let project_id = snapshot
+            .instantiate(&mut tx, &user_id, &normalized_name)
+            .await?;
+        tx.commit().await?;
+
+        Ok(fetch_project(pool, &project_id, &user_id).await?)
+    }
+}
@@ -1,10 +1,15 @@
 use crate::auth::Claims;
 use crate::auth::guard::require_member;
-use crate::graphql::takenlijst::types::{CreateSeriesInput, RecurringSeries};
+use crate::config;
+use crate::graphql::takenlijst::types::{CreateSeriesInput, RecurringSeries, fetch_series};
+use crate::tasks::quick_add::ensure_tags;
+use crate::tasks::reminders::{self, Reminder, ReminderKind};
+use crate::tasks::series::{self, SeriesMode};
+use crate::tasks::time_utils;
 use async_graphql::{Context, ErrorExtensions, Object};
-use chrono::{Datelike, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono::{NaiveDate, Utc};
 use chrono_tz::Tz;
-use rrule::{RRule, RRuleSet};
+use rrule::RRule;
 use sqlx::SqlitePool;
 use std::sync::Arc;
 
@@ -13,10 +18,24 @@ pub struct CreateRecurringSeriesMutation;
 
 #[Object]
 impl CreateRecurringSeriesMutation {
-    async fn create_recurring_series(
+    pub async fn create_recurring_series(
         &self,
         ctx: &Context<'_>,
         input: CreateSeriesInput,
+    ) -> async_graphql::Result<RecurringSeries> {
+        self.create_recurring_series_with_new_tags(ctx, input, &[])
+            .await
+    }
+}
+
+impl CreateRecurringSeriesMutation {
+    /// Create a series whose tasks are also tagged with `tag_names`. Tags that don't exist
+    /// yet are created along with the series, once the input has passed validation.
+    pub async fn create_recurring_series_with_new_tags(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateSeriesInput,
+        tag_names: &[String],
     ) -> async_graphql::Result<RecurringSeries> {
         // Require authentication
         let claims = match ctx.data_opt::<Arc<Claims>>() {
@@ -43,15 +62,52 @@ impl CreateRecurringSeriesMutation {
             return Err(error);
         }
 
-        // Validate RRULE
-        let _rrule = match input.rrule.parse::<RRule<rrule::Unvalidated>>() {
-            Ok(rrule) => rrule,
-            Err(_) => {
-                let error = async_graphql::Error::new("Invalid RRULE format")
+        if let Some(m) = input.estimate_minutes {
+            if !(1..=525600).contains(&m) {
+                let error =
+                    async_graphql::Error::new("estimateMinutes must be between 1 and 525600")
+                        .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+                return Err(error);
+            }
+        }
+
+        // A calendar series repeats on its RRULE; an after-completion one on its interval,
+        // which is stored as the equivalent daily rule
+        let mode = input.mode.unwrap_or_default();
+        let rrule = match (mode, input.rrule, input.interval_days) {
+            (SeriesMode::Calendar, Some(rrule), None) => {
+                if rrule.parse::<RRule<rrule::Unvalidated>>().is_err() {
+                    let error = async_graphql::Error::new("Invalid RRULE format")
+                        .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+                    return Err(error);
+                }
+                rrule
+            }
+            (SeriesMode::AfterCompletion, None, Some(days)) if (1..=3650).contains(&days) => {
+                format!("FREQ=DAILY;INTERVAL={}", days)
+            }
+            (SeriesMode::AfterCompletion, None, Some(_)) => {
+                let error = async_graphql::Error::new("intervalDays must be between 1 and 3650")
                     .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
                 return Err(error);
             }
+            (SeriesMode::Calendar, _, _) => {
+                let error = async_graphql::Error::new(
+                    "A calendar series takes an rrule and no intervalDays",
+                )
+                .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+                return Err(error);
+            }
+            (SeriesMode::AfterCompletion, _, _) => {
+                let error = async_graphql::Error::new(
+                    "An after-completion series takes intervalDays and no rrule",
+                )
+                .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+                return Err(error);
+            }
         };
+        let interval_days = input.interval_days;
+        let advance_on_abandon = input.advance_on_abandon.unwrap_or(false);
 
         // Parse and validate dtstart date
         let dtstart_date = match NaiveDate::parse_from_str(&input.dtstart_date, "%Y-%m-%d") {
@@ -96,19 +152,8 @@ impl CreateRecurringSeriesMutation {
             }
 
             if dtstart_date == today_in_tz {
-                let hours = time_minutes / 60;
-                let minutes = time_minutes % 60;
-                let dtstart_time = match NaiveTime::from_hms_opt(hours as u32, minutes as u32, 0) {
-                    Some(time) => time,
-                    None => {
-                        let error = async_graphql::Error::new("Invalid dtstartTimeMinutes")
-                            .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
-                        return Err(error);
-                    }
-                };
-
-                let dtstart_datetime = dtstart_date.and_time(dtstart_time);
-                let dtstart_in_tz = tz.from_local_datetime(&dtstart_datetime).single();
+                let dtstart_in_tz =
+                    time_utils::resolve_local_time(&input.dtstart_date, Some(time_minutes), tz);
 
                 if let Some(dtstart_in_tz) = dtstart_in_tz {
                     if dtstart_in_tz < now_in_tz {
@@ -159,31 +204,68 @@ impl CreateRecurringSeriesMutation {
             }
         }
 
+        // Validate default reminders; there's no single instant an absolute one could copy to
+        let mut default_reminders = Vec::new();
+        for reminder in input.default_reminders.unwrap_or_default() {
+            if reminder.kind == ReminderKind::Absolute {
+                let error =
+                    async_graphql::Error::new("Series reminders must be relative to each task")
+                        .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+                return Err(error);
+            }
+            let (_, offset_minutes) = reminders::validate(
+                reminder.kind,
+                reminder.remind_at.as_deref(),
+                reminder.offset_minutes,
+            )
+            .map_err(|message| {
+                async_graphql::Error::new(message)
+                    .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"))
+            })?;
+            default_reminders.push(Reminder {
+                id: uuid::Uuid::new_v4().to_string(),
+                kind: reminder.kind,
+                remind_at: None,
+                offset_minutes,
+                timezone: input.timezone.clone(),
+                created_by: user_id.clone(),
+                fire_at: None,
+            });
+        }
+        if default_reminders.len() > config::MAX_REMINDERS_PER_TASK {
+            let error = async_graphql::Error::new(format!(
+                "A series can have at most {} reminders",
+                config::MAX_REMINDERS_PER_TASK
+            ))
+            .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"));
+            return Err(error);
+        }
+
         // Create the recurring series
+        let priority = input.priority.unwrap_or_default();
         let series_id = uuid::Uuid::new_v4().to_string();
 
-        // Normalize RRULE for date-only series by stripping time-based parts (BYHOUR/BYMINUTE/BYSECOND)
-        let has_time_input = input.dtstart_time_minutes.is_some();
-        let normalized_rrule = if has_time_input {
-            input.rrule.clone()
-        } else {
-            let parts: Vec<&str> = input.rrule.split(';').collect();
-            let kept: Vec<&str> = parts
-                .into_iter()
-                .filter(|p| {
-                    let up = p.to_ascii_uppercase();
-                    !(up.starts_with("BYHOUR=")
-                        || up.starts_with("BYMINUTE=")
-                        || up.starts_with("BYSECOND="))
-                })
-                .collect();
-            kept.join(";")
-        };
+        // Date-only series have no time of day for BYHOUR/BYMINUTE/BYSECOND to pick
+        let normalized_rrule =
+            series::normalize_rrule(&rrule, input.dtstart_time_minutes.is_some());
+
+        series::rule_set(
+            &normalized_rrule,
+            &input.dtstart_date,
+            input.dtstart_time_minutes,
+            &input.timezone,
+        )
+        .map_err(|message| {
+            async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"))
+        })?;
+
+        let mut tx = pool.begin().await?;
 
         sqlx::query(
             "INSERT INTO recurring_series 
-             (id, project_id, created_by, title, description, assignee_id, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+             (id, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, mode, interval_days, advance_on_abandon) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
         )
         .bind(&series_id)
         .bind(&input.project_id)
@@ -191,142 +273,49 @@ impl CreateRecurringSeriesMutation {
         .bind(&input.title)
         .bind(&input.description)
         .bind(&input.assignee_id)
+        .bind(priority.as_db())
+        .bind(input.estimate_minutes)
         .bind(&normalized_rrule)
         .bind(&input.dtstart_date)
         .bind(input.dtstart_time_minutes)
         .bind(input.deadline_offset_minutes)
-        .execute(pool)
+        .bind(&input.timezone)
+        .bind(mode.as_db())
+        .bind(interval_days)
+        .bind(advance_on_abandon)
+        .execute(&mut *tx)
         .await?;
 
         // Insert default tags
+        let mut default_tag_ids = default_tag_ids;
+        default_tag_ids.extend(ensure_tags(&mut tx, tag_names).await?);
         for tag_id in &default_tag_ids {
             sqlx::query("INSERT INTO recurring_series_tags (series_id, tag_id) VALUES (?1, ?2)")
                 .bind(&series_id)
                 .bind(tag_id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
 
-        // Generate 5 future task occurrences linked to this series using RRULE iterator with chrono-tz adapter
-        let dtstart_time_minutes = input.dtstart_time_minutes;
-        let (start_naive, has_time) = {
-            let date = chrono::NaiveDate::parse_from_str(&input.dtstart_date, "%Y-%m-%d")?;
-            match dtstart_time_minutes {
-                Some(m) => {
-                    let h = m / 60;
-                    let min = m % 60;
-                    let t = chrono::NaiveTime::from_hms_opt(h as u32, min as u32, 0)
-                        .ok_or_else(|| anyhow::anyhow!("Invalid time"))?;
-                    (date.and_time(t), true)
-                }
-                None => {
-                    let t = chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap();
-                    (date.and_time(t), false)
-                }
-            }
-        };
-        if let chrono::LocalResult::Single(start_in_tz) = tz.from_local_datetime(&start_naive) {
-            // Build an RRuleSet by composing a DTSTART (with TZID/DATE semantics) and the RRULE line.
-            let y = start_in_tz.year();
-            let mo = start_in_tz.month();
-            let d = start_in_tz.day();
-            let dtstart_line = if has_time {
-                let h = start_in_tz.hour();
-                let mi = start_in_tz.minute();
-                let s = start_in_tz.second();
-                format!(
-                    "DTSTART;TZID={}:{}{:02}{:02}T{:02}{:02}{:02}",
-                    input.timezone, y, mo, d, h, mi, s
-                )
-            } else {
-                format!(
-                    "DTSTART;VALUE=DATE;TZID={}:{}{:02}{:02}",
-                    input.timezone, y, mo, d
-                )
-            };
-            let rrule_line = format!("RRULE:{}", normalized_rrule);
-            let set_str = format!("{}\n{}", dtstart_line, rrule_line);
-            let set: RRuleSet = set_str.parse().map_err(|_| {
-                async_graphql::Error::new("Invalid RRULE/DTSTART combination")
-                    .extend_with(|_, e| e.set("code", "VALIDATION_FAILED"))
-            })?;
-
-            let mut created = 0usize;
-            for occ in set.into_iter() {
-                // occ is expected to be in the correct local timezone per TZID in DTSTART
-                let occ_dt = occ.with_timezone(&tz);
-                if occ_dt < now_in_tz {
-                    continue;
-                }
-
-                let task_id = uuid::Uuid::new_v4().to_string();
-                let scheduled_date = occ_dt.date_naive().format("%Y-%m-%d").to_string();
-                let scheduled_time_minutes = dtstart_time_minutes;
-
-                let deadline_dt =
-                    occ_dt + chrono::Duration::minutes(input.deadline_offset_minutes as i64);
-                let deadline_date = Some(deadline_dt.date_naive().format("%Y-%m-%d").to_string());
-                let deadline_time_minutes = if has_time {
-                    Some((deadline_dt.hour() as i32) * 60 + (deadline_dt.minute() as i32))
-                } else {
-                    None
-                };
-
-                sqlx::query("INSERT INTO tasks (id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 'todo', ?8, ?9, ?10, ?11)")
-                    .bind(&task_id)
-                    .bind(&input.project_id)
-                    .bind(&user_id)
-                    .bind(&input.assignee_id)
-                    .bind(&series_id)
-                    .bind(&input.title)
-                    .bind(&input.description)
-                    .bind(&scheduled_date)
-                    .bind(&scheduled_time_minutes)
-                    .bind(&deadline_date)
-                    .bind(&deadline_time_minutes)
-                    .execute(pool)
-                    .await?;
-
-                for tag_id in &default_tag_ids {
-                    sqlx::query(
-                        "INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)",
-                    )
-                    .bind(&task_id)
-                    .bind(tag_id)
-                    .execute(pool)
-                    .await?;
-                }
-
-                created += 1;
-                if created >= 5 {
-                    break;
-                }
-            }
+        for reminder in &default_reminders {
+            sqlx::query(
+                "INSERT INTO series_reminders (id, series_id, created_by, kind, offset_minutes, timezone) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .bind(&reminder.id)
+            .bind(&series_id)
+            .bind(&user_id)
+            .bind(reminder.kind.as_db())
+            .bind(reminder.offset_minutes)
+            .bind(&reminder.timezone)
+            .execute(&mut *tx)
+            .await?;
         }
 
-        // Fetch the created series
-        let series = sqlx::query_as::<_, (String, String, String, String, Option<String>, Option<String>, String, String, Option<i32>, i32, String, String)>(
-            "SELECT id, project_id, created_by, title, description, assignee_id, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, created_at, updated_at 
-             FROM recurring_series WHERE id = ?1"
-        )
-        .bind(&series_id)
-        .fetch_one(pool)
-        .await?;
+        // Create the tasks for its first occurrences; the jobs keep the horizon rolling
+        series::materialize(&mut tx, &series_id, Utc::now()).await?;
+        tx.commit().await?;
 
-        Ok(RecurringSeries {
-            id: series.0,
-            project_id: series.1,
-            created_by: series.2,
-            title: series.3,
-            description: series.4,
-            assignee_id: series.5,
-            rrule: series.6,
-            dtstart_date: series.7,
-            dtstart_time_minutes: series.8,
-            deadline_offset_minutes: series.9,
-            created_at: series.10,
-            updated_at: series.11,
-            default_tag_ids,
-        })
+        Ok(fetch_series(pool, &series_id).await?)
     }
 }
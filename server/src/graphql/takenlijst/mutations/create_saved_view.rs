@@ -2,6 +2,7 @@ use crate::auth::Claims;
 use crate::auth::guard::require_member;
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::{SavedView, SavedViewFilters, SavedViewFiltersInput};
+use crate::tasks::custom_fields::project_fields;
 use async_graphql::{Context, ErrorExtensions, Object};
 use sqlx::SqlitePool;
 use std::sync::Arc;
@@ -100,6 +101,38 @@ impl CreateSavedViewMutation {
             }
         }
 
+        // Validate section IDs belong to this project
+        for section_id in &filters.section_ids {
+            let section_exists = sqlx::query_as::<_, (i64,)>(
+                "SELECT COUNT(*) FROM sections WHERE id = ?1 AND project_id = ?2",
+            )
+            .bind(section_id)
+            .bind(&project_id)
+            .fetch_one(pool)
+            .await?;
+
+            if section_exists.0 == 0 {
+                let error = async_graphql::Error::new("One or more sections not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+        }
+
+        // Validate custom field predicates against the project's fields
+        let fields = project_fields(&mut *pool.acquire().await?, &project_id).await?;
+        for filter in &filters.custom_fields {
+            let Some(field) = fields.iter().find(|f| f.id == filter.field_id) else {
+                let error = async_graphql::Error::new("One or more custom fields not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            };
+            if let Err(message) = field.filter(filter.op, filter.value.as_deref()) {
+                let error = async_graphql::Error::new(message)
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
         // Convert input filters to JSON
         let filters_obj = SavedViewFilters {
             statuses: filters.statuses,
@@ -107,6 +140,11 @@ impl CreateSavedViewMutation {
             include_unassigned: filters.include_unassigned,
             assigned_to_me: filters.assigned_to_me,
             tag_ids: filters.tag_ids,
+            section_ids: filters.section_ids,
+            include_unsectioned: filters.include_unsectioned,
+            priorities: filters.priorities,
+            sort: filters.sort,
+            custom_fields: filters.custom_fields.into_iter().map(Into::into).collect(),
         };
 
         let filters_json = serde_json::to_string(&filters_obj).map_err(|_| {
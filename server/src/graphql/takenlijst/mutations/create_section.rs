@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Section;
+use crate::graphql::takenlijst::types::section::fetch_section;
+
+#[derive(Default)]
+pub struct CreateSectionMutation;
+
+#[Object]
+impl CreateSectionMutation {
+    /// Add a section to the end of a project's section list
+    async fn create_section(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        name: String,
+    ) -> async_graphql::Result<Section> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; sections are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        // Validate and normalize name
+        let normalized_name = normalize_project_name(&name);
+        if normalized_name.is_empty() {
+            let error = async_graphql::Error::new("Section name cannot be empty")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        if normalized_name.len() > 60 {
+            let error = async_graphql::Error::new("Section name cannot exceed 60 characters")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        // Check for unique name per project (case-insensitive)
+        let existing_count = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM sections WHERE project_id = ?1 AND LOWER(TRIM(name)) = LOWER(TRIM(?2))",
+        )
+        .bind(&project_id)
+        .bind(&normalized_name)
+        .fetch_one(pool)
+        .await?;
+
+        if existing_count.0 > 0 {
+            let error = async_graphql::Error::new(
+                "A section with this name already exists in this project",
+            )
+            .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO sections (id, project_id, name, position) \
+             VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(position) + 1, 0) FROM sections WHERE project_id = ?2))",
+        )
+        .bind(&id)
+        .bind(&project_id)
+        .bind(&normalized_name)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_section(pool, &id).await?)
+    }
+}
@@ -1,25 +1,43 @@
 use std::sync::Arc;
 
 use async_graphql::{Context, ErrorExtensions, Object};
-use sqlx::{Row, SqlitePool};
+use sqlx::SqlitePool;
 
 use crate::auth::Claims;
 use crate::auth::guard::{is_member, require_member};
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::CreateTaskInput;
 use crate::graphql::takenlijst::types::Task;
-use crate::tasks::{TaskStatus, time_utils};
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::ordering::append_position;
+use crate::tasks::quick_add::ensure_tags;
+use crate::tasks::{custom_fields, fetch_task, subtasks, time_utils};
 
 #[derive(Default)]
 pub struct CreateTaskMutation;
 
 #[Object]
 impl CreateTaskMutation {
-    async fn create_task(
+    pub async fn create_task(
         &self,
         ctx: &Context<'_>,
         input: CreateTaskInput,
         #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        self.create_task_with_new_tags(ctx, input, timezone, &[])
+            .await
+    }
+}
+
+impl CreateTaskMutation {
+    /// Create a task that is also tagged with `tag_names`. Tags that don't exist yet are
+    /// created along with the task, once the input has passed validation.
+    pub async fn create_task_with_new_tags(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateTaskInput,
+        timezone: String,
+        tag_names: &[String],
     ) -> async_graphql::Result<Task> {
         let claims = match ctx.data_opt::<Arc<Claims>>() {
             Some(claims) => claims,
@@ -93,6 +111,14 @@ impl CreateTaskMutation {
                 return Err(error);
             }
         }
+        if let Some(m) = input.estimate_minutes {
+            if !(1..=525600).contains(&m) {
+                let error =
+                    async_graphql::Error::new("estimateMinutes must be between 1 and 525600")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
         if let Some(date) = &input.scheduled_date {
             if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
                 let error = async_graphql::Error::new("Invalid scheduledDate format")
@@ -118,6 +144,48 @@ impl CreateTaskMutation {
             }
         }
 
+        // Section must belong to the same project
+        if let Some(section_id) = &input.section_id {
+            let in_project = sqlx::query_as::<_, (i64,)>(
+                "SELECT COUNT(*) FROM sections WHERE id = ?1 AND project_id = ?2",
+            )
+            .bind(section_id)
+            .bind(&input.project_id)
+            .fetch_one(pool)
+            .await?
+            .0;
+            if in_project == 0 {
+                let error = async_graphql::Error::new("Section not found in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+        }
+
+        // Parent must be in the same project and leave room for another level
+        if let Some(parent_id) = &input.parent_task_id {
+            let in_project = sqlx::query_as::<_, (i64,)>(
+                "SELECT COUNT(*) FROM tasks WHERE id = ?1 AND project_id = ?2 AND deleted_at IS NULL",
+            )
+            .bind(parent_id)
+            .bind(&input.project_id)
+            .fetch_one(pool)
+            .await?
+            .0;
+            if in_project == 0 {
+                let error = async_graphql::Error::new("Parent task not found in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+            if !subtasks::fits_under(subtasks::task_level(pool, parent_id).await?, 1) {
+                let error = async_graphql::Error::new(format!(
+                    "Subtasks cannot be nested more than {} levels deep",
+                    subtasks::MAX_TASK_LEVELS
+                ))
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
         // Validate tag IDs exist if provided
         if let Some(tag_ids) = &input.tag_ids {
             if !tag_ids.is_empty() {
@@ -136,87 +204,71 @@ impl CreateTaskMutation {
             }
         }
 
+        // custom field values must suit the project's fields
+        let custom_values = match &input.custom_fields {
+            Some(values) => {
+                let fields =
+                    custom_fields::project_fields(&mut *pool.acquire().await?, &input.project_id)
+                        .await?;
+                let values: Vec<(String, Option<String>)> = values
+                    .iter()
+                    .map(|v| (v.field_id.clone(), v.value.clone()))
+                    .collect();
+                custom_fields::parse_values(&fields, &values).map_err(|message| {
+                    async_graphql::Error::new(message)
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        let mut tx = pool.begin().await?;
+
         // Insert task
         let id = uuid::Uuid::new_v4().to_string();
-        sqlx::query("INSERT INTO tasks (id, project_id, author_id, assignee_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes) VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'todo', ?7, ?8, ?9, ?10)")
+        let position = append_position(&mut tx, &input.project_id).await?;
+        sqlx::query("INSERT INTO tasks (id, project_id, author_id, assignee_id, section_id, parent_task_id, title, description, status, priority, estimate_minutes, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, position) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'todo', ?9, ?10, ?11, ?12, ?13, ?14, ?15)")
             .bind(&id)
             .bind(&input.project_id)
             .bind(&user_id)
             .bind(&input.assignee_id)
+            .bind(&input.section_id)
+            .bind(&input.parent_task_id)
             .bind(&title_trim)
             .bind(&input.description)
+            .bind(input.priority.unwrap_or_default().as_db())
+            .bind(input.estimate_minutes)
             .bind(&input.scheduled_date)
             .bind(&input.scheduled_time_minutes)
             .bind(&input.deadline_date)
             .bind(&input.deadline_time_minutes)
-            .execute(pool)
+            .bind(&position)
+            .execute(&mut *tx)
             .await?;
 
         // Insert tags mapping
-        if let Some(tag_ids) = &input.tag_ids {
-            for tag_id in tag_ids {
-                sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)")
-                    .bind(&id)
-                    .bind(tag_id)
-                    .execute(pool)
-                    .await?;
-            }
+        let mut tag_ids = input.tag_ids.unwrap_or_default();
+        tag_ids.extend(ensure_tags(&mut tx, tag_names).await?);
+        for tag_id in &tag_ids {
+            sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)")
+                .bind(&id)
+                .bind(tag_id)
+                .execute(&mut *tx)
+                .await?;
         }
 
-        // Fetch created
-        let row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1")
-            .bind(&id)
-            .fetch_one(pool)
-            .await?;
+        custom_fields::store_values(&mut tx, &id, &custom_values).await?;
 
-        let status: String = row.get("status");
-        let status = match status.as_str() {
-            "todo" => TaskStatus::Todo,
-            "done" => TaskStatus::Done,
-            "abandoned" => TaskStatus::Abandoned,
-            _ => TaskStatus::Todo,
-        };
-        let scheduled_date: Option<String> = row.get("scheduled_date");
-        let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-        let deadline_date: Option<String> = row.get("deadline_date");
-        let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
-
-        let is_overdue = time_utils::is_task_overdue(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-        let bucket = time_utils::get_task_bucket(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-
-        Ok(Task {
-            id: row.get("id"),
-            project_id: row.get("project_id"),
-            author_id: row.get("author_id"),
-            assignee_id: row.get("assignee_id"),
-            series_id: row.get("series_id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status,
-            scheduled_date,
-            scheduled_time_minutes,
-            deadline_date,
-            deadline_time_minutes,
-            completed_at: row.get("completed_at"),
-            completed_by: row.get("completed_by"),
-            abandoned_at: row.get("abandoned_at"),
-            abandoned_by: row.get("abandoned_by"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_overdue,
-            bucket,
-        })
+        activity::record(
+            &mut tx,
+            &id,
+            &user_id,
+            ActivityAction::Created,
+            &Default::default(),
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
     }
 }
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::{is_owner, require_member};
+use crate::config;
+use crate::error_codes::ErrorCode;
+use crate::tasks::attachments::{fetch_attachment, remove_if_unreferenced};
+
+#[derive(Default)]
+pub struct DeleteAttachmentMutation;
+
+#[Object]
+impl DeleteAttachmentMutation {
+    /// Delete an attachment. Uploaders can delete their own files; the project owner can
+    /// delete any.
+    async fn delete_attachment(
+        &self,
+        ctx: &Context<'_>,
+        attachment_id: String,
+    ) -> async_graphql::Result<bool> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // current user id
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let attachment = fetch_attachment(pool, &attachment_id).await.map_err(|_| {
+            async_graphql::Error::new("Attachment not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id =
+            sqlx::query_as::<_, (String,)>("SELECT project_id FROM tasks WHERE id = ?1")
+                .bind(&attachment.task_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+
+        // membership
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if attachment.uploaded_by != user_id && !is_owner(pool, &user_id, &project_id).await? {
+            let error = async_graphql::Error::new("Only the uploader can delete an attachment")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query("DELETE FROM task_attachments WHERE id = ?1")
+            .bind(&attachment_id)
+            .execute(pool)
+            .await?;
+
+        // The same contents may still be attached elsewhere
+        let root = PathBuf::from(config::attachments_dir());
+        remove_if_unreferenced(pool, &root, &attachment.sha256).await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::{is_owner, require_member};
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::comment::fetch_comment;
+
+#[derive(Default)]
+pub struct DeleteCommentMutation;
+
+#[Object]
+impl DeleteCommentMutation {
+    /// Delete a comment. Authors can delete their own comments; the project owner can
+    /// delete any.
+    async fn delete_comment(
+        &self,
+        ctx: &Context<'_>,
+        comment_id: String,
+    ) -> async_graphql::Result<bool> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // current user id
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let comment = fetch_comment(pool, &comment_id).await.map_err(|_| {
+            async_graphql::Error::new("Comment not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id =
+            sqlx::query_as::<_, (String,)>("SELECT project_id FROM tasks WHERE id = ?1")
+                .bind(&comment.task_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+
+        // membership
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if comment.author_id != user_id && !is_owner(pool, &user_id, &project_id).await? {
+            let error = async_graphql::Error::new("Only the author can delete a comment")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query("DELETE FROM task_comments WHERE id = ?1")
+            .bind(&comment_id)
+            .execute(pool)
+            .await?;
+
+        Ok(true)
+    }
+}
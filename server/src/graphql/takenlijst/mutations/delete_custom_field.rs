@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::SavedViewFilters;
+use crate::tasks::custom_fields::fetch_field;
+
+#[derive(Default)]
+pub struct DeleteCustomFieldMutation;
+
+#[Object]
+impl DeleteCustomFieldMutation {
+    /// Delete a custom field along with every task's value for it. Saved views stop
+    /// filtering on it.
+    async fn delete_custom_field(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<bool> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let field = fetch_field(&mut *pool.acquire().await?, &id)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Custom field not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?;
+
+        require_owner(pool, &user_id, &field.project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&field.project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; fields are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let views = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, filters FROM saved_views WHERE project_id = ?1",
+        )
+        .bind(&field.project_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        for (view_id, filters) in views {
+            let Ok(mut filters) = serde_json::from_str::<SavedViewFilters>(&filters) else {
+                continue;
+            };
+            if !filters.custom_fields.iter().any(|f| f.field_id == id) {
+                continue;
+            }
+            filters.custom_fields.retain(|f| f.field_id != id);
+            sqlx::query("UPDATE saved_views SET filters = ?1 WHERE id = ?2")
+                .bind(serde_json::to_string(&filters)?)
+                .bind(&view_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query("DELETE FROM custom_fields WHERE id = ?1")
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
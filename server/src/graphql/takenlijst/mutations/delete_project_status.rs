@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::error_codes::ErrorCode;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::workflow::{fetch_status, project_statuses};
+
+#[derive(Default)]
+pub struct DeleteProjectStatusMutation;
+
+#[Object]
+impl DeleteProjectStatusMutation {
+    /// Delete a workflow status, moving its tasks to `moveTasksTo` or, by default, the first
+    /// other status of the same category. Every project keeps at least one status per
+    /// category.
+    async fn delete_project_status(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        move_tasks_to: Option<String>,
+    ) -> async_graphql::Result<bool> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let status = fetch_status(&mut *pool.acquire().await?, &id)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Status not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?;
+
+        require_owner(pool, &user_id, &status.project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&status.project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; statuses are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        // Tasks keep their category, so they can only move within it
+        let siblings: Vec<_> = project_statuses(&mut *pool.acquire().await?, &status.project_id)
+            .await?
+            .into_iter()
+            .filter(|s| s.id != id && s.category == status.category)
+            .collect();
+        let target = match &move_tasks_to {
+            Some(target_id) => siblings
+                .iter()
+                .find(|s| &s.id == target_id)
+                .ok_or_else(|| {
+                    async_graphql::Error::new(
+                    "Tasks can only move to another status of the same category in this project",
+                )
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+                })?,
+            None => siblings.first().ok_or_else(|| {
+                async_graphql::Error::new("The last status of a category cannot be deleted")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+            })?,
+        };
+
+        let mut tx = pool.begin().await?;
+
+        let task_ids = sqlx::query_as::<_, (String,)>("SELECT id FROM tasks WHERE status_id = ?1")
+            .bind(&id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        // Each task logs its own status change
+        for (task_id,) in &task_ids {
+            let before = activity::snapshot(&mut tx, task_id).await?;
+            sqlx::query("UPDATE tasks SET status_id = ?1 WHERE id = ?2")
+                .bind(&target.id)
+                .bind(task_id)
+                .execute(&mut *tx)
+                .await?;
+            activity::record(&mut tx, task_id, &user_id, ActivityAction::Updated, &before).await?;
+        }
+
+        sqlx::query("DELETE FROM project_statuses WHERE id = ?1")
+            .bind(&id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::error_codes::ErrorCode;
+
+#[derive(Default)]
+pub struct DeleteProjectTemplateMutation;
+
+#[Object]
+impl DeleteProjectTemplateMutation {
+    async fn delete_project_template(
+        &self,
+        ctx: &Context<'_>,
+        template_id: String,
+    ) -> async_graphql::Result<bool> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let result = sqlx::query("DELETE FROM project_templates WHERE id = ?1 AND owner_id = ?2")
+            .bind(&template_id)
+            .bind(&user_id)
+            .execute(pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            let error = async_graphql::Error::new("Template not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::section::fetch_section;
+use crate::tasks::versions;
+
+#[derive(Default)]
+pub struct DeleteSectionMutation;
+
+#[Object]
+impl DeleteSectionMutation {
+    /// Delete a section. Its tasks are kept and become unsectioned.
+    async fn delete_section(
+        &self,
+        ctx: &Context<'_>,
+        section_id: String,
+    ) -> async_graphql::Result<bool> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let section = fetch_section(pool, &section_id).await.map_err(|_| {
+            async_graphql::Error::new("Section not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+
+        require_member(pool, &user_id, &section.project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&section.project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; sections are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let task_ids = sqlx::query_as::<_, (String,)>("SELECT id FROM tasks WHERE section_id = ?1")
+            .bind(&section_id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        sqlx::query("UPDATE tasks SET section_id = NULL WHERE section_id = ?1")
+            .bind(&section_id)
+            .execute(&mut *tx)
+            .await?;
+        for (task_id,) in &task_ids {
+            versions::save_current(&mut tx, task_id).await?;
+        }
+
+        sqlx::query("DELETE FROM sections WHERE id = ?1")
+            .bind(&section_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
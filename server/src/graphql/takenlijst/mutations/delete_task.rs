@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::{fetch_task, time_utils, trash};
+
+#[derive(Default)]
+pub struct DeleteTaskMutation;
+
+#[Object]
+impl DeleteTaskMutation {
+    /// Move a task and its subtasks to the project's trash. They can be brought back with
+    /// `restoreDeletedTask` until they are purged.
+    async fn delete_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        last_known_updated_at: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task_row = sqlx::query(
+            "SELECT id, project_id, updated_at FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id: String = task_row.get("project_id");
+        let current_updated_at: String = task_row.get("updated_at");
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if current_updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Task has been modified by another user")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        let before = activity::snapshot(&mut *pool.acquire().await?, &id).await?;
+        trash::soft_delete(&mut *pool.acquire().await?, &id, &user_id).await?;
+        activity::record(
+            &mut *pool.acquire().await?,
+            &id,
+            &user_id,
+            ActivityAction::Deleted,
+            &before,
+        )
+        .await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::{is_owner, require_member};
+use crate::error_codes::ErrorCode;
+use crate::tasks::time_tracking::fetch_time_entry;
+
+#[derive(Default)]
+pub struct DeleteTimeEntryMutation;
+
+#[Object]
+impl DeleteTimeEntryMutation {
+    /// Delete a time entry. Members can delete their own entries; the project owner can
+    /// delete any.
+    async fn delete_time_entry(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<bool> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let not_found = || {
+            async_graphql::Error::new("Time entry not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        };
+        let entry = fetch_time_entry(pool, &id).await.map_err(|_| not_found())?;
+        let project_id = sqlx::query_as::<_, (String,)>(
+            "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&entry.task_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(not_found)?
+        .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if entry.user_id != user_id && !is_owner(pool, &user_id, &project_id).await? {
+            let error = async_graphql::Error::new("Only your own time entries can be deleted")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query("DELETE FROM time_entries WHERE id = ?1")
+            .bind(&id)
+            .execute(pool)
+            .await?;
+
+        Ok(true)
+    }
+}
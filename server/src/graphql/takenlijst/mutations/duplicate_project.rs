@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
+use crate::tasks::template::ProjectSnapshot;
+
+#[derive(Default)]
+pub struct DuplicateProjectMutation;
+
+#[Object]
+impl DuplicateProjectMutation {
+    /// Copy a project's open tasks, saved views, default view and recurring series into a new
+    /// project owned by the caller. With `anchorDate`, all dates shift so the earliest lands on it.
+    async fn duplicate_project(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        name: Option<String>,
+        anchor_date: Option<String>,
+    ) -> async_graphql::Result<Project> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Any member can copy a project they can read
+        require_member(pool, &user_id, &project_id).await?;
+
+        let anchor = match &anchor_date {
+            Some(date) => match chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(d) => Some(d),
+                Err(_) => {
+                    let error = async_graphql::Error::new("Invalid anchorDate format")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+            },
+            None => None,
+        };
+
+        // Default name is "<source> (copy)", trimmed to fit the 60 character limit
+        let normalized_name = match name {
+            Some(name) => normalize_project_name(&name),
+            None => {
+                let source =
+                    sqlx::query_as::<_, (String,)>("SELECT name FROM projects WHERE id = ?1")
+                        .bind(&project_id)
+                        .fetch_one(pool)
+                        .await?
+                        .0;
+                let mut base = source;
+                while base.len() + " (copy)".len() > 60 {
+                    base.pop();
+                }
+                format!("{} (copy)", base.trim_end())
+            }
+        };
+
+        if normalized_name.is_empty() {
+            let error = async_graphql::Error::new("Project name cannot be empty")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        if normalized_name.len() > 60 {
+            let error = async_graphql::Error::new("Project name cannot exceed 60 characters")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let mut snapshot = ProjectSnapshot::capture(pool, &project_id).await?;
+        if let Some(anchor) = anchor {
+            snapshot.shift_to(anchor);
+        }
+
+        let mut tx = pool.begin().await?;
+        let new_project_id = snapshot
+            .instantiate(&mut tx, &user_id, &normalized_name)
+            .await?;
+        tx.commit().await?;
+
+        Ok(fetch_project(pool, &new_project_id, &user_id).await?)
+    }
+}
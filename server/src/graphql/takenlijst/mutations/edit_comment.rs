@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Comment;
+use crate::graphql::takenlijst::types::comment::fetch_comment;
+use crate::tasks::mentions::store_mentions;
+
+#[derive(Default)]
+pub struct EditCommentMutation;
+
+#[Object]
+impl EditCommentMutation {
+    /// Change the body of one of your own comments. Mentions are re-parsed.
+    async fn edit_comment(
+        &self,
+        ctx: &Context<'_>,
+        comment_id: String,
+        body: String,
+        last_known_updated_at: String,
+    ) -> async_graphql::Result<Comment> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // current user id
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let comment = fetch_comment(pool, &comment_id).await.map_err(|_| {
+            async_graphql::Error::new("Comment not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id =
+            sqlx::query_as::<_, (String,)>("SELECT project_id FROM tasks WHERE id = ?1")
+                .bind(&comment.task_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+
+        // membership
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if comment.author_id != user_id {
+            let error = async_graphql::Error::new("Only the author can edit a comment")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        // concurrency check
+        if comment.updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Comment has been modified")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        // validate
+        let body = body.trim().to_string();
+        if body.is_empty() || body.len() > 5000 {
+            let error =
+                async_graphql::Error::new("Comment is required and must be <= 5000 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+        sqlx::query(
+            "UPDATE task_comments SET body = ?1, edited_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = ?2",
+        )
+        .bind(&body)
+        .bind(&comment_id)
+        .execute(&mut *tx)
+        .await?;
+        store_mentions(&mut tx, &comment_id, &project_id, &body).await?;
+        tx.commit().await?;
+
+        Ok(fetch_comment(pool, &comment_id).await?)
+    }
+}
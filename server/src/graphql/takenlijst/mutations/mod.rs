@@ -1,18 +1,66 @@
 pub mod abandon_task;
+pub mod add_comment;
 pub mod add_project_member_by_username;
+pub mod add_task_dependency;
+pub mod add_task_reminder;
+pub mod add_time_entry;
 pub mod archive_project;
+pub mod bulk_update_tasks;
 pub mod complete_task;
+pub mod create_custom_field;
 pub mod create_project;
+pub mod create_project_from_template;
+pub mod create_project_status;
 pub mod create_recurring_series;
 pub mod create_saved_view;
+pub mod create_section;
 pub mod create_tag;
 pub mod create_task;
+pub mod delete_attachment;
+pub mod delete_comment;
+pub mod delete_custom_field;
+pub mod delete_project_status;
+pub mod delete_project_template;
 pub mod delete_saved_view;
+pub mod delete_section;
+pub mod delete_series;
 pub mod delete_tag;
+pub mod delete_task;
+pub mod delete_time_entry;
+pub mod duplicate_project;
+pub mod edit_comment;
+pub mod end_series;
+pub mod move_task;
+pub mod move_task_after;
+pub mod move_task_before;
+pub mod move_task_to_status;
+pub mod pause_series;
+pub mod quick_add_task;
+pub mod remove_task_dependency;
+pub mod remove_task_reminder;
 pub mod rename_project;
+pub mod rename_project_status;
+pub mod rename_section;
 pub mod rename_tag;
+pub mod reorder_project_statuses;
+pub mod reorder_projects;
+pub mod reorder_sections;
+pub mod restore_deleted_task;
 pub mod restore_task;
+pub mod resume_series;
+pub mod save_project_as_template;
 pub mod set_project_default_saved_view;
+pub mod set_project_dependency_enforcement;
+pub mod set_project_favorite;
+pub mod snooze_task;
+pub mod start_timer;
+pub mod stop_timer;
 pub mod unarchive_project;
+pub mod unwatch_task;
+pub mod update_custom_field;
+pub mod update_project_appearance;
+pub mod update_recurring_series;
 pub mod update_saved_view;
 pub mod update_task;
+pub mod update_time_entry;
+pub mod watch_task;
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::config;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::attachments::project_usage_bytes;
+use crate::tasks::moving::{move_refusal, move_subtree, subtree_attachment_bytes};
+use crate::tasks::{SeriesMovePolicy, fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct MoveTaskMutation;
+
+#[Object]
+impl MoveTaskMutation {
+    /// Move a top-level task and its subtasks to another project. The section is cleared,
+    /// and the assignee is kept only if they are a member of the new project.
+    async fn move_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        target_project_id: String,
+        last_known_updated_at: String,
+        #[graphql(default_with = "SeriesMovePolicy::Refuse")] series_policy: SeriesMovePolicy,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task_row = sqlx::query(
+            "SELECT id, project_id, updated_at FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id: String = task_row.get("project_id");
+        let current_updated_at: String = task_row.get("updated_at");
+
+        // The caller needs access to both ends, and neither may be archived
+        require_member(pool, &user_id, &project_id).await?;
+        require_member(pool, &user_id, &target_project_id).await?;
+
+        for project in [&project_id, &target_project_id] {
+            let archived = sqlx::query_as::<_, (Option<String>,)>(
+                "SELECT archived_at FROM projects WHERE id = ?1",
+            )
+            .bind(project)
+            .fetch_one(pool)
+            .await?;
+            if archived.0.is_some() {
+                let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                    .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+                return Err(error);
+            }
+        }
+
+        if current_updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Task has been modified by another user")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        if project_id == target_project_id {
+            let error = async_graphql::Error::new("Task is already in this project")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        if let Some(reason) = move_refusal(pool, &id, series_policy).await? {
+            let error = async_graphql::Error::new(reason)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let used = project_usage_bytes(&mut *pool.acquire().await?, &target_project_id).await?;
+        let moving = subtree_attachment_bytes(pool, &id).await?;
+        if used + moving > config::PROJECT_ATTACHMENT_QUOTA_BYTES {
+            let error = async_graphql::Error::new("Project attachment storage is full")
+                .extend_with(|_, e| e.set("code", ErrorCode::QuotaExceeded.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+        let before = activity::snapshot(&mut *tx, &id).await?;
+        move_subtree(&mut tx, &id, &target_project_id).await?;
+        activity::record(&mut *tx, &id, &user_id, ActivityAction::Moved, &before).await?;
+        tx.commit().await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
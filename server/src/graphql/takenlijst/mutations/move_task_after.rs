@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::ordering::{Placement, place};
+use crate::tasks::{fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct MoveTaskAfterMutation;
+
+#[Object]
+impl MoveTaskAfterMutation {
+    /// Put a task directly after another task of the same project in the manual order
+    async fn move_task_after(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        after_id: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let mut project_ids = Vec::new();
+        for task_id in [&id, &after_id] {
+            let project_id = sqlx::query_as::<_, (String,)>(
+                "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+            )
+            .bind(task_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Task not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?
+            .0;
+            project_ids.push(project_id);
+        }
+        let project_id = &project_ids[0];
+
+        require_member(pool, &user_id, project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if project_ids[1] != *project_id {
+            let error = async_graphql::Error::new("Tasks are in different projects")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        if id == after_id {
+            let error = async_graphql::Error::new("A task cannot be moved next to itself")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+        let before = activity::snapshot(&mut tx, &id).await?;
+        place(&mut tx, &id, &after_id, Placement::After).await?;
+        activity::record(&mut tx, &id, &user_id, ActivityAction::Updated, &before).await?;
+        tx.commit().await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
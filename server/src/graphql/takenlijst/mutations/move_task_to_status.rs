@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::dependencies::open_blockers;
+use crate::tasks::series;
+use crate::tasks::subtasks::open_descendant_count;
+use crate::tasks::workflow::fetch_status;
+use crate::tasks::{TaskStatus, blocked_completion_warning, fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct MoveTaskToStatusMutation;
+
+#[Object]
+impl MoveTaskToStatusMutation {
+    /// Move a task to another status of its project's workflow. Moving into a done or
+    /// cancelled status completes or abandons the task under the same rules as
+    /// `completeTask` and `abandonTask`; moving a closed task back to an open status
+    /// reopens it.
+    async fn move_task_to_status(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        status_id: String,
+        last_known_updated_at: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task_row = sqlx::query(
+            "SELECT project_id, updated_at, status, status_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id: String = task_row.get("project_id");
+        let current_updated_at: String = task_row.get("updated_at");
+        let current_status = TaskStatus::from_db(task_row.get::<String, _>("status").as_str());
+        let current_status_id: Option<String> = task_row.get("status_id");
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let target = fetch_status(&mut *pool.acquire().await?, &status_id)
+            .await
+            .ok()
+            .filter(|s| s.project_id == project_id)
+            .ok_or_else(|| {
+                async_graphql::Error::new("Status not found in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?;
+
+        if current_updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Task has been modified by another user")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        if current_status_id.as_deref() == Some(target.id.as_str()) {
+            return Ok(fetch_task(pool, &id, tz).await?);
+        }
+
+        let new_status = target.category.task_status();
+        let mut blocker_ids = Vec::new();
+        let (sql, action) = match (current_status, new_status) {
+            (TaskStatus::Abandoned, TaskStatus::Done) => {
+                let error = async_graphql::Error::new("Cannot complete an abandoned task")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+            (TaskStatus::Done, TaskStatus::Abandoned) => {
+                let error = async_graphql::Error::new("Cannot abandon a completed task")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+            (TaskStatus::Todo, TaskStatus::Done) => {
+                if open_descendant_count(pool, &id).await? > 0 {
+                    let error = async_graphql::Error::new("Task has open subtasks")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+                let enforce_dependencies = sqlx::query_as::<_, (bool,)>(
+                    "SELECT enforce_dependencies FROM projects WHERE id = ?1",
+                )
+                .bind(&project_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+                blocker_ids = open_blockers(pool, &[id.clone()]).await?;
+                if enforce_dependencies && !blocker_ids.is_empty() {
+                    let error = async_graphql::Error::new("Task is blocked by open tasks")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+                (
+                    "UPDATE tasks SET status_id = ?1, status = 'done', completed_at = (strftime('%Y-%m-%d %H:%M:%f','now')), completed_by = ?3 WHERE id = ?2",
+                    ActivityAction::Completed,
+                )
+            }
+            (TaskStatus::Todo, TaskStatus::Abandoned) => (
+                "UPDATE tasks SET status_id = ?1, status = 'abandoned', abandoned_at = (strftime('%Y-%m-%d %H:%M:%f','now')), abandoned_by = ?3 WHERE id = ?2",
+                ActivityAction::Abandoned,
+            ),
+            (TaskStatus::Done | TaskStatus::Abandoned, TaskStatus::Todo) => (
+                "UPDATE tasks SET status_id = ?1, status = 'todo', completed_at = NULL, completed_by = NULL, \
+                 abandoned_at = NULL, abandoned_by = NULL WHERE id = ?2",
+                ActivityAction::Restored,
+            ),
+            // Same category: only the workflow status changes
+            _ => (
+                "UPDATE tasks SET status_id = ?1 WHERE id = ?2",
+                ActivityAction::Updated,
+            ),
+        };
+
+        let mut tx = pool.begin().await?;
+        let before = activity::snapshot(&mut *tx, &id).await?;
+        let mut query = sqlx::query(sql).bind(&target.id).bind(&id);
+        if matches!(
+            action,
+            ActivityAction::Completed | ActivityAction::Abandoned
+        ) {
+            query = query.bind(&user_id);
+        }
+        query.execute(&mut *tx).await?;
+        activity::record(&mut *tx, &id, &user_id, action, &before).await?;
+        if matches!(
+            action,
+            ActivityAction::Completed | ActivityAction::Abandoned
+        ) {
+            series::advance_after_completion(&mut tx, &id).await?;
+        }
+        tx.commit().await?;
+
+        let mut task = fetch_task(pool, &id, tz).await?;
+        if !blocker_ids.is_empty() {
+            task.warnings.push(blocked_completion_warning(&blocker_ids));
+        }
+        Ok(task)
+    }
+}
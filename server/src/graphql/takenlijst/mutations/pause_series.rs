@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::{RecurringSeries, fetch_series};
+use crate::tasks::series;
+
+#[derive(Default)]
+pub struct PauseSeriesMutation;
+
+#[Object]
+impl PauseSeriesMutation {
+    /// Stop a series generating tasks until it's resumed. Its untouched tasks after today are
+    /// removed.
+    async fn pause_series(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<RecurringSeries> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let Some((project_id, _dtstart_date)) = sqlx::query_as::<_, (String, String)>(
+            "SELECT project_id, dtstart_date FROM recurring_series WHERE id = ?1",
+        )
+        .bind(&id)
+        .fetch_optional(pool)
+        .await?
+        else {
+            let error = async_graphql::Error::new("Series not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        };
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; series are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let mut tx = pool.begin().await?;
+        series::pause(&mut tx, &id, Utc::now()).await?;
+        tx.commit().await?;
+
+        Ok(fetch_series(pool, &id).await?)
+    }
+}
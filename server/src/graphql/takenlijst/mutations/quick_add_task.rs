@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use super::create_recurring_series::CreateRecurringSeriesMutation;
+use super::create_task::CreateTaskMutation;
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::quick_add::preview;
+use crate::graphql::takenlijst::types::{CreateSeriesInput, CreateTaskInput, QuickAddResult};
+use crate::tasks::time_utils;
+
+#[derive(Default)]
+pub struct QuickAddTaskMutation;
+
+#[Object]
+impl QuickAddTaskMutation {
+    /// Create a task from a single line of text such as
+    /// "Take bins out tomorrow 7pm #chores @rob !high". Tags that don't exist yet are
+    /// created. A line with a recurrence ("every monday") creates a recurring series instead.
+    async fn quick_add_task(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        text: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<QuickAddResult> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let (_, now) = time_utils::now_in_timezone(tz);
+        let parsed = preview(pool, &project_id, &text, now.naive_local()).await?;
+
+        if parsed.title.is_empty() {
+            let error = async_graphql::Error::new("Title cannot be empty")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        if let (Some(username), None) = (&parsed.assignee_username, &parsed.assignee_id) {
+            let error =
+                async_graphql::Error::new(format!("No project member is called @{}", username))
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        // Tags are created with the task or series, once the rest of the line checks out
+        let tag_names: Vec<String> = parsed.tags.iter().map(|t| t.name.clone()).collect();
+
+        match parsed.rrule {
+            Some(rrule) => {
+                let input = CreateSeriesInput {
+                    project_id,
+                    title: parsed.title,
+                    description: None,
+                    assignee_id: parsed.assignee_id,
+                    default_tag_ids: None,
+                    priority: parsed.priority,
+                    estimate_minutes: None,
+                    default_reminders: None,
+                    mode: None,
+                    rrule: Some(rrule),
+                    interval_days: None,
+                    advance_on_abandon: None,
+                    dtstart_date: parsed.scheduled_date.unwrap_or_default(),
+                    dtstart_time_minutes: parsed.scheduled_time_minutes,
+                    deadline_offset_minutes: 0,
+                    timezone,
+                };
+                let series = CreateRecurringSeriesMutation
+                    .create_recurring_series_with_new_tags(ctx, input, &tag_names)
+                    .await?;
+                Ok(QuickAddResult {
+                    task: None,
+                    series: Some(series),
+                })
+            }
+            None => {
+                let input = CreateTaskInput {
+                    project_id,
+                    title: parsed.title,
+                    description: None,
+                    assignee_id: parsed.assignee_id,
+                    section_id: None,
+                    parent_task_id: None,
+                    priority: parsed.priority,
+                    estimate_minutes: None,
+                    scheduled_date: parsed.scheduled_date,
+                    scheduled_time_minutes: parsed.scheduled_time_minutes,
+                    deadline_date: None,
+                    deadline_time_minutes: None,
+                    tag_ids: None,
+                    custom_fields: None,
+                };
+                let task = CreateTaskMutation
+                    .create_task_with_new_tags(ctx, input, timezone, &tag_names)
+                    .await?;
+                Ok(QuickAddResult {
+                    task: Some(task),
+                    series: None,
+                })
+            }
+        }
+    }
+}
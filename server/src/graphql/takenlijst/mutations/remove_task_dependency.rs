@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::{fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct RemoveTaskDependencyMutation;
+
+#[Object]
+impl RemoveTaskDependencyMutation {
+    async fn remove_task_dependency(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        blocked_by_task_id: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let project_id =
+            sqlx::query_as::<_, (String,)>("SELECT project_id FROM tasks WHERE id = ?1")
+                .bind(&task_id)
+                .fetch_one(pool)
+                .await
+                .map_err(|_| {
+                    async_graphql::Error::new("Task not found")
+                        .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+                })?
+                .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let result = sqlx::query(
+            "DELETE FROM task_dependencies WHERE task_id = ?1 AND blocked_by_task_id = ?2",
+        )
+        .bind(&task_id)
+        .bind(&blocked_by_task_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            let error = async_graphql::Error::new("Dependency not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        Ok(fetch_task(pool, &task_id, tz).await?)
+    }
+}
@@ -8,6 +8,7 @@ use crate::auth::guard::require_owner;
 use crate::db::helpers::normalize_project_name;
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
 
 #[derive(Default)]
 pub struct RenameProjectMutation;
@@ -78,20 +79,6 @@ impl RenameProjectMutation {
             .await?;
 
         // Fetch updated project
-        let updated_project = sqlx::query_as::<_, (String, String, String, Option<String>, String, String)>(
-            "SELECT id, name, owner_id, archived_at, created_at, updated_at FROM projects WHERE id = ?1",
-        )
-        .bind(&project_id)
-        .fetch_one(pool)
-        .await?;
-
-        Ok(Project {
-            id: updated_project.0,
-            name: updated_project.1,
-            owner_id: updated_project.2,
-            archived_at: updated_project.3,
-            created_at: updated_project.4,
-            updated_at: updated_project.5,
-        })
+        Ok(fetch_project(pool, &project_id, &user_id).await?)
     }
 }
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::tasks::workflow::{ProjectStatus, fetch_status};
+
+#[derive(Default)]
+pub struct RenameProjectStatusMutation;
+
+#[Object]
+impl RenameProjectStatusMutation {
+    /// Rename a workflow status. Its category is fixed once created.
+    async fn rename_project_status(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        name: String,
+    ) -> async_graphql::Result<ProjectStatus> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let status = fetch_status(&mut *pool.acquire().await?, &id)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Status not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?;
+
+        require_owner(pool, &user_id, &status.project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&status.project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; statuses are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let name = normalize_project_name(&name);
+        if name.is_empty() || name.chars().count() > 60 {
+            let error =
+                async_graphql::Error::new("Status name is required and must be <= 60 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        let existing = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM project_statuses WHERE project_id = ?1 AND LOWER(TRIM(name)) = LOWER(TRIM(?2)) AND id != ?3",
+        )
+        .bind(&status.project_id)
+        .bind(&name)
+        .bind(&id)
+        .fetch_one(pool)
+        .await?
+        .0;
+        if existing > 0 {
+            let error =
+                async_graphql::Error::new("A status with this name already exists in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query("UPDATE project_statuses SET name = ?1 WHERE id = ?2")
+            .bind(&name)
+            .bind(&id)
+            .execute(pool)
+            .await?;
+
+        Ok(fetch_status(&mut *pool.acquire().await?, &id).await?)
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::error_codes::ErrorCode;
+use crate::tasks::workflow::{ProjectStatus, project_statuses};
+
+#[derive(Default)]
+pub struct ReorderProjectStatusesMutation;
+
+#[Object]
+impl ReorderProjectStatusesMutation {
+    /// Apply a new order to a project's workflow. Unlisted statuses keep their relative
+    /// order after the listed ones.
+    async fn reorder_project_statuses(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        status_ids: Vec<String>,
+    ) -> async_graphql::Result<Vec<ProjectStatus>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_owner(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; statuses are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let mut seen = HashSet::new();
+        for id in &status_ids {
+            if !seen.insert(id.as_str()) {
+                let error = async_graphql::Error::new("Duplicate status in new order")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let current = project_statuses(&mut tx, &project_id).await?;
+        if status_ids
+            .iter()
+            .any(|id| !current.iter().any(|s| &s.id == id))
+        {
+            let error = async_graphql::Error::new("Status not found in this project")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        let ordered = status_ids.iter().map(|id| id.as_str()).chain(
+            current
+                .iter()
+                .map(|s| s.id.as_str())
+                .filter(|id| !seen.contains(id)),
+        );
+
+        for (position, status_id) in ordered.enumerate() {
+            sqlx::query(
+                "UPDATE project_statuses SET position = ?1 WHERE id = ?2 AND position != ?1",
+            )
+            .bind(position as i32)
+            .bind(status_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(project_statuses(&mut *pool.acquire().await?, &project_id).await?)
+    }
+}
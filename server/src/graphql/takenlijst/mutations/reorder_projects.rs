@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::error_codes::ErrorCode;
+
+#[derive(Default)]
+pub struct ReorderProjectsMutation;
+
+#[Object]
+impl ReorderProjectsMutation {
+    /// Apply a new manual order to the current user's projects. Unlisted projects keep their
+    /// relative order after the listed ones.
+    async fn reorder_projects(
+        &self,
+        ctx: &Context<'_>,
+        project_ids: Vec<String>,
+    ) -> async_graphql::Result<bool> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let mut seen = HashSet::new();
+        for id in &project_ids {
+            if !seen.insert(id.as_str()) {
+                let error = async_graphql::Error::new("Duplicate project in new order")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
+        let mut tx = pool.begin().await?;
+
+        // All projects the user can see, in their current order (archived ones included)
+        let current = sqlx::query_as::<_, (String,)>(
+            "SELECT DISTINCT p.id FROM projects p \
+             LEFT JOIN project_members pm ON p.id = pm.project_id \
+             LEFT JOIN project_user_preferences pup ON pup.project_id = p.id AND pup.user_id = ?1 \
+             WHERE (p.owner_id = ?1 OR pm.user_id = ?1) \
+             ORDER BY pup.position IS NULL, pup.position ASC, p.created_at DESC",
+        )
+        .bind(&user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let accessible: HashSet<&str> = current.iter().map(|r| r.0.as_str()).collect();
+
+        if project_ids
+            .iter()
+            .any(|id| !accessible.contains(id.as_str()))
+        {
+            let error = async_graphql::Error::new("Project not found or access denied")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        let ordered = project_ids.iter().map(|id| id.as_str()).chain(
+            current
+                .iter()
+                .map(|r| r.0.as_str())
+                .filter(|id| !seen.contains(id)),
+        );
+
+        for (position, project_id) in ordered.enumerate() {
+            sqlx::query(
+                "INSERT INTO project_user_preferences (project_id, user_id, position) VALUES (?1, ?2, ?3) \
+                 ON CONFLICT(project_id, user_id) DO UPDATE SET position = excluded.position, updated_at = CURRENT_TIMESTAMP",
+            )
+            .bind(project_id)
+            .bind(&user_id)
+            .bind(position as i32)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+}
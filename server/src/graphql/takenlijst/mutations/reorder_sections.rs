@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Section;
+use crate::graphql::takenlijst::types::section::fetch_sections;
+
+#[derive(Default)]
+pub struct ReorderSectionsMutation;
+
+#[Object]
+impl ReorderSectionsMutation {
+    /// Apply a new order to a project's sections. Unlisted sections keep their relative
+    /// order after the listed ones.
+    async fn reorder_sections(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        section_ids: Vec<String>,
+    ) -> async_graphql::Result<Vec<Section>> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        // read-only if archived
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; sections are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let mut seen = HashSet::new();
+        for id in &section_ids {
+            if !seen.insert(id.as_str()) {
+                let error = async_graphql::Error::new("Duplicate section in new order")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
+        let mut tx = pool.begin().await?;
+
+        let current = sqlx::query_as::<_, (String,)>(
+            "SELECT id FROM sections WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        )
+        .bind(&project_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let existing: HashSet<&str> = current.iter().map(|r| r.0.as_str()).collect();
+
+        if section_ids.iter().any(|id| !existing.contains(id.as_str())) {
+            let error = async_graphql::Error::new("Section not found in this project")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        }
+
+        let ordered = section_ids.iter().map(|id| id.as_str()).chain(
+            current
+                .iter()
+                .map(|r| r.0.as_str())
+                .filter(|id| !seen.contains(id)),
+        );
+
+        for (position, section_id) in ordered.enumerate() {
+            sqlx::query(
+                "UPDATE sections SET position = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2 AND position != ?1",
+            )
+            .bind(position as i32)
+            .bind(section_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(fetch_sections(pool, &project_id).await?)
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::{fetch_task, time_utils, trash};
+
+#[derive(Default)]
+pub struct RestoreDeletedTaskMutation;
+
+#[Object]
+impl RestoreDeletedTaskMutation {
+    /// Take a task out of the trash, together with the subtasks deleted along with it
+    async fn restore_deleted_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task_row = sqlx::query(
+            "SELECT t.project_id, t.deleted_at, p.deleted_at AS parent_deleted_at FROM tasks t \
+             LEFT JOIN tasks p ON p.id = t.parent_task_id WHERE t.id = ?1",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id: String = task_row.get("project_id");
+        let deleted_at: Option<String> = task_row.get("deleted_at");
+        let parent_deleted_at: Option<String> = task_row.get("parent_deleted_at");
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if deleted_at.is_none() {
+            let error = async_graphql::Error::new("Task is not in the trash")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        // A subtask can't come back under a parent that is still deleted
+        if parent_deleted_at.is_some() {
+            let error = async_graphql::Error::new("Restore the parent task first")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let before = activity::snapshot(&mut *pool.acquire().await?, &id).await?;
+        trash::restore(&mut *pool.acquire().await?, &id).await?;
+        activity::record(
+            &mut *pool.acquire().await?,
+            &id,
+            &user_id,
+            ActivityAction::Undeleted,
+            &before,
+        )
+        .await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
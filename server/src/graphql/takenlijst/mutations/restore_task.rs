@@ -7,7 +7,8 @@ use crate::auth::Claims;
 use crate::auth::guard::require_member;
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::Task;
-use crate::tasks::{TaskStatus, time_utils};
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::{fetch_task, time_utils};
 
 #[derive(Default)]
 pub struct RestoreTaskMutation;
@@ -38,7 +39,7 @@ impl RestoreTaskMutation {
             .0;
 
         let task_row =
-            sqlx::query("SELECT id, project_id, updated_at, status FROM tasks WHERE id = ?1")
+            sqlx::query("SELECT id, project_id, updated_at, status FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
                 .bind(&id)
                 .fetch_one(pool)
                 .await
@@ -76,64 +77,21 @@ impl RestoreTaskMutation {
             return Err(error);
         }
 
+        let before = activity::snapshot(&mut *pool.acquire().await?, &id).await?;
         sqlx::query("UPDATE tasks SET status = 'todo', abandoned_at = NULL, abandoned_by = NULL WHERE id = ?1")
             .bind(&id)
             .execute(pool)
             .await?;
 
-        let row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1")
-            .bind(&id)
-            .fetch_one(pool)
-            .await?;
-
-        let status: String = row.get("status");
-        let status = match status.as_str() {
-            "todo" => TaskStatus::Todo,
-            "done" => TaskStatus::Done,
-            "abandoned" => TaskStatus::Abandoned,
-            _ => TaskStatus::Todo,
-        };
-        let scheduled_date: Option<String> = row.get("scheduled_date");
-        let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-        let deadline_date: Option<String> = row.get("deadline_date");
-        let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
-
-        let is_overdue = time_utils::is_task_overdue(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-        let bucket = time_utils::get_task_bucket(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
+        activity::record(
+            &mut *pool.acquire().await?,
+            &id,
+            &user_id,
+            ActivityAction::Restored,
+            &before,
+        )
+        .await?;
 
-        Ok(Task {
-            id: row.get("id"),
-            project_id: row.get("project_id"),
-            author_id: row.get("author_id"),
-            assignee_id: row.get("assignee_id"),
-            series_id: row.get("series_id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status,
-            scheduled_date,
-            scheduled_time_minutes,
-            deadline_date,
-            deadline_time_minutes,
-            completed_at: row.get("completed_at"),
-            completed_by: row.get("completed_by"),
-            abandoned_at: row.get("abandoned_at"),
-            abandoned_by: row.get("abandoned_by"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_overdue,
-            bucket,
-        })
+        Ok(fetch_task(pool, &id, tz).await?)
     }
 }
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::ProjectTemplate;
+use crate::tasks::template::ProjectSnapshot;
+
+#[derive(Default)]
+pub struct SaveProjectAsTemplateMutation;
+
+#[Object]
+impl SaveProjectAsTemplateMutation {
+    async fn save_project_as_template(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        name: String,
+    ) -> async_graphql::Result<ProjectTemplate> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        // Validate and normalize name
+        let normalized_name = normalize_project_name(&name);
+        if normalized_name.is_empty() {
+            let error = async_graphql::Error::new("Template name cannot be empty")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        if normalized_name.len() > 60 {
+            let error = async_graphql::Error::new("Template name cannot exceed 60 characters")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        // Check for unique name per owner (case-insensitive)
+        let existing_count = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM project_templates WHERE owner_id = ?1 AND LOWER(TRIM(name)) = LOWER(TRIM(?2))",
+        )
+        .bind(&user_id)
+        .bind(&normalized_name)
+        .fetch_one(pool)
+        .await?;
+
+        if existing_count.0 > 0 {
+            let error = async_graphql::Error::new("A template with this name already exists")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let snapshot = ProjectSnapshot::capture(pool, &project_id).await?;
+        let payload = serde_json::to_string(&snapshot).map_err(|_| {
+            async_graphql::Error::new("Failed to serialize template")
+                .extend_with(|_, e| e.set("code", ErrorCode::Internal.as_str()))
+        })?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO project_templates (id, owner_id, name, source_project_id, payload) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(&id)
+        .bind(&user_id)
+        .bind(&normalized_name)
+        .bind(&project_id)
+        .bind(&payload)
+        .execute(pool)
+        .await?;
+
+        // Fetch the created template
+        let template = sqlx::query_as::<_, (String, String, String, Option<String>, String, String, String)>(
+            "SELECT id, name, owner_id, source_project_id, payload, created_at, updated_at FROM project_templates WHERE id = ?1",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await?;
+
+        ProjectTemplate::from_row(template).map_err(|_| {
+            async_graphql::Error::new("Invalid template payload")
+                .extend_with(|_, e| e.set("code", ErrorCode::Internal.as_str()))
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
+
+#[derive(Default)]
+pub struct SetProjectDependencyEnforcementMutation;
+
+#[Object]
+impl SetProjectDependencyEnforcementMutation {
+    /// When enforced, `completeTask` refuses tasks that still have open blockers
+    async fn set_project_dependency_enforcement(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        enforce: bool,
+    ) -> async_graphql::Result<Project> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Check permission (only owner can change project settings)
+        require_owner(pool, &user_id, &project_id).await?;
+
+        sqlx::query(
+            "UPDATE projects SET enforce_dependencies = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        )
+        .bind(enforce)
+        .bind(&project_id)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_project(pool, &project_id, &user_id).await?)
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
+
+#[derive(Default)]
+pub struct SetProjectFavoriteMutation;
+
+#[Object]
+impl SetProjectFavoriteMutation {
+    /// Mark or unmark a project as a favourite for the current user
+    async fn set_project_favorite(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        is_favorite: bool,
+    ) -> async_graphql::Result<Project> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Favourites are personal, so any member may set them
+        require_member(pool, &user_id, &project_id).await?;
+
+        sqlx::query(
+            "INSERT INTO project_user_preferences (project_id, user_id, is_favorite) VALUES (?1, ?2, ?3) \
+             ON CONFLICT(project_id, user_id) DO UPDATE SET is_favorite = excluded.is_favorite, updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(&project_id)
+        .bind(&user_id)
+        .bind(is_favorite)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_project(pool, &project_id, &user_id).await?)
+    }
+}
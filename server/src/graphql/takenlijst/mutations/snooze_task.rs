@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::{Row, SqlitePool};
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::snooze::{self, SnoozePreset};
+use crate::tasks::{TaskTimes, fetch_task, time_utils};
+
+#[derive(Default)]
+pub struct SnoozeTaskMutation;
+
+#[Object]
+impl SnoozeTaskMutation {
+    /// Move an open task to a preset time in `timezone`. The deadline moves with the
+    /// scheduled date; a task with only a deadline has its deadline moved. Presets such as
+    /// `TOMORROW_MORNING` can land before a task's current date, so like other task writes
+    /// this takes `lastKnownUpdatedAt`.
+    async fn snooze_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        last_known_updated_at: String,
+        preset: SnoozePreset,
+        duration_minutes: Option<i32>,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let task_row = sqlx::query(
+            "SELECT project_id, status, updated_at, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes \
+             FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?;
+        let project_id: String = task_row.get("project_id");
+        let status: String = task_row.get("status");
+        let current_updated_at: String = task_row.get("updated_at");
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if current_updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Task has been modified by another user")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        if status != "todo" {
+            let error = async_graphql::Error::new("Only open tasks can be snoozed")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let (_, now) = time_utils::now_in_timezone(tz);
+        let target =
+            snooze::resolve(preset, now.naive_local(), duration_minutes).map_err(|message| {
+                async_graphql::Error::new(message)
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+            })?;
+        let times = TaskTimes {
+            scheduled_date: task_row.get("scheduled_date"),
+            scheduled_time_minutes: task_row.get("scheduled_time_minutes"),
+            deadline_date: task_row.get("deadline_date"),
+            deadline_time_minutes: task_row.get("deadline_time_minutes"),
+        };
+        let snoozed = snooze::apply(target, &times);
+
+        let mut tx = pool.begin().await?;
+        let before = activity::snapshot(&mut tx, &id).await?;
+        sqlx::query(
+            "UPDATE tasks SET scheduled_date = ?1, scheduled_time_minutes = ?2, deadline_date = ?3, \
+             deadline_time_minutes = ?4, snooze_count = snooze_count + 1, \
+             last_snoozed_at = (strftime('%Y-%m-%d %H:%M:%f','now')) WHERE id = ?5",
+        )
+        .bind(&snoozed.scheduled_date)
+        .bind(snoozed.scheduled_time_minutes)
+        .bind(&snoozed.deadline_date)
+        .bind(snoozed.deadline_time_minutes)
+        .bind(&id)
+        .execute(&mut *tx)
+        .await?;
+        activity::record(&mut tx, &id, &user_id, ActivityAction::Updated, &before).await?;
+        tx.commit().await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
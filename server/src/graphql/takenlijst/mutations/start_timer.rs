@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::tasks::time_tracking::{TimeEntry, fetch_time_entry, stop_running};
+use crate::tasks::time_utils::to_db;
+
+#[derive(Default)]
+pub struct StartTimerMutation;
+
+#[Object]
+impl StartTimerMutation {
+    /// Start tracking time on a task. A timer already running for the caller, on any task,
+    /// is stopped first.
+    async fn start_timer(
+        &self,
+        ctx: &Context<'_>,
+        task_id: String,
+        note: Option<String>,
+    ) -> async_graphql::Result<TimeEntry> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let project_id = sqlx::query_as::<_, (String,)>(
+            "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&task_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?
+        .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+        let id = uuid::Uuid::new_v4().to_string();
+
+        let mut tx = pool.begin().await?;
+        stop_running(&mut tx, &user_id).await?;
+        sqlx::query(
+            "INSERT INTO time_entries (id, task_id, user_id, started_at, note) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )
+        .bind(&id)
+        .bind(&task_id)
+        .bind(&user_id)
+        .bind(to_db(Utc::now()))
+        .bind(&note)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(fetch_time_entry(pool, &id).await?)
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::error_codes::ErrorCode;
+use crate::tasks::time_tracking::{TimeEntry, fetch_time_entry, stop_running};
+
+#[derive(Default)]
+pub struct StopTimerMutation;
+
+#[Object]
+impl StopTimerMutation {
+    /// Stop the caller's running timer
+    async fn stop_timer(&self, ctx: &Context<'_>) -> async_graphql::Result<TimeEntry> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let stopped = stop_running(&mut *pool.acquire().await?, &user_id).await?;
+        let Some(id) = stopped else {
+            let error = async_graphql::Error::new("No timer is running")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        };
+
+        Ok(fetch_time_entry(pool, &id).await?)
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Task;
+use crate::tasks::{fetch_task, time_utils, watchers};
+
+#[derive(Default)]
+pub struct UnwatchTaskMutation;
+
+#[Object]
+impl UnwatchTaskMutation {
+    /// Stop following a task. Authors and assignees may unwatch too; being assigned the task
+    /// again makes them a watcher again.
+    async fn unwatch_task(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<Task> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let project_id = sqlx::query_as::<_, (String,)>(
+            "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| {
+            async_graphql::Error::new("Task not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        })?
+        .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        watchers::unwatch(&mut *pool.acquire().await?, &id, &user_id).await?;
+
+        Ok(fetch_task(pool, &id, tz).await?)
+    }
+}
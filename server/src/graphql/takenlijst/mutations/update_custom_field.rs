@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::db::helpers::normalize_project_name;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::UpdateCustomFieldInput;
+use crate::tasks::custom_fields::{CustomField, check_rules, clean_options, fetch_field};
+
+#[derive(Default)]
+pub struct UpdateCustomFieldMutation;
+
+#[Object]
+impl UpdateCustomFieldMutation {
+    /// Rename a custom field or change its rules. Only the owner can change fields.
+    async fn update_custom_field(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateCustomFieldInput,
+    ) -> async_graphql::Result<CustomField> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let field = fetch_field(&mut *pool.acquire().await?, &id)
+            .await
+            .map_err(|_| {
+                async_graphql::Error::new("Custom field not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+            })?;
+
+        require_owner(pool, &user_id, &field.project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&field.project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; fields are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        let name = match &input.name {
+            Some(name) => normalize_project_name(name),
+            None => field.name.clone(),
+        };
+        if name.is_empty() || name.chars().count() > 60 {
+            let error =
+                async_graphql::Error::new("Field name is required and must be <= 60 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        let existing = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM custom_fields WHERE project_id = ?1 AND LOWER(TRIM(name)) = LOWER(TRIM(?2)) AND id != ?3",
+        )
+        .bind(&field.project_id)
+        .bind(&name)
+        .bind(&id)
+        .fetch_one(pool)
+        .await?
+        .0;
+        if existing > 0 {
+            let error =
+                async_graphql::Error::new("A field with this name already exists in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let options = match &input.options {
+            Some(options) => clean_options(options),
+            None => field.options.clone(),
+        };
+        let (mut min_value, mut max_value, mut max_length) =
+            (field.min_value, field.max_value, field.max_length);
+        input.min_value.update_to(&mut min_value);
+        input.max_value.update_to(&mut max_value);
+        input.max_length.update_to(&mut max_length);
+        check_rules(field.kind, &options, min_value, max_value, max_length).map_err(|message| {
+            async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+        })?;
+
+        let options = (!options.is_empty())
+            .then(|| serde_json::to_string(&options))
+            .transpose()?;
+        sqlx::query(
+            "UPDATE custom_fields SET name = ?1, options = ?2, min_value = ?3, max_value = ?4, max_length = ?5 WHERE id = ?6",
+        )
+        .bind(&name)
+        .bind(options)
+        .bind(min_value)
+        .bind(max_value)
+        .bind(max_length)
+        .bind(&id)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_field(&mut *pool.acquire().await?, &id).await?)
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_owner;
+use crate::db::helpers::normalize_project_color;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::fetch_project;
+
+#[derive(Default)]
+pub struct UpdateProjectAppearanceMutation;
+
+#[Object]
+impl UpdateProjectAppearanceMutation {
+    /// Set the colour and icon shown for a project. Passing null clears the value.
+    async fn update_project_appearance(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        color: Option<String>,
+        icon: Option<String>,
+        last_known_updated_at: String,
+    ) -> async_graphql::Result<Project> {
+        // Require authentication
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        // Validate colour
+        let normalized_color = match &color {
+            Some(c) => match normalize_project_color(c) {
+                Some(c) => Some(c),
+                None => {
+                    let error = async_graphql::Error::new("Color must be a hex value like #1e90ff")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+            },
+            None => None,
+        };
+
+        // Validate icon
+        let normalized_icon = match &icon {
+            Some(i) => {
+                let trimmed = i.trim().to_string();
+                if trimmed.is_empty() || trimmed.chars().count() > 16 {
+                    let error = async_graphql::Error::new("Icon must be 1-16 characters")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                    return Err(error);
+                }
+                Some(trimmed)
+            }
+            None => None,
+        };
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Check permission (only owner can change appearance)
+        require_owner(pool, &user_id, &project_id).await?;
+
+        // Check for stale write
+        let current =
+            sqlx::query_as::<_, (String,)>("SELECT updated_at FROM projects WHERE id = ?1")
+                .bind(&project_id)
+                .fetch_one(pool)
+                .await?;
+        if current.0 != last_known_updated_at {
+            let error = async_graphql::Error::new("Project has been modified by another user")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        sqlx::query(
+            "UPDATE projects SET color = ?1, icon = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?3",
+        )
+        .bind(&normalized_color)
+        .bind(&normalized_icon)
+        .bind(&project_id)
+        .execute(pool)
+        .await?;
+
+        // Fetch updated project
+        Ok(fetch_project(pool, &project_id, &user_id).await?)
+    }
+}
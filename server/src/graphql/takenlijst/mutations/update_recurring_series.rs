@@ -0,0 +1,296 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, MaybeUndefined, Object};
+use chrono::{NaiveDate, Utc};
+use rrule::RRule;
+use sqlx::SqlitePool;
+
+use super::update_task::UpdateTaskMutation;
+use crate::auth::Claims;
+use crate::auth::guard::{is_member, require_member};
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::{
+    RecurringSeries, UpdateSeriesInput, UpdateTaskInput, fetch_series,
+};
+use crate::tasks::series::{self, SeriesEditScope, SeriesMode};
+
+#[derive(Default)]
+pub struct UpdateRecurringSeriesMutation;
+
+#[Object]
+impl UpdateRecurringSeriesMutation {
+    /// Edit one occurrence, an occurrence and the ones after it, or the whole series. Future
+    /// tasks nobody has touched are regenerated; edited ones are kept as exceptions. Returns
+    /// the series that now holds the edited occurrences.
+    async fn update_recurring_series(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        #[graphql(default_with = "SeriesEditScope::All")] scope: SeriesEditScope,
+        task_id: Option<String>,
+        input: UpdateSeriesInput,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<RecurringSeries> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let Some((project_id, mode, rrule, dtstart_date, dtstart_time_minutes, series_tz)) =
+            sqlx::query_as::<_, (String, String, String, String, Option<i32>, String)>(
+                "SELECT project_id, mode, rrule, dtstart_date, dtstart_time_minutes, timezone \
+                 FROM recurring_series WHERE id = ?1",
+            )
+            .bind(&id)
+            .fetch_optional(pool)
+            .await?
+        else {
+            let error = async_graphql::Error::new("Series not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+            return Err(error);
+        };
+        let mode = SeriesMode::from_db(&mode);
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; series are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        // validate
+        if let Some(ref title) = input.title {
+            let t = title.trim();
+            if t.is_empty() || t.len() > 120 {
+                let error =
+                    async_graphql::Error::new("Title is required and must be <= 120 characters")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+        if let Some(ref desc) = input.description {
+            if desc.len() > 5000 {
+                let error = async_graphql::Error::new("Description must be <= 5000 characters")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+        if let MaybeUndefined::Value(m) = input.estimate_minutes {
+            if !(1..=525600).contains(&m) {
+                let error =
+                    async_graphql::Error::new("estimateMinutes must be between 1 and 525600")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+        if let Some(ref assignee_id) = input.assignee_id {
+            if !is_member(pool, assignee_id, &project_id).await? {
+                let error = async_graphql::Error::new("Assignee must be a project member")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+        for tag_id in input.default_tag_ids.iter().flatten() {
+            let (exists,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tags WHERE id = ?1")
+                .bind(tag_id)
+                .fetch_one(pool)
+                .await?;
+            if exists == 0 {
+                let error = async_graphql::Error::new("One or more tags not found")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+        }
+        let rule_change = input.rrule.is_some() || input.interval_days.is_some();
+        if input.rrule.is_some() && mode != SeriesMode::Calendar {
+            let error =
+                async_graphql::Error::new("An after-completion series repeats on intervalDays")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        if input.interval_days.is_some() && mode != SeriesMode::AfterCompletion {
+            let error = async_graphql::Error::new("A calendar series repeats on its rrule")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        if input
+            .interval_days
+            .is_some_and(|days| !(1..=3650).contains(&days))
+        {
+            let error = async_graphql::Error::new("intervalDays must be between 1 and 3650")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+        if input
+            .rrule
+            .as_ref()
+            .is_some_and(|r| r.parse::<RRule<rrule::Unvalidated>>().is_err())
+        {
+            let error = async_graphql::Error::new("Invalid RRULE format")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        // The occurrence the edit starts from
+        let occurrence = match (scope, &task_id) {
+            (SeriesEditScope::All, _) => None,
+            (_, None) => {
+                let error = async_graphql::Error::new("taskId is required for this scope")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+            (_, Some(task_id)) => {
+                let row = sqlx::query_as::<_, (Option<String>, String)>(
+                    "SELECT occurrence_date, updated_at FROM tasks \
+                     WHERE id = ?1 AND series_id = ?2 AND deleted_at IS NULL",
+                )
+                .bind(task_id)
+                .bind(&id)
+                .fetch_optional(pool)
+                .await?;
+                match row {
+                    Some((Some(occurrence_date), updated_at)) => Some((
+                        task_id.clone(),
+                        NaiveDate::parse_from_str(&occurrence_date, "%Y-%m-%d")?,
+                        updated_at,
+                    )),
+                    _ => {
+                        let error =
+                            async_graphql::Error::new("Task is not an occurrence of this series")
+                                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                        return Err(error);
+                    }
+                }
+            }
+        };
+
+        // A single occurrence is just its task, edited like any other
+        if scope == SeriesEditScope::This {
+            if rule_change {
+                let error =
+                    async_graphql::Error::new("The rule can't change for a single occurrence")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+            let (task_id, _, updated_at) = occurrence.expect("checked above");
+            let task_input = UpdateTaskInput {
+                title: input.title,
+                description: input.description,
+                assignee_id: input.assignee_id,
+                priority: input.priority,
+                estimate_minutes: input.estimate_minutes,
+                tag_ids: input.default_tag_ids,
+                ..Default::default()
+            };
+            UpdateTaskMutation
+                .update_task(ctx, task_id, task_input, updated_at, timezone)
+                .await?;
+            return Ok(fetch_series(pool, &id).await?);
+        }
+
+        // Splitting at the first occurrence is the same as editing the whole series
+        let split_at = occurrence
+            .map(|(_, date, _)| date)
+            .filter(|date| date.format("%Y-%m-%d").to_string() > dtstart_date);
+
+        let new_rrule = match (input.rrule, input.interval_days) {
+            (Some(rrule), _) => Some(series::normalize_rrule(
+                &rrule,
+                dtstart_time_minutes.is_some(),
+            )),
+            (None, Some(days)) => {
+                let rrule_for_days = format!("FREQ=DAILY;INTERVAL={}", days);
+                Some(match series::until_date(&rrule, &series_tz) {
+                    Some(until) => series::with_until(&rrule_for_days, until, &series_tz),
+                    None => rrule_for_days,
+                })
+            }
+            (None, None) => None,
+        };
+        if let Some(ref new_rrule) = new_rrule {
+            let from = split_at
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or(dtstart_date);
+            series::rule_set(new_rrule, &from, dtstart_time_minutes, &series_tz).map_err(
+                |message| {
+                    async_graphql::Error::new(message)
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+                },
+            )?;
+        }
+
+        let mut tx = pool.begin().await?;
+        let target_id = match split_at {
+            Some(date) => series::split(&mut tx, &id, date, &user_id).await?,
+            None => id,
+        };
+
+        sqlx::query(
+            "UPDATE recurring_series SET title = COALESCE(?1, title), description = COALESCE(?2, description), \
+             assignee_id = COALESCE(?3, assignee_id), priority = COALESCE(?4, priority), rrule = COALESCE(?5, rrule), \
+             interval_days = COALESCE(?6, interval_days), updated_at = CURRENT_TIMESTAMP WHERE id = ?7",
+        )
+        .bind(input.title.as_deref().map(str::trim))
+        .bind(&input.description)
+        .bind(&input.assignee_id)
+        .bind(input.priority.map(|p| p.as_db()))
+        .bind(&new_rrule)
+        .bind(input.interval_days)
+        .bind(&target_id)
+        .execute(&mut *tx)
+        .await?;
+        match input.estimate_minutes {
+            MaybeUndefined::Value(m) => {
+                sqlx::query("UPDATE recurring_series SET estimate_minutes = ?1 WHERE id = ?2")
+                    .bind(m)
+                    .bind(&target_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Null => {
+                sqlx::query("UPDATE recurring_series SET estimate_minutes = NULL WHERE id = ?1")
+                    .bind(&target_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Undefined => {}
+        }
+        if let Some(tag_ids) = &input.default_tag_ids {
+            sqlx::query("DELETE FROM recurring_series_tags WHERE series_id = ?1")
+                .bind(&target_id)
+                .execute(&mut *tx)
+                .await?;
+            for tag_id in tag_ids {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO recurring_series_tags (series_id, tag_id) VALUES (?1, ?2)",
+                )
+                .bind(&target_id)
+                .bind(tag_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        series::regenerate(&mut tx, &target_id, Utc::now()).await?;
+        tx.commit().await?;
+
+        Ok(fetch_series(pool, &target_id).await?)
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use async_graphql::{Context, ErrorExtensions, Object};
+use async_graphql::{Context, ErrorExtensions, MaybeUndefined, Object, Value};
 use sqlx::{Row, SqlitePool};
 
 use crate::auth::Claims;
@@ -8,18 +8,19 @@ use crate::auth::guard::{is_member, require_member};
 use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::Task;
 use crate::graphql::takenlijst::types::UpdateTaskInput;
-use crate::tasks::{TaskStatus, time_utils};
+use crate::tasks::activity::{self, ActivityAction};
+use crate::tasks::{custom_fields, fetch_task, subtasks, time_utils, versions};
 
 #[derive(Default)]
 pub struct UpdateTaskMutation;
 
 #[Object]
 impl UpdateTaskMutation {
-    async fn update_task(
+    pub async fn update_task(
         &self,
         ctx: &Context<'_>,
         id: String,
-        input: UpdateTaskInput,
+        mut input: UpdateTaskInput,
         last_known_updated_at: String,
         #[graphql(default = "UTC")] timezone: String,
     ) -> async_graphql::Result<Task> {
@@ -41,7 +42,7 @@ impl UpdateTaskMutation {
             .0;
 
         // Load task and project
-        let task_row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1")
+        let task_row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1 AND deleted_at IS NULL")
             .bind(&id)
             .fetch_one(pool)
             .await
@@ -64,14 +65,6 @@ impl UpdateTaskMutation {
             return Err(error);
         }
 
-        // concurrency check
-        let current_updated_at: String = task_row.get("updated_at");
-        if current_updated_at != last_known_updated_at {
-            let error = async_graphql::Error::new("Task has been modified by another user")
-                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
-            return Err(error);
-        }
-
         // validate
         if let Some(ref title) = input.title {
             let t = title.trim();
@@ -113,6 +106,14 @@ impl UpdateTaskMutation {
                 return Err(error);
             }
         }
+        if let MaybeUndefined::Value(m) = input.estimate_minutes {
+            if !(1..=525600).contains(&m) {
+                let error =
+                    async_graphql::Error::new("estimateMinutes must be between 1 and 525600")
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
         if let Some(date) = &input.scheduled_date {
             if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
                 let error = async_graphql::Error::new("Invalid scheduledDate format")
@@ -137,6 +138,56 @@ impl UpdateTaskMutation {
             }
         }
 
+        // section must belong to the task's project
+        if let MaybeUndefined::Value(section_id) = &input.section_id {
+            let in_project = sqlx::query_as::<_, (i64,)>(
+                "SELECT COUNT(*) FROM sections WHERE id = ?1 AND project_id = ?2",
+            )
+            .bind(section_id)
+            .bind(&project_id)
+            .fetch_one(pool)
+            .await?
+            .0;
+            if in_project == 0 {
+                let error = async_graphql::Error::new("Section not found in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+        }
+
+        // new parent must be in the same project, outside this task's own subtree,
+        // and leave room for the subtree below it
+        if let MaybeUndefined::Value(parent_id) = &input.parent_task_id {
+            let in_project = sqlx::query_as::<_, (i64,)>(
+                "SELECT COUNT(*) FROM tasks WHERE id = ?1 AND project_id = ?2 AND deleted_at IS NULL",
+            )
+            .bind(parent_id)
+            .bind(&project_id)
+            .fetch_one(pool)
+            .await?
+            .0;
+            if in_project == 0 {
+                let error = async_graphql::Error::new("Parent task not found in this project")
+                    .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()));
+                return Err(error);
+            }
+            if subtasks::subtree_ids(pool, &id).await?.contains(parent_id) {
+                let error = async_graphql::Error::new("A task cannot be nested under itself")
+                    .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+            let parent_level = subtasks::task_level(pool, parent_id).await?;
+            let height = subtasks::subtree_height(pool, &id).await?;
+            if !subtasks::fits_under(parent_level, height) {
+                let error = async_graphql::Error::new(format!(
+                    "Subtasks cannot be nested more than {} levels deep",
+                    subtasks::MAX_TASK_LEVELS
+                ))
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+                return Err(error);
+            }
+        }
+
         // tags exist
         if let Some(tag_ids) = &input.tag_ids {
             if !tag_ids.is_empty() {
@@ -155,54 +206,160 @@ impl UpdateTaskMutation {
             }
         }
 
+        // custom field values must suit the project's fields
+        let mut custom_values = match &input.custom_fields {
+            Some(values) => {
+                let fields =
+                    custom_fields::project_fields(&mut *pool.acquire().await?, &project_id).await?;
+                let values: Vec<(String, Option<String>)> = values
+                    .iter()
+                    .map(|v| (v.field_id.clone(), v.value.clone()))
+                    .collect();
+                custom_fields::parse_values(&fields, &values).map_err(|message| {
+                    async_graphql::Error::new(message)
+                        .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        // The check, the merge and the write see the same version of the task
+        let mut tx = pool.begin().await?;
+        let before = activity::snapshot(&mut tx, &id).await?;
+
+        // concurrency check: a write against an older version only applies the fields that
+        // haven't been changed since, and is rejected if any of them has been changed too
+        let (current_updated_at,) =
+            sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = ?1")
+                .bind(&id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if current_updated_at != last_known_updated_at {
+            let base = versions::fetch(&mut tx, &id, &last_known_updated_at).await?;
+            let merged = versions::merge(base.as_ref(), &before, &input.changes(&custom_values));
+            if !merged.conflicts.is_empty() {
+                let conflicts = Value::from_json(serde_json::to_value(&merged.conflicts)?)?;
+                let current = Value::from_json(serde_json::Value::Object(before))?;
+                let error = async_graphql::Error::new("Task has been modified by another user")
+                    .extend_with(|_, e| {
+                        e.set("code", ErrorCode::ConflictStaleWrite.as_str());
+                        e.set("conflicts", conflicts.clone());
+                        e.set("current", current.clone());
+                        e.set("currentUpdatedAt", current_updated_at.clone());
+                    });
+                return Err(error);
+            }
+            input.retain(&merged.apply);
+            custom_values.retain(|(field_id, _)| {
+                merged
+                    .apply
+                    .contains(&custom_fields::snapshot_key(field_id))
+            });
+        }
+
         // Apply updates
         if let Some(t) = input.title.as_ref().map(|s| s.trim().to_string()) {
             sqlx::query("UPDATE tasks SET title = ?1 WHERE id = ?2")
                 .bind(&t)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
         if let Some(d) = &input.description {
             sqlx::query("UPDATE tasks SET description = ?1 WHERE id = ?2")
                 .bind(d)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
         if let Some(a) = &input.assignee_id {
             sqlx::query("UPDATE tasks SET assignee_id = ?1 WHERE id = ?2")
                 .bind(a)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
+                .await?;
+        }
+        match &input.section_id {
+            MaybeUndefined::Value(section_id) => {
+                sqlx::query("UPDATE tasks SET section_id = ?1 WHERE id = ?2")
+                    .bind(section_id)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Null => {
+                sqlx::query("UPDATE tasks SET section_id = NULL WHERE id = ?1")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Undefined => {}
+        }
+        match &input.parent_task_id {
+            MaybeUndefined::Value(parent_id) => {
+                sqlx::query("UPDATE tasks SET parent_task_id = ?1 WHERE id = ?2")
+                    .bind(parent_id)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Null => {
+                sqlx::query("UPDATE tasks SET parent_task_id = NULL WHERE id = ?1")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Undefined => {}
+        }
+        if let Some(priority) = input.priority {
+            sqlx::query("UPDATE tasks SET priority = ?1 WHERE id = ?2")
+                .bind(priority.as_db())
+                .bind(&id)
+                .execute(&mut *tx)
                 .await?;
         }
+        match input.estimate_minutes {
+            MaybeUndefined::Value(m) => {
+                sqlx::query("UPDATE tasks SET estimate_minutes = ?1 WHERE id = ?2")
+                    .bind(m)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Null => {
+                sqlx::query("UPDATE tasks SET estimate_minutes = NULL WHERE id = ?1")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            MaybeUndefined::Undefined => {}
+        }
         if let Some(sd) = &input.scheduled_date {
             sqlx::query("UPDATE tasks SET scheduled_date = ?1 WHERE id = ?2")
                 .bind(sd)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
         if let Some(stm) = input.scheduled_time_minutes {
             sqlx::query("UPDATE tasks SET scheduled_time_minutes = ?1 WHERE id = ?2")
                 .bind(stm)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
         if let Some(dd) = &input.deadline_date {
             sqlx::query("UPDATE tasks SET deadline_date = ?1 WHERE id = ?2")
                 .bind(dd)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
         if let Some(dtm) = input.deadline_time_minutes {
             sqlx::query("UPDATE tasks SET deadline_time_minutes = ?1 WHERE id = ?2")
                 .bind(dtm)
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
         }
 
@@ -210,71 +367,22 @@ impl UpdateTaskMutation {
         if let Some(tag_ids) = &input.tag_ids {
             sqlx::query("DELETE FROM task_tags WHERE task_id = ?1")
                 .bind(&id)
-                .execute(pool)
+                .execute(&mut *tx)
                 .await?;
             for tag_id in tag_ids {
                 sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)")
                     .bind(&id)
                     .bind(tag_id)
-                    .execute(pool)
+                    .execute(&mut *tx)
                     .await?;
             }
         }
 
-        // Fetch updated
-        let row = sqlx::query("SELECT id, project_id, author_id, assignee_id, series_id, title, description, status, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, completed_at, completed_by, abandoned_at, abandoned_by, created_at, updated_at FROM tasks WHERE id = ?1")
-            .bind(&id)
-            .fetch_one(pool)
-            .await?;
-
-        let status: String = row.get("status");
-        let status = match status.as_str() {
-            "todo" => TaskStatus::Todo,
-            "done" => TaskStatus::Done,
-            "abandoned" => TaskStatus::Abandoned,
-            _ => TaskStatus::Todo,
-        };
-        let scheduled_date: Option<String> = row.get("scheduled_date");
-        let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-        let deadline_date: Option<String> = row.get("deadline_date");
-        let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
+        custom_fields::store_values(&mut tx, &id, &custom_values).await?;
 
-        let is_overdue = time_utils::is_task_overdue(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
-        let bucket = time_utils::get_task_bucket(
-            scheduled_date.as_deref(),
-            scheduled_time_minutes,
-            deadline_date.as_deref(),
-            deadline_time_minutes,
-            tz,
-        );
+        activity::record(&mut tx, &id, &user_id, ActivityAction::Updated, &before).await?;
+        tx.commit().await?;
 
-        Ok(Task {
-            id: row.get("id"),
-            project_id: row.get("project_id"),
-            author_id: row.get("author_id"),
-            assignee_id: row.get("assignee_id"),
-            series_id: row.get("series_id"),
-            title: row.get("title"),
-            description: row.get("description"),
-            status,
-            scheduled_date,
-            scheduled_time_minutes,
-            deadline_date,
-            deadline_time_minutes,
-            completed_at: row.get("completed_at"),
-            completed_by: row.get("completed_by"),
-            abandoned_at: row.get("abandoned_at"),
-            abandoned_by: row.get("abandoned_by"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_overdue,
-            bucket,
-        })
+        Ok(fetch_task(pool, &id, tz).await?)
     }
 }
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use async_graphql::{Context, ErrorExtensions, MaybeUndefined, Object};
+use sqlx::SqlitePool;
+
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::tasks::time_tracking::{TimeEntry, fetch_time_entry, validate_period};
+use crate::tasks::time_utils::{parse_db, parse_instant, to_db};
+
+#[derive(Default)]
+pub struct UpdateTimeEntryMutation;
+
+#[Object]
+impl UpdateTimeEntryMutation {
+    /// Correct one of the caller's own time entries. Setting `endedAt` on a running timer
+    /// stops it; a null `note` clears the note.
+    async fn update_time_entry(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        started_at: Option<String>,
+        ended_at: Option<String>,
+        note: MaybeUndefined<String>,
+        last_known_updated_at: String,
+    ) -> async_graphql::Result<TimeEntry> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let not_found = || {
+            async_graphql::Error::new("Time entry not found")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        };
+        let entry = fetch_time_entry(pool, &id).await.map_err(|_| not_found())?;
+        let project_id = sqlx::query_as::<_, (String,)>(
+            "SELECT project_id FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        )
+        .bind(&entry.task_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(not_found)?
+        .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let archived = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT archived_at FROM projects WHERE id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_one(pool)
+        .await?;
+        if archived.0.is_some() {
+            let error = async_graphql::Error::new("Project is archived; tasks are read-only")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if entry.user_id != user_id {
+            let error = async_graphql::Error::new("Only your own time entries can be edited")
+                .extend_with(|_, e| e.set("code", ErrorCode::PermissionDenied.as_str()));
+            return Err(error);
+        }
+
+        if entry.updated_at != last_known_updated_at {
+            let error = async_graphql::Error::new("Time entry has been modified")
+                .extend_with(|_, e| e.set("code", ErrorCode::ConflictStaleWrite.as_str()));
+            return Err(error);
+        }
+
+        let invalid = || {
+            async_graphql::Error::new("Invalid timestamp")
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+        };
+        let start = match started_at {
+            Some(value) => parse_instant(&value).ok_or_else(invalid)?,
+            None => parse_db(&entry.started_at).ok_or_else(invalid)?,
+        };
+        let end = match ended_at {
+            Some(value) => Some(parse_instant(&value).ok_or_else(invalid)?),
+            None => entry.ended_at.as_deref().and_then(parse_db),
+        };
+        if let Err(message) = validate_period(start, end) {
+            let error = async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()));
+            return Err(error);
+        }
+
+        let note = match note {
+            MaybeUndefined::Undefined => entry.note,
+            MaybeUndefined::Null => None,
+            MaybeUndefined::Value(n) => Some(n.trim().to_string()).filter(|n| !n.is_empty()),
+        };
+
+        sqlx::query(
+            "UPDATE time_entries SET started_at = ?1, ended_at = ?2, note = ?3 WHERE id = ?4",
+        )
+        .bind(to_db(start))
+        .bind(end.map(to_db))
+        .bind(&note)
+        .bind(&id)
+        .execute(pool)
+        .await?;
+
+        Ok(fetch_time_entry(pool, &id).await?)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::auth::Claims;
 use crate::graphql::takenlijst::types::PagedTasks;
 use crate::graphql::takenlijst::types::Task;
-use crate::tasks::{TaskStatus, time_utils};
+use crate::tasks::{TASK_COLUMNS, TaskStatus, time_utils};
 use async_graphql::{Context, Object};
 use sqlx::{Row, SqlitePool};
 use std::sync::Arc;
@@ -62,14 +62,13 @@ impl HistoryQuery {
 
         // Build the base query
         let mut query_parts = vec![
-            "SELECT DISTINCT t.id, t.project_id, t.author_id, t.assignee_id, t.series_id, t.title, t.description,".to_string(),
-            "       t.status, t.scheduled_date, t.scheduled_time_minutes, t.deadline_date, t.deadline_time_minutes,".to_string(),
-            "       t.completed_at, t.completed_by, t.abandoned_at, t.abandoned_by, t.created_at, t.updated_at".to_string(),
+            format!("SELECT DISTINCT {}", TASK_COLUMNS),
             "FROM tasks t".to_string(),
         ];
 
         let mut joins = Vec::new();
-        let mut conditions = Vec::new();
+        // Tasks in the trash are left out
+        let mut conditions = vec!["t.deleted_at IS NULL".to_string()];
         let mut bind_values: Vec<String> = Vec::new();
 
         // Status filter - expect done or abandoned
@@ -210,76 +209,13 @@ impl HistoryQuery {
         // Convert rows to Task objects
         let mut tasks = Vec::new();
         for row in rows {
-            let id: String = row.get("id");
-            let project_id: String = row.get("project_id");
-            let author_id: String = row.get("author_id");
-            let assignee_id: Option<String> = row.get("assignee_id");
-            let series_id: Option<String> = row.get("series_id");
-            let title: String = row.get("title");
-            let description: Option<String> = row.get("description");
-            let status_str: String = row.get("status");
-            let scheduled_date: Option<String> = row.get("scheduled_date");
-            let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-            let deadline_date: Option<String> = row.get("deadline_date");
-            let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
-            let completed_at: Option<String> = row.get("completed_at");
-            let completed_by: Option<String> = row.get("completed_by");
-            let abandoned_at: Option<String> = row.get("abandoned_at");
-            let abandoned_by: Option<String> = row.get("abandoned_by");
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            let status = match status_str.as_str() {
-                "todo" => TaskStatus::Todo,
-                "done" => TaskStatus::Done,
-                "abandoned" => TaskStatus::Abandoned,
-                _ => TaskStatus::Todo,
-            };
-
-            // Compute derived fields
-            let is_overdue = time_utils::is_task_overdue(
-                scheduled_date.as_deref(),
-                scheduled_time_minutes,
-                deadline_date.as_deref(),
-                deadline_time_minutes,
-                tz,
-            );
-
-            let bucket = time_utils::get_task_bucket(
-                scheduled_date.as_deref(),
-                scheduled_time_minutes,
-                deadline_date.as_deref(),
-                deadline_time_minutes,
-                tz,
-            );
-
-            tasks.push(Task {
-                id,
-                project_id,
-                author_id,
-                assignee_id,
-                series_id,
-                title,
-                description,
-                status,
-                scheduled_date,
-                scheduled_time_minutes,
-                deadline_date,
-                deadline_time_minutes,
-                completed_at,
-                completed_by,
-                abandoned_at,
-                abandoned_by,
-                created_at,
-                updated_at,
-                is_overdue,
-                bucket,
-            });
+            tasks.push(Task::from_row(&row, tz));
         }
 
         Ok(PagedTasks {
             items: tasks,
             total_count: total_count as i32,
+            groups: None,
         })
     }
 }
@@ -1,17 +1,47 @@
+pub mod custom_fields_query;
+pub use custom_fields_query::CustomFieldsQuery;
+
 pub mod history_query;
 pub use history_query::HistoryQuery;
 
+pub mod project_activity_query;
+pub use project_activity_query::ProjectActivityQuery;
+
 pub mod project_default_saved_view_query;
 pub use project_default_saved_view_query::ProjectDefaultSavedViewQuery;
 
+pub mod project_templates_query;
+pub use project_templates_query::ProjectTemplatesQuery;
+
+pub mod project_statuses_query;
+pub use project_statuses_query::ProjectStatusesQuery;
+
 pub mod projects_query;
 pub use projects_query::ProjectsQuery;
 
+pub mod quick_add_preview_query;
+pub use quick_add_preview_query::QuickAddPreviewQuery;
+
 pub mod saved_views_query;
 pub use saved_views_query::SavedViewsQuery;
 
+pub mod sections_query;
+pub use sections_query::SectionsQuery;
+
 pub mod tags_query;
 pub use tags_query::TagsQuery;
 
 pub mod tasks_query;
 pub use tasks_query::TasksQuery;
+
+pub mod tasks_by_status_query;
+pub use tasks_by_status_query::TasksByStatusQuery;
+
+pub mod time_report_query;
+pub use time_report_query::TimeReportQuery;
+
+pub mod trash_query;
+pub use trash_query::TrashQuery;
+
+pub mod watched_tasks_query;
+pub use watched_tasks_query::WatchedTasksQuery;
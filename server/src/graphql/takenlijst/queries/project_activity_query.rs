@@ -0,0 +1,69 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::PagedActivity;
+use crate::graphql::takenlijst::types::task_activity::{ACTIVITY_COLUMNS, TaskActivity};
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct ProjectActivityQuery;
+
+#[Object]
+impl ProjectActivityQuery {
+    /// Changes to a project's tasks, newest first, optionally only those made by `actorId`
+    async fn project_activity(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        actor_id: Option<String>,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<PagedActivity> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let where_clause = "WHERE project_id = ?1 AND (?2 IS NULL OR actor_id = ?2)";
+
+        let total_count = sqlx::query_as::<_, (i64,)>(&format!(
+            "SELECT COUNT(*) FROM task_activity {}",
+            where_clause
+        ))
+        .bind(&project_id)
+        .bind(&actor_id)
+        .fetch_one(pool)
+        .await?
+        .0;
+
+        let sql = format!(
+            "SELECT {} FROM task_activity {} ORDER BY created_at DESC, rowid DESC LIMIT ?3 OFFSET ?4",
+            ACTIVITY_COLUMNS, where_clause
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&project_id)
+            .bind(&actor_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(PagedActivity {
+            items: rows.iter().map(TaskActivity::from_row).collect(),
+            total_count: total_count as i32,
+        })
+    }
+}
@@ -0,0 +1,38 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::tasks::workflow::{ProjectStatus, project_statuses};
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct ProjectStatusesQuery;
+
+#[Object]
+impl ProjectStatusesQuery {
+    /// A project's workflow statuses, in order
+    async fn project_statuses(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+    ) -> async_graphql::Result<Vec<ProjectStatus>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        Ok(project_statuses(&mut *pool.acquire().await?, &project_id).await?)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::auth::Claims;
+use crate::graphql::takenlijst::types::ProjectTemplate;
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct ProjectTemplatesQuery;
+
+#[Object]
+impl ProjectTemplatesQuery {
+    async fn project_templates(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ProjectTemplate>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let username = &claims.sub;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(username)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        let rows = sqlx::query_as::<
+            _,
+            (
+                String,
+                String,
+                String,
+                Option<String>,
+                String,
+                String,
+                String,
+            ),
+        >(
+            "SELECT id, name, owner_id, source_project_id, payload, created_at, updated_at \
+             FROM project_templates WHERE owner_id = ?1 ORDER BY name",
+        )
+        .bind(&user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut templates = Vec::new();
+        for row in rows {
+            match ProjectTemplate::from_row(row) {
+                Ok(template) => templates.push(template),
+                Err(_) => {
+                    return Err(async_graphql::Error::new("Invalid template payload"));
+                }
+            }
+        }
+
+        Ok(templates)
+    }
+}
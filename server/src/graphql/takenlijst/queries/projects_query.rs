@@ -1,5 +1,6 @@
 use crate::auth::Claims;
 use crate::graphql::takenlijst::types::Project;
+use crate::graphql::takenlijst::types::project::PROJECT_COLUMNS;
 use async_graphql::{Context, Object};
 use sqlx::SqlitePool;
 use std::sync::Arc;
@@ -34,39 +35,32 @@ impl ProjectsQuery {
             .0;
 
         // Build the query to get projects where user is owner or member
-        let mut query = String::from(
-            "SELECT DISTINCT p.id, p.name, p.owner_id, p.archived_at, p.created_at, p.updated_at \
+        let mut query = format!(
+            "SELECT DISTINCT {} \
              FROM projects p \
              LEFT JOIN project_members pm ON p.id = pm.project_id \
+             LEFT JOIN project_user_preferences pup ON pup.project_id = p.id AND pup.user_id = ?1 \
              WHERE (p.owner_id = ?1 OR pm.user_id = ?1)",
+            PROJECT_COLUMNS
         );
 
         if !include_archived {
             query.push_str(" AND p.archived_at IS NULL");
         }
 
-        query.push_str(" ORDER BY p.created_at DESC LIMIT ?2 OFFSET ?3");
+        // Respect the user's arrangement: favourites first, then manual position, then newest
+        query.push_str(
+            " ORDER BY is_favorite DESC, pup.position IS NULL, pup.position ASC, p.created_at DESC \
+             LIMIT ?2 OFFSET ?3",
+        );
 
-        let projects =
-            sqlx::query_as::<_, (String, String, String, Option<String>, String, String)>(&query)
-                .bind(&user_id)
-                .bind(limit)
-                .bind(offset)
-                .fetch_all(pool)
-                .await?;
+        let projects = sqlx::query(&query)
+            .bind(&user_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
 
-        Ok(projects
-            .into_iter()
-            .map(
-                |(id, name, owner_id, archived_at, created_at, updated_at)| Project {
-                    id,
-                    name,
-                    owner_id,
-                    archived_at,
-                    created_at,
-                    updated_at,
-                },
-            )
-            .collect())
+        Ok(projects.iter().map(Project::from_row).collect())
     }
 }
@@ -0,0 +1,44 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::QuickAddPreview;
+use crate::graphql::takenlijst::types::quick_add::preview;
+use crate::tasks::time_utils;
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct QuickAddPreviewQuery;
+
+#[Object]
+impl QuickAddPreviewQuery {
+    /// What `quickAddTask` would make of a line, without saving anything
+    async fn preview_quick_add(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        text: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<QuickAddPreview> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let (_, now) = time_utils::now_in_timezone(tz);
+        Ok(preview(pool, &project_id, &text, now.naive_local()).await?)
+    }
+}
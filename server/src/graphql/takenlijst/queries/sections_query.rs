@@ -0,0 +1,40 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::Section;
+use crate::graphql::takenlijst::types::section::fetch_sections;
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct SectionsQuery;
+
+#[Object]
+impl SectionsQuery {
+    async fn sections(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+    ) -> async_graphql::Result<Vec<Section>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let username = &claims.sub;
+
+        // Get user ID
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(username)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        Ok(fetch_sections(pool, &project_id).await?)
+    }
+}
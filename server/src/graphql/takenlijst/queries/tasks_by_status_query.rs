@@ -0,0 +1,79 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::{StatusColumn, Task};
+use crate::tasks::ordering::MANUAL_ORDER;
+use crate::tasks::workflow::project_statuses;
+use crate::tasks::{TASK_COLUMNS, time_utils};
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct TasksByStatusQuery;
+
+#[Object]
+impl TasksByStatusQuery {
+    /// A project's tasks as a board: one column per workflow status, in workflow order, each
+    /// holding up to `limit` tasks in manual order
+    async fn tasks_by_status(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        #[graphql(default = "UTC")] timezone: String,
+        #[graphql(default = false)] include_subtasks: bool,
+        #[graphql(default = 50)] limit: i32,
+    ) -> async_graphql::Result<Vec<StatusColumn>> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let where_clause = format!(
+            "WHERE t.status_id = ?1 AND t.deleted_at IS NULL{}",
+            if include_subtasks {
+                ""
+            } else {
+                " AND t.parent_task_id IS NULL"
+            }
+        );
+        let count_sql = format!("SELECT COUNT(*) FROM tasks t {}", where_clause);
+        let items_sql = format!(
+            "SELECT {} FROM tasks t {} ORDER BY {} LIMIT ?2",
+            TASK_COLUMNS, where_clause, MANUAL_ORDER
+        );
+
+        let mut columns = Vec::new();
+        for status in project_statuses(&mut *pool.acquire().await?, &project_id).await? {
+            let total_count = sqlx::query_as::<_, (i64,)>(&count_sql)
+                .bind(&status.id)
+                .fetch_one(pool)
+                .await?
+                .0;
+            let rows = sqlx::query(&items_sql)
+                .bind(&status.id)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?;
+            columns.push(StatusColumn {
+                status,
+                items: rows.iter().map(|row| Task::from_row(row, tz)).collect(),
+                total_count: total_count as i32,
+            });
+        }
+
+        Ok(columns)
+    }
+}
@@ -1,10 +1,15 @@
 use crate::auth::Claims;
 use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
 use crate::graphql::takenlijst::types::PagedTasks;
-use crate::graphql::takenlijst::types::Task;
-use crate::tasks::{TaskStatus, time_utils};
-use async_graphql::{Context, Object};
-use sqlx::{Row, SqlitePool};
+use crate::graphql::takenlijst::types::section::fetch_sections;
+use crate::graphql::takenlijst::types::{CustomFieldFilterInput, CustomFieldSortInput};
+use crate::graphql::takenlijst::types::{Task, TaskGroup};
+use crate::tasks::custom_fields::{StoredValue, project_fields};
+use crate::tasks::ordering::MANUAL_ORDER;
+use crate::tasks::{TASK_COLUMNS, TaskPriority, TaskSort, TaskStatus, time_utils};
+use async_graphql::{Context, ErrorExtensions, Object};
+use sqlx::SqlitePool;
 use std::sync::Arc;
 
 #[derive(Default)]
@@ -23,6 +28,16 @@ impl TasksQuery {
         #[graphql(default = false)] assigned_to_me: bool,
         tag_ids: Option<Vec<String>>,
         search: Option<String>,
+        section_ids: Option<Vec<String>>,
+        #[graphql(default = false)] include_unsectioned: bool,
+        #[graphql(default = false)] group_by_section: bool,
+        #[graphql(default = false)] include_subtasks: bool,
+        #[graphql(default = false)] hide_blocked: bool,
+        priorities: Option<Vec<TaskPriority>>,
+        min_snooze_count: Option<i32>,
+        custom_fields: Option<Vec<CustomFieldFilterInput>>,
+        custom_field_sort: Option<CustomFieldSortInput>,
+        #[graphql(default)] sort: TaskSort,
         #[graphql(default = 0)] offset: i32,
         #[graphql(default = 20)] limit: i32,
     ) -> async_graphql::Result<PagedTasks> {
@@ -50,7 +65,24 @@ impl TasksQuery {
         require_member(pool, &user_id, &project_id).await?;
 
         // Build the base query with conditions
-        let mut where_conditions = vec!["t.project_id = ?".to_string()];
+        let mut where_conditions = vec![
+            "t.project_id = ?".to_string(),
+            "t.deleted_at IS NULL".to_string(),
+        ];
+
+        // Subtasks are reached through their parent unless asked for
+        if !include_subtasks {
+            where_conditions.push("t.parent_task_id IS NULL".to_string());
+        }
+
+        // Blocked means at least one open blocker
+        if hide_blocked {
+            where_conditions.push(
+                "NOT EXISTS (SELECT 1 FROM task_dependencies d INNER JOIN tasks b ON b.id = d.blocked_by_task_id \
+                 WHERE d.task_id = t.id AND b.status = 'todo' AND b.deleted_at IS NULL)"
+                    .to_string(),
+            );
+        }
         let mut join_clause = String::new();
 
         // Add join for tag filtering if needed
@@ -93,12 +125,115 @@ impl TasksQuery {
             }
         }
 
+        // Add section filtering; includeUnsectioned on its own selects only unsectioned tasks
+        let section_filter = section_ids.as_deref().unwrap_or_default();
+        if !section_filter.is_empty() {
+            let placeholders = vec!["?"; section_filter.len()].join(",");
+            if include_unsectioned {
+                where_conditions.push(format!(
+                    "(t.section_id IN ({}) OR t.section_id IS NULL)",
+                    placeholders
+                ));
+            } else {
+                where_conditions.push(format!("t.section_id IN ({})", placeholders));
+            }
+        } else if include_unsectioned {
+            where_conditions.push("t.section_id IS NULL".to_string());
+        }
+
+        // Add priority filtering
+        let priority_filter = priorities.as_deref().unwrap_or_default();
+        if !priority_filter.is_empty() {
+            let placeholders = vec!["?"; priority_filter.len()].join(",");
+            where_conditions.push(format!("t.priority IN ({})", placeholders));
+        }
+
+        // Add snooze count filtering
+        if min_snooze_count.is_some() {
+            where_conditions.push("t.snooze_count >= ?".to_string());
+        }
+
+        // Add custom field filtering; fields must belong to this project
+        let fields = if custom_fields.is_some() || custom_field_sort.is_some() {
+            project_fields(&mut *pool.acquire().await?, &project_id).await?
+        } else {
+            Vec::new()
+        };
+        let field_not_found = || {
+            async_graphql::Error::new("Custom field not found in this project")
+                .extend_with(|_, e| e.set("code", ErrorCode::NotFound.as_str()))
+        };
+        let mut custom_binds: Vec<StoredValue> = Vec::new();
+        for filter in custom_fields.as_deref().unwrap_or_default() {
+            let field = fields
+                .iter()
+                .find(|f| f.id == filter.field_id)
+                .ok_or_else(field_not_found)?;
+            let (condition, binds) =
+                field
+                    .filter(filter.op, filter.value.as_deref())
+                    .map_err(|message| {
+                        async_graphql::Error::new(message)
+                            .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+                    })?;
+            where_conditions.push(condition);
+            custom_binds.extend(binds);
+        }
+
         let where_clause = format!(" WHERE {}", where_conditions.join(" AND "));
 
+        // Grouping keeps sections contiguous: unsectioned tasks first, then sections in order
+        let (section_join, section_order) = if group_by_section {
+            (
+                " LEFT JOIN sections s ON s.id = t.section_id",
+                "CASE WHEN t.section_id IS NULL THEN -1 ELSE s.position END ASC, t.section_id,\n                ",
+            )
+        } else {
+            ("", "")
+        };
+        let order = match sort {
+            TaskSort::Manual => MANUAL_ORDER.to_string(),
+            TaskSort::Default | TaskSort::Priority => {
+                let priority_order = if sort == TaskSort::Priority {
+                    "t.priority DESC,\n                "
+                } else {
+                    ""
+                };
+                format!(
+                    "{}CASE WHEN t.scheduled_date IS NULL AND t.deadline_date IS NULL THEN t.title ELSE '' END ASC,\n                CASE WHEN t.scheduled_date IS NULL THEN 1 ELSE 0 END,\n                t.scheduled_date ASC,\n                CASE WHEN t.scheduled_time_minutes IS NULL THEN 1 ELSE 0 END,\n                t.scheduled_time_minutes ASC,\n                CASE WHEN t.deadline_date IS NULL THEN 1 ELSE 0 END,\n                t.deadline_date ASC,\n                CASE WHEN t.deadline_time_minutes IS NULL THEN 1 ELSE 0 END,\n                t.deadline_time_minutes ASC,\n                t.created_at ASC",
+                    priority_order
+                )
+            }
+        };
+
+        // Sorting by a custom field comes before the chosen sort, tasks without a value last
+        let custom_order = match &custom_field_sort {
+            Some(field_sort) => {
+                if !fields.iter().any(|f| f.id == field_sort.field_id) {
+                    return Err(field_not_found());
+                }
+                let value = "(SELECT cv.value FROM task_custom_values cv WHERE cv.task_id = t.id AND cv.field_id = ?)";
+                format!(
+                    "CASE WHEN {} IS NULL THEN 1 ELSE 0 END, {} {},\n                ",
+                    value,
+                    value,
+                    if field_sort.descending { "DESC" } else { "ASC" }
+                )
+            }
+            None => String::new(),
+        };
+
         let base_query = format!(
-            "SELECT DISTINCT t.id, t.project_id, t.author_id, t.assignee_id, t.series_id, t.title, t.description, \
-                    t.status, t.scheduled_date, t.scheduled_time_minutes, t.deadline_date, t.deadline_time_minutes,\n                    t.completed_at, t.completed_by, t.abandoned_at, t.abandoned_by, t.created_at, t.updated_at\n             FROM tasks t{}{}\n             ORDER BY \n                CASE WHEN t.scheduled_date IS NULL AND t.deadline_date IS NULL THEN t.title ELSE '' END ASC,\n                CASE WHEN t.scheduled_date IS NULL THEN 1 ELSE 0 END,\n                t.scheduled_date ASC,\n                CASE WHEN t.scheduled_time_minutes IS NULL THEN 1 ELSE 0 END,\n                t.scheduled_time_minutes ASC,\n                CASE WHEN t.deadline_date IS NULL THEN 1 ELSE 0 END,\n                t.deadline_date ASC,\n                CASE WHEN t.deadline_time_minutes IS NULL THEN 1 ELSE 0 END,\n                t.deadline_time_minutes ASC,\n                t.created_at ASC\n             LIMIT {} OFFSET {}",
-            join_clause, where_clause, limit, offset
+            "SELECT DISTINCT {}\n             FROM tasks t{}{}{}\n             ORDER BY \n                {}{}{}\n             LIMIT {} OFFSET {}",
+            TASK_COLUMNS,
+            join_clause,
+            section_join,
+            where_clause,
+            section_order,
+            custom_order,
+            order,
+            limit,
+            offset
         );
 
         let count_query = format!(
@@ -149,82 +284,77 @@ impl TasksQuery {
             }
         }
 
+        // Bind section parameters
+        for section_id in section_filter {
+            count_stmt = count_stmt.bind(section_id);
+            main_stmt = main_stmt.bind(section_id);
+        }
+
+        // Bind priority parameters
+        for priority in priority_filter {
+            count_stmt = count_stmt.bind(priority.as_db());
+            main_stmt = main_stmt.bind(priority.as_db());
+        }
+
+        // Bind snooze count parameter
+        if let Some(count) = min_snooze_count {
+            count_stmt = count_stmt.bind(count);
+            main_stmt = main_stmt.bind(count);
+        }
+
+        // Bind custom field parameters
+        for value in &custom_binds {
+            (count_stmt, main_stmt) = match value {
+                StoredValue::Text(text) => {
+                    (count_stmt.bind(text.clone()), main_stmt.bind(text.clone()))
+                }
+                StoredValue::Number(number) => (count_stmt.bind(*number), main_stmt.bind(*number)),
+                StoredValue::Flag(flag) => (count_stmt.bind(*flag), main_stmt.bind(*flag)),
+            };
+        }
+        if let Some(field_sort) = &custom_field_sort {
+            main_stmt = main_stmt
+                .bind(field_sort.field_id.clone())
+                .bind(field_sort.field_id.clone());
+        }
+
         // Execute queries
         let total_count = count_stmt.fetch_one(pool).await?.0;
         let rows = main_stmt.fetch_all(pool).await?;
 
         let mut tasks = Vec::new();
         for row in rows {
-            let id: String = row.get("id");
-            let project_id: String = row.get("project_id");
-            let author_id: String = row.get("author_id");
-            let assignee_id: Option<String> = row.get("assignee_id");
-            let series_id: Option<String> = row.get("series_id");
-            let title: String = row.get("title");
-            let description: Option<String> = row.get("description");
-            let status_str: String = row.get("status");
-            let scheduled_date: Option<String> = row.get("scheduled_date");
-            let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
-            let deadline_date: Option<String> = row.get("deadline_date");
-            let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
-            let completed_at: Option<String> = row.get("completed_at");
-            let completed_by: Option<String> = row.get("completed_by");
-            let abandoned_at: Option<String> = row.get("abandoned_at");
-            let abandoned_by: Option<String> = row.get("abandoned_by");
-            let created_at: String = row.get("created_at");
-            let updated_at: String = row.get("updated_at");
-
-            let status = match status_str.as_str() {
-                "todo" => TaskStatus::Todo,
-                "done" => TaskStatus::Done,
-                "abandoned" => TaskStatus::Abandoned,
-                _ => TaskStatus::Todo,
-            };
-
-            // Compute derived fields
-            let is_overdue = time_utils::is_task_overdue(
-                scheduled_date.as_deref(),
-                scheduled_time_minutes,
-                deadline_date.as_deref(),
-                deadline_time_minutes,
-                tz,
-            );
-
-            let bucket = time_utils::get_task_bucket(
-                scheduled_date.as_deref(),
-                scheduled_time_minutes,
-                deadline_date.as_deref(),
-                deadline_time_minutes,
-                tz,
-            );
-
-            tasks.push(Task {
-                id,
-                project_id,
-                author_id,
-                assignee_id,
-                series_id,
-                title,
-                description,
-                status,
-                scheduled_date,
-                scheduled_time_minutes,
-                deadline_date,
-                deadline_time_minutes,
-                completed_at,
-                completed_by,
-                abandoned_at,
-                abandoned_by,
-                created_at,
-                updated_at,
-                is_overdue,
-                bucket,
-            });
+            tasks.push(Task::from_row(&row, tz));
         }
 
+        let groups = if group_by_section {
+            let sections = fetch_sections(pool, &project_id).await?;
+            let mut groups: Vec<TaskGroup> = Vec::new();
+            for task in &tasks {
+                match groups.last_mut() {
+                    Some(group)
+                        if group.section.as_ref().map(|s| &s.id) == task.section_id.as_ref() =>
+                    {
+                        group.items.push(task.clone());
+                    }
+                    _ => groups.push(TaskGroup {
+                        section: task
+                            .section_id
+                            .as_ref()
+                            .and_then(|id| sections.iter().find(|s| &s.id == id).cloned()),
+                        items: vec![task.clone()],
+                    }),
+                }
+            }
+            Some(groups)
+        } else {
+            None
+        };
+
         Ok(PagedTasks {
             items: tasks,
             total_count: total_count as i32,
+            groups,
         })
     }
 }
@@ -0,0 +1,145 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::error_codes::ErrorCode;
+use crate::graphql::takenlijst::types::TimeReport;
+use crate::graphql::takenlijst::types::time_report::{TagTime, UserTime};
+use crate::tasks::time_tracking::{ReportEntry, aggregate, local_date_range, to_minutes};
+use crate::tasks::time_utils::{self, parse_db, to_db};
+use async_graphql::{Context, ErrorExtensions, Object};
+use chrono::{NaiveDate, Utc};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct TimeReportQuery;
+
+#[Object]
+impl TimeReportQuery {
+    /// Time tracked on a project's tasks between two local dates, inclusive. Entries that
+    /// cross the range's edges count only their time inside it; running timers count up to
+    /// now. Tasks in the trash are left out.
+    async fn time_report(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        from: String,
+        to: String,
+        #[graphql(default = "UTC")] timezone: String,
+    ) -> async_graphql::Result<TimeReport> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let invalid = |message: &str| {
+            async_graphql::Error::new(message)
+                .extend_with(|_, e| e.set("code", ErrorCode::ValidationFailed.as_str()))
+        };
+        let (Ok(from_date), Ok(to_date)) = (
+            NaiveDate::parse_from_str(&from, "%Y-%m-%d"),
+            NaiveDate::parse_from_str(&to, "%Y-%m-%d"),
+        ) else {
+            return Err(invalid("Dates must be formatted as YYYY-MM-DD"));
+        };
+        if to_date < from_date {
+            return Err(invalid("to must not be before from"));
+        }
+        let (start, end) = local_date_range(from_date, to_date, tz)
+            .ok_or_else(|| invalid("Invalid date range"))?;
+
+        let rows = sqlx::query_as::<_, (String, String, String, Option<String>)>(
+            "SELECT e.task_id, e.user_id, e.started_at, e.ended_at FROM time_entries e \
+             INNER JOIN tasks t ON t.id = e.task_id \
+             WHERE t.project_id = ?1 AND t.deleted_at IS NULL \
+               AND e.started_at < ?2 AND (e.ended_at IS NULL OR e.ended_at > ?3)",
+        )
+        .bind(&project_id)
+        .bind(to_db(end))
+        .bind(to_db(start))
+        .fetch_all(pool)
+        .await?;
+
+        let mut task_tags: HashMap<String, Vec<String>> = HashMap::new();
+        let tag_rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT tt.task_id, tt.tag_id FROM task_tags tt \
+             INNER JOIN tasks t ON t.id = tt.task_id WHERE t.project_id = ?1",
+        )
+        .bind(&project_id)
+        .fetch_all(pool)
+        .await?;
+        for (task_id, tag_id) in tag_rows {
+            task_tags.entry(task_id).or_default().push(tag_id);
+        }
+
+        let now = Utc::now();
+        let entries: Vec<ReportEntry> = rows
+            .into_iter()
+            .filter_map(|(task_id, user_id, started_at, ended_at)| {
+                Some(ReportEntry {
+                    tag_ids: task_tags.get(&task_id).cloned().unwrap_or_default(),
+                    user_id,
+                    started_at: parse_db(&started_at)?,
+                    ended_at: match ended_at {
+                        Some(ended_at) => parse_db(&ended_at)?,
+                        None => now,
+                    },
+                })
+            })
+            .collect();
+        let totals = aggregate(&entries, start, end);
+
+        let mut by_user = Vec::new();
+        for (id, seconds) in totals.by_user {
+            let username =
+                sqlx::query_as::<_, (String,)>("SELECT username FROM users WHERE id = ?1")
+                    .bind(&id)
+                    .fetch_one(pool)
+                    .await?
+                    .0;
+            by_user.push(UserTime {
+                user_id: id,
+                username,
+                minutes: to_minutes(seconds),
+            });
+        }
+        by_user.sort_by(|a, b| b.minutes.cmp(&a.minutes).then(a.username.cmp(&b.username)));
+
+        let mut by_tag = Vec::new();
+        for (id, seconds) in totals.by_tag {
+            let name = sqlx::query_as::<_, (String,)>("SELECT name FROM tags WHERE id = ?1")
+                .bind(&id)
+                .fetch_one(pool)
+                .await?
+                .0;
+            by_tag.push(TagTime {
+                tag_id: id,
+                name,
+                minutes: to_minutes(seconds),
+            });
+        }
+        by_tag.sort_by(|a, b| b.minutes.cmp(&a.minutes).then(a.name.cmp(&b.name)));
+
+        Ok(TimeReport {
+            from,
+            to,
+            total_minutes: to_minutes(totals.total),
+            untagged_minutes: to_minutes(totals.untagged),
+            by_user,
+            by_tag,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+use crate::auth::Claims;
+use crate::auth::guard::require_member;
+use crate::graphql::takenlijst::types::{PagedTasks, Task};
+use crate::tasks::{TASK_COLUMNS, time_utils};
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct TrashQuery;
+
+#[Object]
+impl TrashQuery {
+    /// Deleted tasks of a project, most recently deleted first. Subtasks deleted along with
+    /// their parent are listed under it rather than on their own.
+    async fn trash(
+        &self,
+        ctx: &Context<'_>,
+        project_id: String,
+        #[graphql(default = "UTC")] timezone: String,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<PagedTasks> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        require_member(pool, &user_id, &project_id).await?;
+
+        let where_clause = "WHERE t.project_id = ?1 AND t.deleted_at IS NOT NULL \
+             AND NOT EXISTS (SELECT 1 FROM tasks p WHERE p.id = t.parent_task_id AND p.deleted_at = t.deleted_at)";
+
+        let total_count =
+            sqlx::query_as::<_, (i64,)>(&format!("SELECT COUNT(*) FROM tasks t {}", where_clause))
+                .bind(&project_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+
+        let sql = format!(
+            "SELECT {} FROM tasks t {} ORDER BY t.deleted_at DESC, t.id ASC LIMIT ?2 OFFSET ?3",
+            TASK_COLUMNS, where_clause
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&project_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(PagedTasks {
+            items: rows.iter().map(|row| Task::from_row(row, tz)).collect(),
+            total_count: total_count as i32,
+            groups: None,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use crate::auth::Claims;
+use crate::graphql::takenlijst::types::{PagedTasks, Task};
+use crate::tasks::{TASK_COLUMNS, time_utils};
+use async_graphql::{Context, Object};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct WatchedTasksQuery;
+
+#[Object]
+impl WatchedTasksQuery {
+    /// Tasks the current user watches across all their projects, most recently changed first.
+    /// Closed tasks are left out unless `includeClosed` is set.
+    async fn watched_tasks(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = false)] include_closed: bool,
+        #[graphql(default = "UTC")] timezone: String,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<PagedTasks> {
+        let claims = match ctx.data_opt::<Arc<Claims>>() {
+            Some(claims) => claims,
+            None => {
+                return Err(async_graphql::Error::new("Authentication required"));
+            }
+        };
+
+        let pool = ctx.data::<SqlitePool>()?;
+        let tz = time_utils::parse_timezone(&timezone).map_err(async_graphql::Error::new)?;
+
+        let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+            .bind(&claims.sub)
+            .fetch_one(pool)
+            .await?
+            .0;
+
+        // Only projects the user still belongs to
+        let where_clause = format!(
+            "INNER JOIN task_watchers w ON w.task_id = t.id AND w.user_id = ?1 \
+             INNER JOIN projects p ON p.id = t.project_id \
+             WHERE t.deleted_at IS NULL{} AND (p.owner_id = ?1 OR EXISTS ( \
+                 SELECT 1 FROM project_members pm WHERE pm.project_id = p.id AND pm.user_id = ?1))",
+            if include_closed {
+                ""
+            } else {
+                " AND t.status = 'todo'"
+            }
+        );
+
+        let total_count =
+            sqlx::query_as::<_, (i64,)>(&format!("SELECT COUNT(*) FROM tasks t {}", where_clause))
+                .bind(&user_id)
+                .fetch_one(pool)
+                .await?
+                .0;
+
+        let sql = format!(
+            "SELECT {} FROM tasks t {} ORDER BY t.updated_at DESC, t.id ASC LIMIT ?2 OFFSET ?3",
+            TASK_COLUMNS, where_clause
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&user_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(PagedTasks {
+            items: rows.iter().map(|row| Task::from_row(row, tz)).collect(),
+            total_count: total_count as i32,
+            groups: None,
+        })
+    }
+}
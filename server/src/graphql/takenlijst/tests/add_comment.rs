@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "Alice"), ("user3", "bob")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // alice is a member, bob is not
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES ('t1', 'p1', 'user1', 'Paint walls', 'todo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    const ADD: &str = "mutation($t: String!, $b: String!) { addComment(taskId: $t, body: $b) { id body updatedAt editedAt mentions { username } } }";
+
+    #[tokio::test]
+    async fn comments_record_member_mentions_and_paginate() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "testuser",
+            ADD,
+            json!({ "t": "t1", "b": "  @alice @bob which colour? mail me at x@y.com  " }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let comment = &data["addComment"];
+        assert_eq!(
+            comment["body"],
+            "@alice @bob which colour? mail me at x@y.com"
+        );
+        assert_eq!(comment["editedAt"], serde_json::Value::Null);
+        assert_eq!(comment["mentions"], json!([{ "username": "Alice" }]));
+
+        for body in ["second", "third"] {
+            let response = execute(&schema, "Alice", ADD, json!({ "t": "t1", "b": body })).await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+        }
+
+        // blank and non-member comments are rejected
+        assert!(
+            !execute(&schema, "testuser", ADD, json!({ "t": "t1", "b": "   " }))
+                .await
+                .errors
+                .is_empty()
+        );
+        assert!(
+            !execute(&schema, "bob", ADD, json!({ "t": "t1", "b": "hi" }))
+                .await
+                .errors
+                .is_empty()
+        );
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\") { items { comments(offset: 1, limit: 1) { totalCount items { body } } } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["tasks"]["items"][0]["comments"],
+            json!({ "totalCount": 3, "items": [{ "body": "second" }] })
+        );
+    }
+
+    #[tokio::test]
+    async fn only_author_edits_and_owner_may_delete() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(&schema, "Alice", ADD, json!({ "t": "t1", "b": "hello" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let id = data["addComment"]["id"].as_str().unwrap().to_string();
+        let updated_at = data["addComment"]["updatedAt"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        const EDIT: &str = "mutation($id: String!, $b: String!, $at: String!) { editComment(commentId: $id, body: $b, lastKnownUpdatedAt: $at) { body editedAt mentions { username } } }";
+
+        // the project owner cannot edit someone else's comment
+        let response = execute(
+            &schema,
+            "testuser",
+            EDIT,
+            json!({ "id": id, "b": "hijacked", "at": updated_at }),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "Alice",
+            EDIT,
+            json!({ "id": id, "b": "hello @testuser", "at": updated_at }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["editComment"]["body"], "hello @testuser");
+        assert!(data["editComment"]["editedAt"].is_string());
+        assert_eq!(
+            data["editComment"]["mentions"],
+            json!([{ "username": "testuser" }])
+        );
+
+        // a second edit with the old timestamp is stale
+        let response = execute(
+            &schema,
+            "Alice",
+            EDIT,
+            json!({ "id": id, "b": "again", "at": updated_at }),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!) { deleteComment(commentId: $id) }",
+            json!({ "id": id }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM task_comment_mentions")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count.0, 0);
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, name) in [("p1", "House"), ("p2", "Garden")] {
+            sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES (?, ?, 'user1')")
+                .bind(id)
+                .bind(name)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        for (id, project_id, title) in [
+            ("a", "p1", "Buy paint"),
+            ("b", "p1", "Paint walls"),
+            ("c", "p1", "Hang pictures"),
+            ("x", "p2", "Mow lawn"),
+        ] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES (?, ?, 'user1', ?, 'todo')",
+            )
+            .bind(id)
+            .bind(project_id)
+            .bind(title)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn add(
+        schema: &crate::graphql::AppSchema,
+        task: &str,
+        blocker: &str,
+    ) -> async_graphql::Response {
+        execute(
+            schema,
+            "mutation($t: String!, $b: String!) { addTaskDependency(taskId: $t, blockedByTaskId: $b) { id isBlocked } }",
+            json!({ "t": task, "b": blocker }),
+        )
+        .await
+    }
+
+    async fn complete(
+        pool: &SqlitePool,
+        schema: &crate::graphql::AppSchema,
+        id: &str,
+    ) -> async_graphql::Response {
+        let updated_at: (String,) = sqlx::query_as("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        execute(
+            schema,
+            "mutation($id: String!, $at: String!) { completeTask(id: $id, lastKnownUpdatedAt: $at) { status isBlocked warnings } }",
+            json!({ "id": id, "at": updated_at.0 }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn dependencies_reject_cycles_and_other_projects() {
+        let schema = build(setup_test_db().await);
+
+        // b waits on a, c waits on b
+        let response = add(&schema, "b", "a").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["addTaskDependency"]["isBlocked"], true);
+        assert!(add(&schema, "c", "b").await.errors.is_empty());
+
+        // a waiting on c would close the loop
+        assert!(!add(&schema, "a", "c").await.errors.is_empty());
+        assert!(!add(&schema, "a", "a").await.errors.is_empty());
+        assert!(!add(&schema, "a", "x").await.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", hideBlocked: true) { items { id blocking { id } } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["tasks"]["items"],
+            json!([{ "id": "a", "blocking": [{ "id": "b" }] }])
+        );
+
+        let response = execute(
+            &schema,
+            "mutation { removeTaskDependency(taskId: \"b\", blockedByTaskId: \"a\") { isBlocked blockedBy { id } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["removeTaskDependency"],
+            json!({ "isBlocked": false, "blockedBy": [] })
+        );
+    }
+
+    #[tokio::test]
+    async fn completing_blocked_task_respects_project_setting() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        assert!(add(&schema, "b", "a").await.errors.is_empty());
+
+        // Not enforced: completes but still reports the open blocker
+        let response = complete(&pool, &schema, "b").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["completeTask"]["isBlocked"], true);
+        assert_eq!(
+            data["completeTask"]["warnings"],
+            json!(["Completed while blocked by open tasks: a"])
+        );
+
+        // Moving into a done status warns the same way
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES ('d', 'p1', 'user1', 'Clean brushes', 'todo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(add(&schema, "d", "a").await.errors.is_empty());
+        let (done, updated_at): (String, String) = sqlx::query_as(
+            "SELECT s.id, t.updated_at FROM project_statuses s, tasks t \
+             WHERE s.project_id = 'p1' AND s.category = 'done' AND t.id = 'd'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let response = execute(
+            &schema,
+            "mutation($s: String!, $at: String!) { moveTaskToStatus(id: \"d\", statusId: $s, lastKnownUpdatedAt: $at) { status warnings } }",
+            json!({ "s": done, "at": updated_at }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["moveTaskToStatus"],
+            json!({ "status": "DONE", "warnings": ["Completed while blocked by open tasks: a"] })
+        );
+
+        assert!(add(&schema, "c", "a").await.errors.is_empty());
+        let response = execute(
+            &schema,
+            "mutation { setProjectDependencyEnforcement(projectId: \"p1\", enforce: true) { enforceDependencies } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        assert!(!complete(&pool, &schema, "c").await.errors.is_empty());
+        assert!(complete(&pool, &schema, "a").await.errors.is_empty());
+        let response = complete(&pool, &schema, "c").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["completeTask"]["warnings"], json!([]));
+    }
+}
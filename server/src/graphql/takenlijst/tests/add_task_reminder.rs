@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use crate::tasks::reminders::{Delivery, ReminderSink, deliver_pending, enqueue_due};
+    use crate::tasks::time_utils::parse_instant;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::{Arc, Mutex};
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "alice")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, assignee_id, title, status, scheduled_date, scheduled_time_minutes) \
+             VALUES ('bins', 'p1', 'user1', 'user2', 'Put the bins out', 'todo', '2030-06-15', 540)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    /// Records deliveries, failing the first `failures` of them
+    struct TestSink {
+        failures: Mutex<u32>,
+        delivered: Mutex<Vec<Delivery>>,
+    }
+
+    impl ReminderSink for TestSink {
+        fn deliver(&self, delivery: &Delivery) -> Result<(), String> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err("unavailable".to_string());
+            }
+            self.delivered.lock().unwrap().push(delivery.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn due_reminders_are_delivered_once_per_fire_time() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "mutation { addTaskReminder(taskId: \"bins\", timezone: \"Europe/Amsterdam\", \
+             reminder: { kind: BEFORE_SCHEDULED, offsetMinutes: 30 }) { kind offsetMinutes timezone fireAt } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        // 09:00 CEST less half an hour
+        assert_eq!(
+            response.data.into_json().unwrap()["addTaskReminder"],
+            json!({
+                "kind": "BEFORE_SCHEDULED",
+                "offsetMinutes": 30,
+                "timezone": "Europe/Amsterdam",
+                "fireAt": "2030-06-15 06:30:00.000"
+            })
+        );
+
+        let response = execute(
+            &schema,
+            "mutation { addTaskReminder(taskId: \"bins\", reminder: { kind: ABSOLUTE, offsetMinutes: 5 }) { id } }",
+            json!({}),
+        )
+        .await;
+        assert_eq!(
+            response.errors[0].message,
+            "offsetMinutes only applies to relative reminders"
+        );
+
+        let sink = TestSink {
+            failures: Mutex::new(1),
+            delivered: Mutex::new(Vec::new()),
+        };
+
+        // Not due yet
+        let early = parse_instant("2030-06-15T06:00:00Z").unwrap();
+        assert_eq!(enqueue_due(&pool, early).await.unwrap(), 0);
+
+        // Due; the first attempt fails and is retried later
+        let due = parse_instant("2030-06-15T06:31:00Z").unwrap();
+        assert_eq!(enqueue_due(&pool, due).await.unwrap(), 1);
+        assert_eq!(enqueue_due(&pool, due).await.unwrap(), 0);
+        assert_eq!(deliver_pending(&pool, &sink, due).await.unwrap(), 0);
+        assert_eq!(deliver_pending(&pool, &sink, due).await.unwrap(), 0);
+        let retry = parse_instant("2030-06-15T06:35:00Z").unwrap();
+        assert_eq!(deliver_pending(&pool, &sink, retry).await.unwrap(), 1);
+        assert_eq!(deliver_pending(&pool, &sink, retry).await.unwrap(), 0);
+        {
+            let delivered = sink.delivered.lock().unwrap();
+            assert_eq!(delivered.len(), 1);
+            assert_eq!(delivered[0].recipient_id, "user2");
+            assert_eq!(delivered[0].fire_at, "2030-06-15 06:30:00.000");
+        }
+
+        // Rescheduling the task gives the reminder a new fire time
+        sqlx::query("UPDATE tasks SET scheduled_date = '2030-06-16' WHERE id = 'bins'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let next_day = parse_instant("2030-06-16T06:30:00Z").unwrap();
+        assert_eq!(enqueue_due(&pool, next_day).await.unwrap(), 1);
+
+        // Completed before delivery, so it's cancelled rather than sent
+        sqlx::query("UPDATE tasks SET status = 'done' WHERE id = 'bins'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(deliver_pending(&pool, &sink, next_day).await.unwrap(), 0);
+        let statuses: Vec<(String,)> =
+            sqlx::query_as("SELECT status FROM reminder_outbox ORDER BY fire_at")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            statuses,
+            vec![("sent".to_string(),), ("cancelled".to_string(),)]
+        );
+    }
+}
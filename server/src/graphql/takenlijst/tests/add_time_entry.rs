@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "alice")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('t1', 'diy')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status, estimate_minutes) \
+             VALUES ('paint', 'p1', 'user1', 'Paint', 'todo', 90), ('mow', 'p1', 'user1', 'Mow', 'todo', NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO task_tags (task_id, tag_id) VALUES ('paint', 't1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    #[tokio::test]
+    async fn manual_entries_count_towards_totals_and_reports() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let add = "mutation($task: String!, $start: String!, $end: String!) { \
+                   addTimeEntry(taskId: $task, startedAt: $start, endedAt: $end) { id durationMinutes updatedAt } }";
+
+        let response = execute(
+            &schema,
+            "testuser",
+            add,
+            json!({ "task": "paint", "start": "2025-03-30T09:00:00+02:00", "end": "2025-03-30T10:00:00+02:00" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let entry = response.data.into_json().unwrap()["addTimeEntry"].clone();
+        assert_eq!(entry["durationMinutes"], 60);
+
+        let response = execute(
+            &schema,
+            "alice",
+            add,
+            json!({ "task": "mow", "start": "2025-03-30T23:30:00+02:00", "end": "2025-03-31T00:30:00+02:00" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let response = execute(
+            &schema,
+            "testuser",
+            add,
+            json!({ "task": "paint", "start": "2025-03-30T10:00:00Z", "end": "2025-03-30T09:00:00Z" }),
+        )
+        .await;
+        assert_eq!(
+            response.errors[0].message,
+            "endedAt must be after startedAt"
+        );
+
+        // Only the owner of an entry can edit it
+        let update = "mutation($id: String!, $start: String, $at: String!) { \
+                      updateTimeEntry(id: $id, startedAt: $start, lastKnownUpdatedAt: $at) { durationMinutes } }";
+        let variables = json!({ "id": entry["id"], "start": "2025-03-30T08:30:00+02:00", "at": entry["updatedAt"] });
+        let response = execute(&schema, "alice", update, variables.clone()).await;
+        assert_eq!(
+            response.errors[0].message,
+            "Only your own time entries can be edited"
+        );
+        let response = execute(&schema, "testuser", update, variables).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap()["updateTimeEntry"]["durationMinutes"],
+            90
+        );
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\") { items { id estimateMinutes trackedMinutes } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let paint = data["tasks"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["id"] == "paint")
+            .unwrap()
+            .clone();
+        assert_eq!(paint["estimateMinutes"], 90);
+        assert_eq!(paint["trackedMinutes"], 90);
+
+        // Alice's entry runs past local midnight, so only half of it falls on the 30th
+        let response = execute(
+            &schema,
+            "testuser",
+            "{ timeReport(projectId: \"p1\", from: \"2025-03-30\", to: \"2025-03-30\", timezone: \"Europe/Amsterdam\") { \
+             totalMinutes untaggedMinutes byUser { username minutes } byTag { name minutes } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap()["timeReport"],
+            json!({
+                "totalMinutes": 120,
+                "untaggedMinutes": 30,
+                "byUser": [
+                    { "username": "testuser", "minutes": 90 },
+                    { "username": "alice", "minutes": 30 }
+                ],
+                "byTag": [{ "name": "diy", "minutes": 90 }]
+            })
+        );
+    }
+}
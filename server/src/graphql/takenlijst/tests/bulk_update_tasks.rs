@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "other")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query(
+            "INSERT INTO projects (id, name, owner_id, enforce_dependencies) VALUES \
+             ('p1', 'House', 'user1', 1), ('p2', 'Garden', 'user1', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO sections (id, project_id, name, position) VALUES ('s1', 'p1', 'Upstairs', 0)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, parent) in [
+            ("paint", None),
+            ("buy-paint", Some("paint")),
+            ("sand", Some("paint")),
+            ("fix-tap", None),
+            ("plumber", None),
+            ("bins", None),
+        ] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, assignee_id, section_id, parent_task_id, title, status) \
+                 VALUES (?1, 'p1', 'user1', 'user2', 's1', ?2, ?1, 'todo')",
+            )
+            .bind(id)
+            .bind(parent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO task_dependencies (task_id, blocked_by_task_id, created_by) VALUES ('fix-tap', 'plumber', 'user1'), ('sand', 'bins', 'user1')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn items(pool: &SqlitePool, ids: &[&str]) -> serde_json::Value {
+        let mut items = Vec::new();
+        for id in ids {
+            let (updated_at,): (String,) =
+                sqlx::query_as("SELECT updated_at FROM tasks WHERE id = ?1")
+                    .bind(id)
+                    .fetch_one(pool)
+                    .await
+                    .unwrap();
+            items.push(json!({ "id": id, "lastKnownUpdatedAt": updated_at }));
+        }
+        json!(items)
+    }
+
+    async fn bulk(
+        schema: &crate::graphql::AppSchema,
+        items: serde_json::Value,
+        action: serde_json::Value,
+    ) -> Vec<(String, bool, Option<String>)> {
+        let response = execute(
+            schema,
+            "mutation($items: [BulkTaskItemInput!]!, $action: BulkTaskActionInput!) { \
+             bulkUpdateTasks(items: $items, action: $action) { id ok code } }",
+            json!({ "items": items, "action": action }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["bulkUpdateTasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                (
+                    r["id"].as_str().unwrap().to_string(),
+                    r["ok"].as_bool().unwrap(),
+                    r["code"].as_str().map(str::to_string),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn bulk_complete_reports_each_task() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let mut batch = items(&pool, &["paint", "buy-paint", "sand", "fix-tap", "bins"]).await;
+        batch[4]["lastKnownUpdatedAt"] = json!("2000-01-01 00:00:00.000");
+
+        // sand waits on bins, which is stale, so paint waits on sand; fix-tap waits on a
+        // blocker outside the batch
+        let results = bulk(&schema, batch, json!({ "kind": "COMPLETE" })).await;
+        let outcome: Vec<_> = results.iter().map(|r| (r.1, r.2.as_deref())).collect();
+        assert_eq!(
+            outcome,
+            vec![
+                (false, Some("VALIDATION_FAILED")),
+                (true, None),
+                (false, Some("VALIDATION_FAILED")),
+                (false, Some("VALIDATION_FAILED")),
+                (false, Some("CONFLICT_STALE_WRITE")),
+            ]
+        );
+
+        // Blockers completed in the same batch count as closed
+        let batch = items(&pool, &["paint", "sand", "bins", "bins"]).await;
+        let results = bulk(&schema, batch, json!({ "kind": "COMPLETE" })).await;
+        let ok: Vec<_> = results.iter().map(|r| r.1).collect();
+        assert_eq!(ok, vec![true, true, true, false]);
+
+        let (done,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM tasks WHERE status = 'done' AND completed_by = 'user1'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(done, 4);
+    }
+
+    #[tokio::test]
+    async fn bulk_move_takes_subtasks_and_drops_project_links() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let batch = items(&pool, &["paint", "sand"]).await;
+        let results = bulk(
+            &schema,
+            batch,
+            json!({ "kind": "MOVE_TO_PROJECT", "projectId": "p2" }),
+        )
+        .await;
+        assert_eq!(results[0], ("paint".to_string(), true, None));
+        assert_eq!(
+            results[1],
+            (
+                "sand".to_string(),
+                false,
+                Some("VALIDATION_FAILED".to_string())
+            )
+        );
+
+        let moved = sqlx::query_as::<_, (String, String, Option<String>, Option<String>)>(
+            "SELECT id, project_id, section_id, assignee_id FROM tasks WHERE id IN ('paint', 'buy-paint', 'sand') ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        for (id, project_id, section_id, assignee_id) in moved {
+            assert_eq!(project_id, "p2", "{}", id);
+            assert_eq!(section_id, None);
+            assert_eq!(assignee_id, None);
+        }
+
+        let (dependencies,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM task_dependencies")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(dependencies, 1);
+
+        // Tags are added once however often they're asked for
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('t1', 'diy')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            let batch = items(&pool, &["paint", "fix-tap"]).await;
+            let results = bulk(
+                &schema,
+                batch,
+                json!({ "kind": "ADD_TAGS", "tagIds": ["t1"] }),
+            )
+            .await;
+            assert!(results.iter().all(|r| r.1));
+        }
+        let (tagged,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM task_tags")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tagged, 2);
+    }
+}
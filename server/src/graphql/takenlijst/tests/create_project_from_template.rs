@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/create_project_from_template resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::create_project_from_template::CreateProjectFromTemplateMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_create_project_from_template() {
+        let _ = CreateProjectFromTemplateMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/create_section resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::create_section::CreateSectionMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_create_section() {
+        let _ = CreateSectionMutation::default();
+        assert!(true);
+    }
+}
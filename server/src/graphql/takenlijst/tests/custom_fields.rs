@@ -0,0 +1,310 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES \
+             ('user1', 'testuser', 'password', 'Test'), ('user2', 'rob', 'password', 'Rob')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        // Keep consecutive writes on distinct millisecond timestamps
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn data(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> serde_json::Value {
+        let response = execute(schema, "testuser", query, variables).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    const CREATE_FIELD: &str = "mutation($input: CustomFieldInput!) { \
+                                createCustomField(projectId: \"p1\", input: $input) { id name kind options } }";
+
+    const CREATE_TASK: &str = "mutation($title: String!, $values: [CustomFieldValueInput!]) { \
+                               createTask(input: { projectId: \"p1\", title: $title, customFields: $values }) { \
+                               id updatedAt customFields { fieldId name kind value } } }";
+
+    #[tokio::test]
+    async fn owners_define_fields_and_tasks_hold_checked_values() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let input = json!({ "name": "Cost", "kind": "NUMBER", "minValue": 0 });
+        let response = execute(&schema, "rob", CREATE_FIELD, json!({ "input": input })).await;
+        assert_eq!(response.errors.len(), 1, "only the owner defines fields");
+        let cost =
+            data(&schema, CREATE_FIELD, json!({ "input": input })).await["createCustomField"]["id"]
+                .clone();
+        let size = data(
+            &schema,
+            CREATE_FIELD,
+            json!({ "input": { "name": "Size", "kind": "SINGLE_SELECT", "options": ["Small", " Large ", "small"] } }),
+        )
+        .await["createCustomField"]
+            .clone();
+        assert_eq!(size["options"], json!(["Small", "Large"]));
+
+        // Rules that don't fit the kind are rejected
+        let response = execute(
+            &schema,
+            "testuser",
+            CREATE_FIELD,
+            json!({ "input": { "name": "Link", "kind": "URL", "maxLength": 10 } }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+
+        let task = data(
+            &schema,
+            CREATE_TASK,
+            json!({ "title": "Paint fence", "values": [
+                { "fieldId": cost, "value": "120" },
+                { "fieldId": size["id"], "value": "large" }
+            ] }),
+        )
+        .await["createTask"]
+            .clone();
+        assert_eq!(
+            task["customFields"],
+            json!([
+                { "fieldId": cost, "name": "Cost", "kind": "NUMBER", "value": "120" },
+                { "fieldId": size["id"], "name": "Size", "kind": "SINGLE_SELECT", "value": "Large" }
+            ])
+        );
+
+        // Values are checked against the field's rules
+        for value in ["-1", "lots"] {
+            let response = execute(
+                &schema,
+                "testuser",
+                CREATE_TASK,
+                json!({ "title": "Mow", "values": [{ "fieldId": cost, "value": value }] }),
+            )
+            .await;
+            assert_eq!(response.errors.len(), 1, "{}", value);
+        }
+
+        // updateTask changes and clears values
+        let updated = data(
+            &schema,
+            "mutation($id: String!, $at: String!, $values: [CustomFieldValueInput!]) { \
+             updateTask(id: $id, lastKnownUpdatedAt: $at, input: { customFields: $values }) { customFields { name value } } }",
+            json!({ "id": task["id"], "at": task["updatedAt"], "values": [
+                { "fieldId": cost, "value": "99.5" },
+                { "fieldId": size["id"], "value": null }
+            ] }),
+        )
+        .await;
+        assert_eq!(
+            updated["updateTask"]["customFields"],
+            json!([{ "name": "Cost", "value": "99.5" }])
+        );
+
+        // Deleting a field removes its values
+        data(
+            &schema,
+            "mutation($id: String!) { deleteCustomField(id: $id) }",
+            json!({ "id": cost }),
+        )
+        .await;
+        let fields = data(
+            &schema,
+            "query { customFields(projectId: \"p1\") { name } }",
+            json!({}),
+        )
+        .await;
+        assert_eq!(fields["customFields"], json!([{ "name": "Size" }]));
+        let (values,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM task_custom_values")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(values, 0);
+    }
+
+    #[tokio::test]
+    async fn tasks_filter_and_sort_by_custom_fields() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let cost = data(
+            &schema,
+            CREATE_FIELD,
+            json!({ "input": { "name": "Cost", "kind": "NUMBER" } }),
+        )
+        .await["createCustomField"]["id"]
+            .clone();
+        // Numbers compare as numbers, not text
+        for (title, value) in [("Fence", Some("9")), ("Roof", Some("100")), ("Shed", None)] {
+            let values = value.map(|v| json!([{ "fieldId": cost, "value": v }]));
+            data(
+                &schema,
+                CREATE_TASK,
+                json!({ "title": title, "values": values }),
+            )
+            .await;
+        }
+
+        let query = "query($filters: [CustomFieldFilterInput!], $sort: CustomFieldSortInput) { \
+                     tasks(projectId: \"p1\", timezone: \"UTC\", customFields: $filters, customFieldSort: $sort) { items { title } } }";
+        let titles = |data: serde_json::Value| -> Vec<String> {
+            data["tasks"]["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|t| t["title"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        let result = data(
+            &schema,
+            query,
+            json!({ "filters": [{ "fieldId": cost, "op": "GREATER_THAN", "value": "10" }] }),
+        )
+        .await;
+        assert_eq!(titles(result), ["Roof"]);
+        let result = data(
+            &schema,
+            query,
+            json!({ "filters": [{ "fieldId": cost, "op": "IS_NOT_SET" }] }),
+        )
+        .await;
+        assert_eq!(titles(result), ["Shed"]);
+        let result = data(
+            &schema,
+            query,
+            json!({ "sort": { "fieldId": cost, "descending": true } }),
+        )
+        .await;
+        assert_eq!(titles(result), ["Roof", "Fence", "Shed"]);
+
+        // Operators must suit the field
+        let response = execute(
+            &schema,
+            "testuser",
+            query,
+            json!({ "filters": [{ "fieldId": cost, "op": "CONTAINS", "value": "1" }] }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+
+        // Saved views store the predicates
+        let view = data(
+            &schema,
+            "mutation($filters: SavedViewFiltersInput!) { createSavedView(projectId: \"p1\", name: \"Pricey\", filters: $filters) { \
+             filters { customFields { fieldId op value } } } }",
+            json!({ "filters": {
+                "statuses": ["TODO"], "includeUnassigned": false, "assignedToMe": false, "tagIds": [],
+                "customFields": [{ "fieldId": cost, "op": "GREATER_THAN", "value": "10" }]
+            } }),
+        )
+        .await;
+        assert_eq!(
+            view["createSavedView"]["filters"]["customFields"],
+            json!([{ "fieldId": cost, "op": "GREATER_THAN", "value": "10" }])
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_custom_field_writes_are_merged() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let cost = data(
+            &schema,
+            CREATE_FIELD,
+            json!({ "input": { "name": "Cost", "kind": "NUMBER" } }),
+        )
+        .await["createCustomField"]["id"]
+            .clone();
+        let room = data(
+            &schema,
+            CREATE_FIELD,
+            json!({ "input": { "name": "Room", "kind": "TEXT" } }),
+        )
+        .await["createCustomField"]["id"]
+            .clone();
+        let task =
+            data(&schema, CREATE_TASK, json!({ "title": "Paint fence" })).await["createTask"]
+                .clone();
+        let stale = task["updatedAt"].clone();
+
+        const UPDATE: &str = "mutation($id: String!, $at: String!, $values: [CustomFieldValueInput!]) { \
+                              updateTask(id: $id, input: { customFields: $values }, lastKnownUpdatedAt: $at) { \
+                              updatedAt customFields { name value } } }";
+        let updated = data(
+            &schema,
+            UPDATE,
+            json!({ "id": task["id"], "at": stale, "values": [{ "fieldId": cost, "value": "5" }] }),
+        )
+        .await;
+        assert_ne!(updated["updateTask"]["updatedAt"], stale);
+
+        // Another field merges into the newer version
+        let merged = data(
+            &schema,
+            UPDATE,
+            json!({ "id": task["id"], "at": stale, "values": [{ "fieldId": room, "value": "Shed" }] }),
+        )
+        .await;
+        assert_eq!(
+            merged["updateTask"]["customFields"],
+            json!([{ "name": "Cost", "value": "5" }, { "name": "Room", "value": "Shed" }])
+        );
+
+        // The same field is a conflict rather than a silent overwrite
+        let response = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": task["id"], "at": stale, "values": [{ "fieldId": cost, "value": "7" }] }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+        let error = serde_json::to_value(&response.errors[0]).unwrap();
+        assert_eq!(error["extensions"]["code"], "CONFLICT_STALE_WRITE");
+        assert_eq!(
+            error["extensions"]["conflicts"][0]["field"],
+            json!(format!("customFields.{}", cost.as_str().unwrap()))
+        );
+    }
+}
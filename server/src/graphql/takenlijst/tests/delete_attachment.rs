@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "alice")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES ('t1', 'p1', 'user1', 'Fix tap', 'todo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, uploader, filename, content_type, has_thumbnail) in [
+            ("a1", "user1", "tap.jpg", "image/jpeg", true),
+            ("a2", "user1", "receipt.pdf", "application/pdf", false),
+        ] {
+            sqlx::query(
+                "INSERT INTO task_attachments (id, task_id, uploaded_by, filename, content_type, size_bytes, sha256, has_thumbnail) \
+                 VALUES (?, 't1', ?, ?, ?, 10, ?, ?)",
+            )
+            .bind(id)
+            .bind(uploader)
+            .bind(filename)
+            .bind(content_type)
+            .bind(format!("{:0>64}", id))
+            .bind(has_thumbnail)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(json!({})))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    #[tokio::test]
+    async fn tasks_list_attachments_and_only_uploader_or_owner_deletes() {
+        let schema = build(setup_test_db().await);
+
+        let response = execute(
+            &schema,
+            "alice",
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\") { items { attachments { id filename url thumbnailUrl } } } }",
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["tasks"]["items"][0]["attachments"],
+            json!([
+                { "id": "a1", "filename": "tap.jpg", "url": "/v1/attachments/a1", "thumbnailUrl": "/v1/attachments/a1/thumbnail" },
+                { "id": "a2", "filename": "receipt.pdf", "url": "/v1/attachments/a2", "thumbnailUrl": null },
+            ])
+        );
+
+        // alice didn't upload it and doesn't own the project
+        let response = execute(
+            &schema,
+            "alice",
+            "mutation { deleteAttachment(attachmentId: \"a1\") }",
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation { deleteAttachment(attachmentId: \"a1\") }",
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\") { items { attachments { id } } } }",
+        )
+        .await;
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["tasks"]["items"][0]["attachments"],
+            json!([{ "id": "a2" }])
+        );
+    }
+}
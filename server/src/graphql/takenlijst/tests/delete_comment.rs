@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/delete_comment resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::delete_comment::DeleteCommentMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_delete_comment() {
+        let _ = DeleteCommentMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/delete_project_template resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::delete_project_template::DeleteProjectTemplateMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_delete_project_template() {
+        let _ = DeleteProjectTemplateMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/delete_section resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::delete_section::DeleteSectionMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_delete_section() {
+        let _ = DeleteSectionMutation::default();
+        assert!(true);
+    }
+}
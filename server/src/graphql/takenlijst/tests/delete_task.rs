@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, parent, status) in [
+            ("paint", None, "todo"),
+            ("buy-paint", Some("paint"), "todo"),
+            ("typo", None, "todo"),
+            ("done", None, "done"),
+        ] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, parent_task_id, title, status, completed_at) \
+                 VALUES (?1, 'p1', 'user1', ?2, ?1, ?3, CASE WHEN ?3 = 'done' THEN strftime('%Y-%m-%d %H:%M:%f','now') END)",
+            )
+            .bind(id)
+            .bind(parent)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn delete(
+        pool: &SqlitePool,
+        schema: &crate::graphql::AppSchema,
+        id: &str,
+    ) -> async_graphql::Response {
+        let updated_at: (String,) = sqlx::query_as("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        execute(
+            schema,
+            "mutation($id: String!, $at: String!) { deleteTask(id: $id, lastKnownUpdatedAt: $at) { id deletedAt deletedBy } }",
+            json!({ "id": id, "at": updated_at.0 }),
+        )
+        .await
+    }
+
+    async fn ids(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        field: &str,
+    ) -> serde_json::Value {
+        let response = execute(schema, query, json!({})).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let ids: Vec<_> = data[field]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["id"].clone())
+            .collect();
+        json!(ids)
+    }
+
+    #[tokio::test]
+    async fn deleted_tasks_move_to_trash_and_can_be_restored() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = delete(&pool, &schema, "paint").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert!(data["deleteTask"]["deletedAt"].is_string());
+        assert_eq!(data["deleteTask"]["deletedBy"], "user1");
+        assert!(delete(&pool, &schema, "done").await.errors.is_empty());
+
+        assert_eq!(
+            ids(
+                &schema,
+                "{ tasks(projectId: \"p1\", timezone: \"UTC\", includeSubtasks: true) { items { id } } }",
+                "tasks"
+            )
+            .await,
+            json!(["typo"])
+        );
+        assert_eq!(
+            ids(
+                &schema,
+                "{ history(statuses: [DONE], projectId: \"p1\", timezone: \"UTC\") { items { id } } }",
+                "history"
+            )
+            .await,
+            json!([])
+        );
+
+        // The subtask is listed under its parent rather than on its own
+        let response = execute(
+            &schema,
+            "{ trash(projectId: \"p1\") { totalCount items { id subtasks { id } } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["trash"]["totalCount"], 2);
+        let paint = data["trash"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["id"] == "paint")
+            .unwrap();
+        assert_eq!(paint["subtasks"], json!([{ "id": "buy-paint" }]));
+
+        // Deleted tasks can't be changed, and subtasks wait for their parent
+        assert!(!delete(&pool, &schema, "paint").await.errors.is_empty());
+        let restore = "mutation($id: String!) { restoreDeletedTask(id: $id) { id deletedAt subtasks { id } } }";
+        assert!(
+            !execute(&schema, restore, json!({ "id": "buy-paint" }))
+                .await
+                .errors
+                .is_empty()
+        );
+
+        let response = execute(&schema, restore, json!({ "id": "paint" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["restoreDeletedTask"],
+            json!({ "id": "paint", "deletedAt": null, "subtasks": [{ "id": "buy-paint" }] })
+        );
+        assert_eq!(
+            ids(
+                &schema,
+                "{ trash(projectId: \"p1\") { items { id } } }",
+                "trash"
+            )
+            .await,
+            json!(["done"])
+        );
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/delete_time_entry resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::delete_time_entry::DeleteTimeEntryMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_delete_time_entry() {
+        let _ = DeleteTimeEntryMutation::default();
+        assert!(true);
+    }
+}
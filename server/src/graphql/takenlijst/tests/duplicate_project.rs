@@ -0,0 +1,339 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id, color) VALUES (?, ?, ?, ?)")
+            .bind("p1")
+            .bind("Move house")
+            .bind("user1")
+            .bind("#112233")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('tag1', 'boxes')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, title, status, scheduled) in [
+            ("t1", "Order boxes", "todo", Some("2025-03-01")),
+            ("t2", "Book van", "todo", Some("2025-03-10")),
+            ("t3", "Old chore", "done", Some("2025-02-01")),
+        ] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, title, status, scheduled_date) VALUES (?, 'p1', 'user1', ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(title)
+            .bind(status)
+            .bind(scheduled)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO task_tags (task_id, tag_id) VALUES ('t1', 'tag1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query(
+            "INSERT INTO saved_views (id, project_id, name, filters, created_by) VALUES ('v1', 'p1', 'Boxes', '{\"statuses\":[\"todo\"]}', 'user1')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            "INSERT INTO project_default_view (project_id, saved_view_id) VALUES ('p1', 'v1')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    #[tokio::test]
+    async fn duplicate_copies_open_tasks_views_and_tags() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { duplicateProject(projectId: $id) { id name color } }",
+            json!({ "id": "p1" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let project = &data["duplicateProject"];
+        assert_eq!(project["name"], "Move house (copy)");
+        assert_eq!(project["color"], "#112233");
+        let new_id = project["id"].as_str().unwrap().to_string();
+
+        let titles: Vec<(String, Option<String>)> = sqlx::query_as(
+            "SELECT title, scheduled_date FROM tasks WHERE project_id = ?1 ORDER BY title",
+        )
+        .bind(&new_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            titles,
+            vec![
+                ("Book van".to_string(), Some("2025-03-10".to_string())),
+                ("Order boxes".to_string(), Some("2025-03-01".to_string())),
+            ]
+        );
+
+        let tag_count: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM task_tags tt JOIN tasks t ON t.id = tt.task_id WHERE t.project_id = ?1",
+        )
+        .bind(&new_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(tag_count.0, 1);
+
+        let default_view: (String,) = sqlx::query_as(
+            "SELECT sv.name FROM project_default_view d JOIN saved_views sv ON sv.id = d.saved_view_id WHERE d.project_id = ?1",
+        )
+        .bind(&new_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(default_view.0, "Boxes");
+    }
+
+    #[tokio::test]
+    async fn duplicate_shifts_dates_to_anchor() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { duplicateProject(projectId: $id, name: \"Next move\", anchorDate: \"2025-06-01\") { id } }",
+            json!({ "id": "p1" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let new_id = data["duplicateProject"]["id"].as_str().unwrap().to_string();
+
+        let dates: Vec<(String,)> = sqlx::query_as(
+            "SELECT scheduled_date FROM tasks WHERE project_id = ?1 ORDER BY scheduled_date",
+        )
+        .bind(&new_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            dates,
+            vec![("2025-06-01".to_string(),), ("2025-06-10".to_string(),)]
+        );
+    }
+
+    #[tokio::test]
+    async fn template_round_trip() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "mutation { saveProjectAsTemplate(projectId: \"p1\", name: \"Moving\") { id taskCount savedViewCount } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["saveProjectAsTemplate"]["taskCount"], 2);
+        assert_eq!(data["saveProjectAsTemplate"]["savedViewCount"], 1);
+        let template_id = data["saveProjectAsTemplate"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // Duplicate names are rejected per owner
+        let response = execute(
+            &schema,
+            "mutation { saveProjectAsTemplate(projectId: \"p1\", name: \" moving \") { id } }",
+            json!({}),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { createProjectFromTemplate(templateId: $id, name: \"Move again\") { id name } }",
+            json!({ "id": template_id }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["createProjectFromTemplate"]["name"], "Move again");
+        let new_id = data["createProjectFromTemplate"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tasks WHERE project_id = ?1")
+            .bind(&new_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count.0, 2);
+
+        let response = execute(&schema, "{ projectTemplates { name } }", json!({})).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["projectTemplates"][0]["name"], "Moving");
+    }
+
+    #[tokio::test]
+    async fn duplicate_keeps_fields_statuses_and_paused_series() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        sqlx::query(
+            "INSERT INTO custom_fields (id, project_id, name, kind) VALUES ('f1', 'p1', 'Cost', 'number')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO task_custom_values (task_id, field_id, value) VALUES ('t1', 'f1', 12.5)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO project_statuses (id, project_id, name, category, position) VALUES ('st1', 'p1', 'Waiting', 'open', 5)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE tasks SET status_id = 'st1' WHERE id = 't2'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE saved_views SET filters = '{\"statuses\":[\"todo\"],\"customFields\":[{\"fieldId\":\"f1\",\"op\":\"is_set\",\"value\":null}]}' WHERE id = 'v1'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, deadline_offset_minutes, paused_at, materialized_through) \
+             VALUES ('s1', 'p1', 'user1', 'Water plants', 'FREQ=DAILY', '2025-03-01', 0, CURRENT_TIMESTAMP, '2025-03-05')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { duplicateProject(projectId: $id) { id } }",
+            json!({ "id": "p1" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let new_id = data["duplicateProject"]["id"].as_str().unwrap().to_string();
+
+        let (field_id,): (String,) =
+            sqlx::query_as("SELECT id FROM custom_fields WHERE project_id = ?1 AND name = 'Cost'")
+                .bind(&new_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let (value_type,): (String,) = sqlx::query_as(
+            "SELECT typeof(v.value) FROM task_custom_values v JOIN tasks t ON t.id = v.task_id \
+             WHERE t.project_id = ?1 AND t.title = 'Order boxes' AND v.field_id = ?2",
+        )
+        .bind(&new_id)
+        .bind(&field_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(value_type, "real");
+
+        let (status,): (String,) = sqlx::query_as(
+            "SELECT s.name FROM tasks t JOIN project_statuses s ON s.id = t.status_id \
+             WHERE t.project_id = ?1 AND t.title = 'Book van'",
+        )
+        .bind(&new_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(status, "Waiting");
+        let (status_count,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM project_statuses WHERE project_id = ?1")
+                .bind(&new_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status_count, 4);
+
+        let (filters,): (String,) =
+            sqlx::query_as("SELECT filters FROM saved_views WHERE project_id = ?1")
+                .bind(&new_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let filters: serde_json::Value = serde_json::from_str(&filters).unwrap();
+        assert_eq!(filters["customFields"][0]["fieldId"], json!(field_id));
+
+        let (paused_at, materialized_through): (Option<String>, Option<String>) = sqlx::query_as(
+            "SELECT paused_at, materialized_through FROM recurring_series WHERE project_id = ?1",
+        )
+        .bind(&new_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(paused_at.is_some());
+        assert_eq!(materialized_through.as_deref(), Some("2025-03-05"));
+
+        let (created,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM task_activity WHERE project_id = ?1 AND action = 'created'",
+        )
+        .bind(&new_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(created, 2);
+    }
+}
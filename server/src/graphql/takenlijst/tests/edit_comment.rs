@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/edit_comment resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::edit_comment::EditCommentMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_edit_comment() {
+        let _ = EditCommentMutation::default();
+        assert!(true);
+    }
+}
@@ -1,20 +1,67 @@
+mod add_comment;
 mod add_project_member_by_username;
+mod add_task_dependency;
+mod add_task_reminder;
+mod add_time_entry;
 mod archive_project;
+mod bulk_update_tasks;
 mod create_project;
+mod create_project_from_template;
 mod create_recurring_series;
 mod create_saved_view;
+mod create_section;
 mod create_tag;
+mod custom_fields;
+mod delete_attachment;
+mod delete_comment;
+mod delete_project_template;
 mod delete_saved_view;
+mod delete_section;
 mod delete_tag;
+mod delete_task;
+mod delete_time_entry;
+mod duplicate_project;
+mod edit_comment;
 mod history_query;
 mod integration;
+mod move_task;
+mod move_task_after;
+mod move_task_before;
+mod move_task_to_status;
 mod project_default_saved_view_query;
 mod projects_query;
+mod quick_add_preview_query;
+mod quick_add_task;
+mod remove_task_dependency;
+mod remove_task_reminder;
 mod rename_project;
+mod rename_section;
 mod rename_tag;
+mod reorder_projects;
+mod reorder_sections;
+mod restore_deleted_task;
+mod save_project_as_template;
 mod saved_views_query;
+mod sections_query;
+mod series_lifecycle;
 mod set_project_default_saved_view;
+mod set_project_dependency_enforcement;
+mod set_project_favorite;
+mod snooze_task;
+mod start_timer;
+mod stop_timer;
+mod subtasks;
 mod tags_query;
+mod task_activity;
+mod task_priority;
 mod tasks_query;
+mod time_report_query;
 mod unarchive_project;
+mod unwatch_task;
+mod update_custom_field;
+mod update_project_appearance;
+mod update_recurring_series;
 mod update_saved_view;
+mod update_task_merge;
+mod update_time_entry;
+mod watch_task;
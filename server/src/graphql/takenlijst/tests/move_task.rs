@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "other")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query(
+            "INSERT INTO projects (id, name, owner_id) VALUES \
+             ('p1', 'House', 'user1'), ('p2', 'Garden', 'user1'), ('p3', 'Theirs', 'user2')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2'), ('p2', 'user2')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, deadline_offset_minutes) \
+             VALUES ('series1', 'p1', 'user1', 'Water plants', 'FREQ=WEEKLY', '2025-01-01', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, series) in [("hedge", None), ("water", Some("series1"))] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, assignee_id, series_id, title, status) \
+                 VALUES (?1, 'p1', 'user1', 'user2', ?2, ?1, 'todo')",
+            )
+            .bind(id)
+            .bind(series)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn move_task(
+        pool: &SqlitePool,
+        schema: &crate::graphql::AppSchema,
+        id: &str,
+        target: &str,
+        policy: &str,
+    ) -> async_graphql::Response {
+        let (updated_at,): (String,) = sqlx::query_as("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        execute(
+            schema,
+            "mutation($id: String!, $target: String!, $at: String!, $policy: SeriesMovePolicy!) { \
+             moveTask(id: $id, targetProjectId: $target, lastKnownUpdatedAt: $at, seriesPolicy: $policy) { \
+             projectId assigneeId seriesId } }",
+            json!({ "id": id, "target": target, "at": updated_at, "policy": policy }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn move_task_checks_both_projects_and_series() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        // Not a member of the target project
+        let response = move_task(&pool, &schema, "hedge", "p3", "REFUSE").await;
+        assert_eq!(
+            response.errors[0].message,
+            "Project not found or access denied"
+        );
+
+        let response = move_task(&pool, &schema, "hedge", "p2", "REFUSE").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["moveTask"],
+            json!({ "projectId": "p2", "assigneeId": "user2", "seriesId": null })
+        );
+
+        let response = move_task(&pool, &schema, "water", "p2", "REFUSE").await;
+        assert_eq!(
+            response.errors[0].message,
+            "Task belongs to a recurring series"
+        );
+
+        let response = move_task(&pool, &schema, "water", "p2", "DETACH").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["moveTask"]["seriesId"], json!(null));
+        assert_eq!(data["moveTask"]["projectId"], "p2");
+    }
+
+    #[tokio::test]
+    async fn moved_tasks_drop_values_of_the_old_projects_fields() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        sqlx::query(
+            "INSERT INTO custom_fields (id, project_id, name, kind) VALUES ('cost', 'p1', 'Cost', 'number')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO task_custom_values (task_id, field_id, value) VALUES ('hedge', 'cost', 40.0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let response = move_task(&pool, &schema, "hedge", "p2", "REFUSE").await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let (values,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM task_custom_values WHERE task_id = 'hedge'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(values, 0);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/move_task_after resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::move_task_after::MoveTaskAfterMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_move_task_after() {
+        let _ = MoveTaskAfterMutation::default();
+        assert!(true);
+    }
+}
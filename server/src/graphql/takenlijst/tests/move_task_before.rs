@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES ('user1', 'testuser', 'password', 'Test')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1'), ('p2', 'Garden', 'user1')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status, position) VALUES \
+             ('a', 'p1', 'user1', 'A', 'todo', '000001V'), \
+             ('b', 'p1', 'user1', 'B', 'todo', '000002V'), \
+             ('c', 'p1', 'user1', 'C', 'todo', '000003V')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES ('weeds', 'p2', 'user1', 'Pull weeds', 'todo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn manual_order(schema: &crate::graphql::AppSchema) -> Vec<String> {
+        let response = execute(
+            schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", sort: MANUAL) { items { title } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()["tasks"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|task| task["title"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn tasks_move_before_and_after_each_other() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let before = "mutation($id: String!, $anchor: String!) { \
+                      moveTaskBefore(id: $id, beforeId: $anchor) { id position } }";
+        let after = "mutation($id: String!, $anchor: String!) { \
+                     moveTaskAfter(id: $id, afterId: $anchor) { id position } }";
+
+        // New tasks go to the end
+        let response = execute(
+            &schema,
+            "mutation { createTask(input: { projectId: \"p1\", title: \"D\" }) { id } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let d = response.data.into_json().unwrap()["createTask"]["id"].clone();
+        assert_eq!(manual_order(&schema).await, ["A", "B", "C", "D"]);
+
+        let response = execute(&schema, before, json!({ "id": "c", "anchor": "b" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert!(!response.data.into_json().unwrap()["moveTaskBefore"]["position"].is_null());
+        assert_eq!(manual_order(&schema).await, ["A", "C", "B", "D"]);
+
+        // Only the moved task gets a new rank
+        let (unchanged,) =
+            sqlx::query_as::<_, (String,)>("SELECT position FROM tasks WHERE id = 'a'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(unchanged, "000001V");
+
+        let response = execute(&schema, after, json!({ "id": "a", "anchor": "b" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(manual_order(&schema).await, ["C", "B", "A", "D"]);
+
+        let response = execute(&schema, before, json!({ "id": d, "anchor": "c" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let response = execute(&schema, after, json!({ "id": "b", "anchor": d })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(manual_order(&schema).await, ["D", "B", "C", "A"]);
+
+        // Tasks of another project can't be used as an anchor, nor can the task itself
+        for anchor in ["weeds", "a"] {
+            let response = execute(&schema, before, json!({ "id": "a", "anchor": anchor })).await;
+            assert_eq!(
+                response.errors[0].extensions.as_ref().unwrap().get("code"),
+                Some(&async_graphql::Value::from("VALIDATION_FAILED"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn moving_to_another_project_appends_the_task() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let (updated_at,) =
+            sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = 'weeds'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let response = execute(
+            &schema,
+            "mutation($at: String!) { moveTask(id: \"weeds\", targetProjectId: \"p1\", lastKnownUpdatedAt: $at) { id } }",
+            json!({ "at": updated_at }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // The moved task joins the end of the new project
+        assert_eq!(manual_order(&schema).await, ["A", "B", "C", "Pull weeds"]);
+
+        // A task without a rank, like one from before manual ordering, still works as an
+        // anchor; it and anything unranked before it are ranked first
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status, created_at) VALUES \
+             ('e', 'p1', 'user1', 'E', 'todo', '2025-01-01 10:00:00.000'), \
+             ('f', 'p1', 'user1', 'F', 'todo', '2025-01-01 10:00:01.000')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            manual_order(&schema).await,
+            ["A", "B", "C", "Pull weeds", "E", "F"]
+        );
+        let response = execute(
+            &schema,
+            "mutation { moveTaskBefore(id: \"a\", beforeId: \"f\") { id } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            manual_order(&schema).await,
+            ["B", "C", "Pull weeds", "E", "A", "F"]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES \
+             ('user1', 'testuser', 'password', 'Test'), \
+             ('user2', 'rob', 'password', 'Rob')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES \
+             ('t1', 'p1', 'user1', 'Paint fence', 'todo'), ('t2', 'p1', 'user1', 'Fix gate', 'todo')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    const STATUSES: &str = "{ projectStatuses(projectId: \"p1\") { id name category } }";
+    const MOVE: &str = "mutation($id: String!, $statusId: String!, $at: String!) { \
+                        moveTaskToStatus(id: $id, statusId: $statusId, lastKnownUpdatedAt: $at) { \
+                        status statusId completedAt workflowStatus { name } updatedAt } }";
+
+    async fn updated_at(pool: &SqlitePool, id: &str) -> String {
+        sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn tasks_move_through_a_custom_workflow() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        // Projects start with one status per category, and tasks in the open one
+        let data = execute(&schema, "testuser", STATUSES, json!({}))
+            .await
+            .data
+            .into_json()
+            .unwrap();
+        let statuses = data["projectStatuses"].as_array().unwrap();
+        let names: Vec<_> = statuses.iter().map(|s| s["name"].clone()).collect();
+        assert_eq!(names, vec!["To do", "Done", "Abandoned"]);
+        let (todo, done) = (statuses[0]["id"].clone(), statuses[1]["id"].clone());
+        let (status_id,) =
+            sqlx::query_as::<_, (String,)>("SELECT status_id FROM tasks WHERE id = 't1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(json!(status_id), todo);
+
+        // Only the owner shapes the workflow
+        let create = "mutation($name: String!) { createProjectStatus(projectId: \"p1\", name: $name, category: OPEN) { id } }";
+        let response = execute(&schema, "rob", create, json!({ "name": "Doing" })).await;
+        assert_eq!(response.errors.len(), 1);
+        let response = execute(&schema, "testuser", create, json!({ "name": "Doing" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let doing = response.data.into_json().unwrap()["createProjectStatus"]["id"].clone();
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation($ids: [String!]!) { reorderProjectStatuses(projectId: \"p1\", statusIds: $ids) { name } }",
+            json!({ "ids": [todo, doing] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // Moving within a category leaves the task open
+        let response = execute(
+            &schema,
+            "rob",
+            MOVE,
+            json!({ "id": "t1", "statusId": doing, "at": updated_at(&pool, "t1").await }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["moveTaskToStatus"].clone();
+        assert_eq!(task["status"], "TODO");
+        assert_eq!(task["workflowStatus"]["name"], "Doing");
+
+        // Moving to a done status completes it; moving back reopens it
+        let response = execute(
+            &schema,
+            "rob",
+            MOVE,
+            json!({ "id": "t1", "statusId": done, "at": task["updatedAt"] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["moveTaskToStatus"].clone();
+        assert_eq!(task["status"], "DONE");
+        assert!(task["completedAt"].is_string());
+        let response = execute(
+            &schema,
+            "rob",
+            MOVE,
+            json!({ "id": "t1", "statusId": doing, "at": task["updatedAt"] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["moveTaskToStatus"].clone();
+        assert_eq!(task["status"], "TODO");
+        assert!(task["completedAt"].is_null());
+
+        let board =
+            "{ tasksByStatus(projectId: \"p1\") { status { name } totalCount items { id } } }";
+        let data = execute(&schema, "rob", board, json!({}))
+            .await
+            .data
+            .into_json()
+            .unwrap();
+        let columns = data["tasksByStatus"].as_array().unwrap();
+        let counts: Vec<_> = columns
+            .iter()
+            .map(|c| (c["status"]["name"].clone(), c["totalCount"].clone()))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                (json!("To do"), json!(1)),
+                (json!("Doing"), json!(1)),
+                (json!("Done"), json!(0)),
+                (json!("Abandoned"), json!(0)),
+            ]
+        );
+
+        // Deleting a status moves its tasks to another of the same category
+        let delete = "mutation($id: String!) { deleteProjectStatus(id: $id) }";
+        let response = execute(&schema, "testuser", delete, json!({ "id": doing })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let (status_id,) =
+            sqlx::query_as::<_, (String,)>("SELECT status_id FROM tasks WHERE id = 't1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(json!(status_id), todo);
+        // ...and the move shows up in their history
+        let (changes,) = sqlx::query_as::<_, (String,)>(
+            "SELECT changes FROM task_activity WHERE task_id = 't1' ORDER BY created_at DESC, rowid DESC LIMIT 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let changes: serde_json::Value = serde_json::from_str(&changes).unwrap();
+        assert_eq!(changes[0]["field"], "statusId");
+        assert_eq!(changes[0]["old"], doing);
+        assert_eq!(changes[0]["new"], todo);
+        let response = execute(&schema, "testuser", delete, json!({ "id": done })).await;
+        assert_eq!(response.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn completing_a_task_puts_it_in_a_done_status() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation($at: String!) { completeTask(id: \"t2\", lastKnownUpdatedAt: $at) { workflowStatus { name category } } }",
+            json!({ "at": updated_at(&pool, "t2").await }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["completeTask"]["workflowStatus"],
+            json!({ "name": "Done", "category": "DONE" })
+        );
+
+        // A completed task can't be moved straight to a cancelled status
+        let data = execute(&schema, "testuser", STATUSES, json!({}))
+            .await
+            .data
+            .into_json()
+            .unwrap();
+        let abandoned = data["projectStatuses"][2]["id"].clone();
+        let response = execute(
+            &schema,
+            "testuser",
+            MOVE,
+            json!({ "id": "t2", "statusId": abandoned, "at": updated_at(&pool, "t2").await }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+    }
+}
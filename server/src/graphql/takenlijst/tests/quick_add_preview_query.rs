@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES ('user1', 'testuser', 'password', 'Test')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('tag-chores', 'chores')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    #[tokio::test]
+    async fn preview_parses_without_saving() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let request = Request::new(
+            "query($text: String!) { previewQuickAdd(projectId: \"p1\", text: $text, timezone: \"Europe/Amsterdam\") { \
+             title scheduledDate scheduledTimeMinutes priority tags { name id } assigneeUsername assigneeId rrule } }",
+        )
+        .variables(Variables::from_json(json!({
+            "text": "Take bins out 7pm every monday #chores #outside @nobody !urgent"
+        })))
+        .data(Arc::new(Claims {
+            sub: "testuser".to_string(),
+            exp: 9999999999,
+        }));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let preview = response.data.into_json().unwrap()["previewQuickAdd"].clone();
+
+        assert_eq!(preview["title"], "Take bins out");
+        assert_eq!(preview["scheduledTimeMinutes"], 19 * 60);
+        assert_eq!(preview["priority"], "URGENT");
+        assert_eq!(preview["rrule"], "FREQ=WEEKLY;BYDAY=MO");
+        assert!(!preview["scheduledDate"].is_null());
+        assert_eq!(
+            preview["tags"],
+            json!([{ "name": "chores", "id": "tag-chores" }, { "name": "outside", "id": null }])
+        );
+        // Unknown usernames are reported rather than rejected
+        assert_eq!(preview["assigneeUsername"], "nobody");
+        assert!(preview["assigneeId"].is_null());
+
+        // Nothing was created
+        let (tags,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tags")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tags, 1);
+    }
+}
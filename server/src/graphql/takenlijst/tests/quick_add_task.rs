@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES \
+             ('user1', 'testuser', 'password', 'Test'), \
+             ('user2', 'rob', 'password', 'Rob'), \
+             ('user3', 'stranger', 'password', 'Stranger')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('tag-chores', 'chores')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    const QUICK_ADD: &str = "mutation($text: String!) { quickAddTask(projectId: \"p1\", text: $text) { \
+                             task { id title scheduledDate scheduledTimeMinutes priority assigneeId } \
+                             series { id title rrule dtstartDate dtstartTimeMinutes defaultTagIds } } }";
+
+    #[tokio::test]
+    async fn quick_add_creates_a_task() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            QUICK_ADD,
+            json!({ "text": "Take bins out tomorrow 7pm #chores #Outside @Rob !high" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let task = &data["quickAddTask"]["task"];
+        assert!(data["quickAddTask"]["series"].is_null());
+        assert_eq!(task["title"], "Take bins out");
+        let tomorrow = Utc::now().date_naive() + Duration::days(1);
+        assert_eq!(
+            task["scheduledDate"],
+            tomorrow.format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(task["scheduledTimeMinutes"], 19 * 60);
+        assert_eq!(task["priority"], "HIGH");
+        assert_eq!(task["assigneeId"], "user2");
+
+        // The existing tag is reused and the new one is created
+        let tags = sqlx::query_as::<_, (String, String)>(
+            "SELECT g.id, g.name FROM task_tags tt INNER JOIN tags g ON g.id = tt.tag_id \
+             WHERE tt.task_id = ?1 ORDER BY g.name",
+        )
+        .bind(task["id"].as_str().unwrap())
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0], ("tag-chores".to_string(), "chores".to_string()));
+        assert_eq!(tags[1].1, "outside");
+
+        // Only project members can be assigned, something has to be left for a title and that
+        // title has to fit
+        let long_title = format!("{} #garden", "Mow ".repeat(40));
+        for text in [
+            "Mow the lawn @stranger #garden",
+            "tomorrow #chores",
+            &long_title,
+        ] {
+            let response = execute(&schema, QUICK_ADD, json!({ "text": text })).await;
+            assert_eq!(response.errors.len(), 1, "{}", text);
+        }
+        // Rejected lines leave no tags behind
+        let (garden,) =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tags WHERE name = 'garden'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(garden, 0);
+    }
+
+    #[tokio::test]
+    async fn quick_add_with_a_recurrence_creates_a_series() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            QUICK_ADD,
+            json!({ "text": "Water plants every day 23:59 #chores" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert!(data["quickAddTask"]["task"].is_null());
+        let series = &data["quickAddTask"]["series"];
+        assert_eq!(series["title"], "Water plants");
+        assert_eq!(series["rrule"], "FREQ=DAILY");
+        assert_eq!(series["dtstartTimeMinutes"], 23 * 60 + 59);
+        assert_eq!(series["defaultTagIds"], json!(["tag-chores"]));
+
+        // Tasks were generated for it
+        let (count,) =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tasks WHERE series_id = ?1")
+                .bind(series["id"].as_str().unwrap())
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(count > 0);
+    }
+}
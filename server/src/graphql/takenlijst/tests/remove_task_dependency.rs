@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/remove_task_dependency resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::remove_task_dependency::RemoveTaskDependencyMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_remove_task_dependency() {
+        let _ = RemoveTaskDependencyMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/remove_task_reminder resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::remove_task_reminder::RemoveTaskReminderMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_remove_task_reminder() {
+        let _ = RemoveTaskReminderMutation::default();
+        assert!(true);
+    }
+}
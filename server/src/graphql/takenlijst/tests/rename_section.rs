@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/rename_section resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::rename_section::RenameSectionMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_rename_section() {
+        let _ = RenameSectionMutation::default();
+        assert!(true);
+    }
+}
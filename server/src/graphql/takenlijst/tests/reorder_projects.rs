@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, created_at) in [
+            ("p1", "2025-01-01 10:00:00"),
+            ("p2", "2025-01-02 10:00:00"),
+            ("p3", "2025-01-03 10:00:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO projects (id, name, owner_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(format!("Project {}", id))
+            .bind("user1")
+            .bind(created_at)
+            .bind(created_at)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn project_ids(schema: &crate::graphql::AppSchema) -> Vec<String> {
+        let request = Request::new("{ projects { id } }").data(Arc::new(Claims {
+            sub: "testuser".to_string(),
+            exp: 9999999999,
+        }));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["projects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    #[tokio::test]
+    async fn projects_default_to_newest_first() {
+        let schema = build(setup_test_db().await);
+        assert_eq!(project_ids(&schema).await, vec!["p3", "p2", "p1"]);
+    }
+
+    #[tokio::test]
+    async fn reorder_projects_applies_manual_order() {
+        let schema = build(setup_test_db().await);
+
+        let response = execute(
+            &schema,
+            "mutation($ids: [String!]!) { reorderProjects(projectIds: $ids) }",
+            json!({ "ids": ["p1", "p3"] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // Unlisted p2 keeps its place after the listed projects
+        assert_eq!(project_ids(&schema).await, vec!["p1", "p3", "p2"]);
+    }
+
+    #[tokio::test]
+    async fn favorites_are_listed_first() {
+        let schema = build(setup_test_db().await);
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { setProjectFavorite(projectId: $id, isFavorite: true) { id isFavorite } }",
+            json!({ "id": "p1" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        assert_eq!(project_ids(&schema).await, vec!["p1", "p3", "p2"]);
+    }
+
+    #[tokio::test]
+    async fn reorder_projects_rejects_unknown_project() {
+        let schema = build(setup_test_db().await);
+
+        let response = execute(
+            &schema,
+            "mutation($ids: [String!]!) { reorderProjects(projectIds: $ids) }",
+            json!({ "ids": ["p1", "nope"] }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+
+        // Nothing was written
+        assert_eq!(project_ids(&schema).await, vec!["p3", "p2", "p1"]);
+    }
+}
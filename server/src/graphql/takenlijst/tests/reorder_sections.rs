@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/reorder_sections resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::reorder_sections::ReorderSectionsMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_reorder_sections() {
+        let _ = ReorderSectionsMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/restore_deleted_task resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::restore_deleted_task::RestoreDeletedTaskMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_restore_deleted_task() {
+        let _ = RestoreDeletedTaskMutation::default();
+        assert!(true);
+    }
+}
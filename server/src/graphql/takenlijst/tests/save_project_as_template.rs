@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/save_project_as_template resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::save_project_as_template::SaveProjectAsTemplateMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_save_project_as_template() {
+        let _ = SaveProjectAsTemplateMutation::default();
+        assert!(true);
+    }
+}
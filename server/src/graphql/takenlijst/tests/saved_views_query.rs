@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
     use crate::graphql::takenlijst::types::SavedViewFilters;
-    use crate::tasks::TaskStatus;
+    use crate::tasks::{TaskPriority, TaskSort, TaskStatus};
     use serde_json;
 
     #[test]
@@ -12,6 +12,11 @@ mod tests {
             include_unassigned: false,
             assigned_to_me: true,
             tag_ids: vec!["tag1".to_string(), "tag2".to_string()],
+            section_ids: vec!["section1".to_string()],
+            include_unsectioned: true,
+            priorities: vec![TaskPriority::High, TaskPriority::Urgent],
+            sort: TaskSort::Priority,
+            custom_fields: vec![],
         };
 
         // Test serialization
@@ -26,6 +31,24 @@ mod tests {
         assert_eq!(parsed.include_unassigned, false);
         assert_eq!(parsed.assigned_to_me, true);
         assert_eq!(parsed.tag_ids.len(), 2);
+        assert_eq!(parsed.section_ids, vec!["section1".to_string()]);
+        assert_eq!(parsed.include_unsectioned, true);
+        assert_eq!(
+            parsed.priorities,
+            vec![TaskPriority::High, TaskPriority::Urgent]
+        );
+        assert_eq!(parsed.sort, TaskSort::Priority);
+    }
+
+    #[test]
+    fn test_saved_view_filters_without_sections() {
+        // Views saved before sections existed still deserialize
+        let json = r#"{"statuses":["todo"],"assignee":null,"includeUnassigned":false,"assignedToMe":false,"tagIds":[]}"#;
+        let parsed: SavedViewFilters = serde_json::from_str(json).expect("Should deserialize");
+        assert!(parsed.section_ids.is_empty());
+        assert!(parsed.priorities.is_empty());
+        assert_eq!(parsed.sort, TaskSort::Default);
+        assert_eq!(parsed.include_unsectioned, false);
     }
 
     #[test]
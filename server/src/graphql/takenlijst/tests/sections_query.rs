@@ -0,0 +1,237 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn create_section(schema: &crate::graphql::AppSchema, name: &str) -> String {
+        let response = execute(
+            schema,
+            "mutation($name: String!) { createSection(projectId: \"p1\", name: $name) { id } }",
+            json!({ "name": name }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["createSection"]["id"].as_str().unwrap().to_string()
+    }
+
+    async fn create_task(
+        schema: &crate::graphql::AppSchema,
+        title: &str,
+        section_id: Option<&str>,
+    ) -> String {
+        let response = execute(
+            schema,
+            "mutation($input: CreateTaskInput!) { createTask(input: $input) { id sectionId } }",
+            json!({ "input": { "projectId": "p1", "title": title, "sectionId": section_id } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["createTask"]["sectionId"].as_str(), section_id);
+        data["createTask"]["id"].as_str().unwrap().to_string()
+    }
+
+    async fn section_names(schema: &crate::graphql::AppSchema) -> Vec<String> {
+        let response = execute(
+            schema,
+            "{ sections(projectId: \"p1\") { name } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["sections"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sections_are_appended_and_reordered() {
+        let schema = build(setup_test_db().await);
+        let kitchen = create_section(&schema, "Kitchen").await;
+        let garden = create_section(&schema, "Garden").await;
+        assert_eq!(section_names(&schema).await, vec!["Kitchen", "Garden"]);
+
+        // Names are unique per project regardless of case
+        let response = execute(
+            &schema,
+            "mutation { createSection(projectId: \"p1\", name: \"kitchen\") { id } }",
+            json!({}),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "mutation($ids: [String!]!) { reorderSections(projectId: \"p1\", sectionIds: $ids) { id } }",
+            json!({ "ids": [garden] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(section_names(&schema).await, vec!["Garden", "Kitchen"]);
+
+        let response = execute(
+            &schema,
+            "mutation($ids: [String!]!) { reorderSections(projectId: \"p1\", sectionIds: $ids) { id } }",
+            json!({ "ids": [kitchen, "missing"] }),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn tasks_filter_and_group_by_section() {
+        let schema = build(setup_test_db().await);
+        let kitchen = create_section(&schema, "Kitchen").await;
+        let garden = create_section(&schema, "Garden").await;
+        create_task(&schema, "Mow lawn", Some(&garden)).await;
+        create_task(&schema, "Clean oven", Some(&kitchen)).await;
+        create_task(&schema, "Call plumber", None).await;
+
+        let response = execute(
+            &schema,
+            "query($ids: [String!]) { tasks(projectId: \"p1\", timezone: \"UTC\", sectionIds: $ids) { items { title } } }",
+            json!({ "ids": [garden] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["tasks"]["items"], json!([{ "title": "Mow lawn" }]));
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", includeUnsectioned: true) { items { title } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["tasks"]["items"], json!([{ "title": "Call plumber" }]));
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", groupBySection: true) { groups { section { name } items { title } } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["tasks"]["groups"],
+            json!([
+                { "section": null, "items": [{ "title": "Call plumber" }] },
+                { "section": { "name": "Kitchen" }, "items": [{ "title": "Clean oven" }] },
+                { "section": { "name": "Garden" }, "items": [{ "title": "Mow lawn" }] },
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn deleting_section_keeps_tasks_unsectioned() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let kitchen = create_section(&schema, "Kitchen").await;
+        let task_id = create_task(&schema, "Clean oven", Some(&kitchen)).await;
+
+        let response = execute(
+            &schema,
+            "mutation($id: String!) { deleteSection(sectionId: $id) }",
+            json!({ "id": kitchen }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let section: (Option<String>,) =
+            sqlx::query_as("SELECT section_id FROM tasks WHERE id = ?1")
+                .bind(&task_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(section.0, None);
+        assert!(section_names(&schema).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn update_task_moves_between_sections() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let kitchen = create_section(&schema, "Kitchen").await;
+        let task_id = create_task(&schema, "Clean oven", None).await;
+
+        let updated_at: (String,) = sqlx::query_as("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(&task_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let response = execute(
+            &schema,
+            "mutation($id: String!, $section: String, $at: String!) { updateTask(id: $id, input: { sectionId: $section }, lastKnownUpdatedAt: $at) { sectionId updatedAt } }",
+            json!({ "id": task_id, "section": kitchen, "at": updated_at.0 }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(
+            data["updateTask"]["sectionId"].as_str(),
+            Some(kitchen.as_str())
+        );
+        let updated_at = data["updateTask"]["updatedAt"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // Explicit null clears the section
+        let response = execute(
+            &schema,
+            "mutation($id: String!, $at: String!) { updateTask(id: $id, input: { sectionId: null }, lastKnownUpdatedAt: $at) { sectionId } }",
+            json!({ "id": task_id, "at": updated_at }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["updateTask"]["sectionId"], json!(null));
+    }
+}
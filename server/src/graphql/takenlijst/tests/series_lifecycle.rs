@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use crate::tasks::series;
+    use async_graphql::{Request, Variables};
+    use chrono::Utc;
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password) VALUES ('user1', 'testuser', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone) \
+             VALUES ('s1', 'p1', 'user1', 'Water plants', 'FREQ=DAILY', '2099-01-01', 480, 0, 'Europe/Amsterdam')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        series::materialize(&mut *pool.acquire().await.unwrap(), "s1", Utc::now())
+            .await
+            .unwrap();
+        // The first occurrence was done early
+        sqlx::query(
+            "UPDATE tasks SET status = 'done', completed_at = (strftime('%Y-%m-%d %H:%M:%f','now')), completed_by = 'user1' \
+             WHERE occurrence_date = '2099-01-01'",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    /// Occurrence dates of the series' tasks that aren't in the trash
+    async fn occurrences(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_as::<_, (String,)>(
+            "SELECT occurrence_date FROM tasks WHERE series_id = 's1' AND deleted_at IS NULL ORDER BY occurrence_date",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.0)
+        .collect()
+    }
+
+    const HISTORY: &str = "{ history(statuses: [DONE], timezone: \"UTC\") { items { title seriesId series { untilDate } } } }";
+
+    #[tokio::test]
+    async fn series_can_be_paused_ended_and_deleted() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let all = [
+            "2099-01-01",
+            "2099-01-02",
+            "2099-01-03",
+            "2099-01-04",
+            "2099-01-05",
+        ];
+        assert_eq!(occurrences(&pool).await, all);
+
+        // Pausing clears the upcoming tasks, and resuming brings them back
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation { pauseSeries(id: \"s1\") { pausedAt } }",
+            json!({}),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert!(data["pauseSeries"]["pausedAt"].is_string());
+        assert_eq!(occurrences(&pool).await, ["2099-01-01"]);
+        assert_eq!(series::materialize_all(&pool, Utc::now()).await.unwrap(), 0);
+
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation { resumeSeries(id: \"s1\") { pausedAt } }",
+            json!({}),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert!(data["resumeSeries"]["pausedAt"].is_null());
+        assert_eq!(occurrences(&pool).await, all);
+
+        // Ending it trashes the later open tasks; done ones still show the series
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation { endSeries(id: \"s1\", untilDate: \"2099-01-03\") { rrule untilDate } }",
+            json!({}),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["endSeries"]["untilDate"], "2099-01-03");
+        assert_eq!(
+            occurrences(&pool).await,
+            ["2099-01-01", "2099-01-02", "2099-01-03"]
+        );
+        assert_eq!(series::materialize_all(&pool, Utc::now()).await.unwrap(), 0);
+        let data = execute(&schema, "testuser", HISTORY, json!({}))
+            .await
+            .data
+            .into_json()
+            .unwrap();
+        assert_eq!(
+            data["history"]["items"],
+            json!([{ "title": "Water plants", "seriesId": "s1", "series": { "untilDate": "2099-01-03" } }])
+        );
+
+        // Deleting it keeps the history as ordinary tasks
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation { deleteSeries(id: \"s1\") }",
+            json!({}),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let (live,) =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tasks WHERE deleted_at IS NULL")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(live, 1);
+        let data = execute(&schema, "testuser", HISTORY, json!({}))
+            .await
+            .data
+            .into_json()
+            .unwrap();
+        assert_eq!(
+            data["history"]["items"],
+            json!([{ "title": "Water plants", "seriesId": null, "series": null }])
+        );
+    }
+
+    #[tokio::test]
+    async fn deleting_a_series_can_purge_its_history() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation { deleteSeries(id: \"s1\", purgeHistory: true) }",
+            json!({}),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let (live, trashed) = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COUNT(*) FILTER (WHERE deleted_at IS NULL), COUNT(*) FILTER (WHERE deleted_at IS NOT NULL) FROM tasks",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((live, trashed), (0, 5));
+        let (series,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM recurring_series")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(series, 0);
+    }
+}
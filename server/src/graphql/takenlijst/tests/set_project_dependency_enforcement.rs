@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/set_project_dependency_enforcement resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::set_project_dependency_enforcement::SetProjectDependencyEnforcementMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_set_project_dependency_enforcement() {
+        let _ = SetProjectDependencyEnforcementMutation::default();
+        assert!(true);
+    }
+}
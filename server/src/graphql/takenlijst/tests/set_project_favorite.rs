@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/set_project_favorite resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::set_project_favorite::SetProjectFavoriteMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_set_project_favorite() {
+        let _ = SetProjectFavoriteMutation::default();
+        assert!(true);
+    }
+}
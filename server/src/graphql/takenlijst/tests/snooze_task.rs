@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES ('user1', 'testuser', 'password', 'Test')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO tasks (id, project_id, author_id, title, status, scheduled_date, deadline_date) VALUES \
+             ('taxes', 'p1', 'user1', 'File taxes', 'todo', '2025-03-01', '2025-03-04'), \
+             ('done', 'p1', 'user1', 'Done already', 'done', '2025-03-01', NULL), \
+             ('other', 'p1', 'user1', 'Never snoozed', 'todo', NULL, NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn updated_at(pool: &SqlitePool, id: &str) -> String {
+        sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn snoozing_moves_dates_and_counts() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let snooze = "mutation($id: String!, $at: String!, $preset: SnoozePreset!, $minutes: Int) { \
+                      snoozeTask(id: $id, lastKnownUpdatedAt: $at, preset: $preset, durationMinutes: $minutes) { \
+                      scheduledDate scheduledTimeMinutes deadlineDate snoozeCount lastSnoozedAt } }";
+
+        let stale = updated_at(&pool, "taxes").await;
+        // Keeps the snooze's updated_at apart from the insert's
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        let response = execute(
+            &schema,
+            snooze,
+            json!({ "id": "taxes", "at": stale, "preset": "TOMORROW_MORNING" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["snoozeTask"].clone();
+
+        // An all-day task stays all-day, and the deadline stays three days after it
+        let tomorrow = Utc::now().date_naive() + Duration::days(1);
+        assert_eq!(
+            task["scheduledDate"],
+            tomorrow.format("%Y-%m-%d").to_string()
+        );
+        assert_eq!(task["scheduledTimeMinutes"], json!(null));
+        assert_eq!(
+            task["deadlineDate"],
+            (tomorrow + Duration::days(3))
+                .format("%Y-%m-%d")
+                .to_string()
+        );
+        assert_eq!(task["snoozeCount"], 1);
+        assert!(task["lastSnoozedAt"].is_string());
+
+        // Snoozing from an outdated copy of the task is refused
+        let response = execute(
+            &schema,
+            snooze,
+            json!({ "id": "taxes", "at": stale, "preset": "NEXT_WEEK" }),
+        )
+        .await;
+        assert_eq!(
+            response.errors[0].message,
+            "Task has been modified by another user"
+        );
+
+        let response = execute(
+            &schema,
+            snooze,
+            json!({ "id": "taxes", "at": updated_at(&pool, "taxes").await, "preset": "CUSTOM" }),
+        )
+        .await;
+        assert_eq!(
+            response.errors[0].message,
+            "durationMinutes is required for CUSTOM"
+        );
+
+        let response = execute(
+            &schema,
+            snooze,
+            json!({ "id": "taxes", "at": updated_at(&pool, "taxes").await, "preset": "CUSTOM", "minutes": 90 }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["snoozeTask"].clone();
+        assert_eq!(task["snoozeCount"], 2);
+        assert!(task["scheduledTimeMinutes"].is_number());
+
+        let response = execute(
+            &schema,
+            snooze,
+            json!({ "id": "done", "at": updated_at(&pool, "done").await, "preset": "NEXT_WEEK" }),
+        )
+        .await;
+        assert_eq!(response.errors[0].message, "Only open tasks can be snoozed");
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", minSnoozeCount: 2) { totalCount items { id } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap()["tasks"],
+            json!({ "totalCount": 1, "items": [{ "id": "taxes" }] })
+        );
+    }
+}
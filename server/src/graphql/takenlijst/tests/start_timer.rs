@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES ('user1', 'testuser', 'password', 'Test')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for id in ["paint", "sand"] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, title, status) VALUES (?1, 'p1', 'user1', ?1, 'todo')",
+            )
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    #[tokio::test]
+    async fn starting_a_timer_stops_the_running_one() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let start = "mutation($task: String!) { startTimer(taskId: $task) { id taskId endedAt } }";
+
+        let response = execute(&schema, start, json!({ "task": "paint" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let first = response.data.into_json().unwrap()["startTimer"].clone();
+        assert_eq!(first["endedAt"], json!(null));
+
+        let response = execute(&schema, start, json!({ "task": "sand" })).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let second = response.data.into_json().unwrap()["startTimer"].clone();
+        assert_eq!(second["taskId"], "sand");
+
+        let running: Vec<(String,)> =
+            sqlx::query_as("SELECT task_id FROM time_entries WHERE ended_at IS NULL")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(running, vec![("sand".to_string(),)]);
+
+        let stop = "mutation { stopTimer { id endedAt } }";
+        let response = execute(&schema, stop, json!({})).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let stopped = response.data.into_json().unwrap()["stopTimer"].clone();
+        assert_eq!(stopped["id"], second["id"]);
+        assert!(stopped["endedAt"].is_string());
+
+        let response = execute(&schema, stop, json!({})).await;
+        assert_eq!(response.errors[0].message, "No timer is running");
+    }
+}
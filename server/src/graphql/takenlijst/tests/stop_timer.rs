@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/stop_timer resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::stop_timer::StopTimerMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_stop_timer() {
+        let _ = StopTimerMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn create_task(
+        schema: &crate::graphql::AppSchema,
+        title: &str,
+        parent: Option<&str>,
+    ) -> async_graphql::Response {
+        execute(
+            schema,
+            "mutation($input: CreateTaskInput!) { createTask(input: $input) { id parentTaskId updatedAt } }",
+            json!({ "input": { "projectId": "p1", "title": title, "parentTaskId": parent } }),
+        )
+        .await
+    }
+
+    async fn create_task_id(
+        schema: &crate::graphql::AppSchema,
+        title: &str,
+        parent: Option<&str>,
+    ) -> String {
+        let response = create_task(schema, title, parent).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        data["createTask"]["id"].as_str().unwrap().to_string()
+    }
+
+    async fn updated_at(pool: &SqlitePool, id: &str) -> String {
+        sqlx::query_as::<_, (String,)>("SELECT updated_at FROM tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn subtasks_are_nested_and_hidden_from_top_level_list() {
+        let schema = build(setup_test_db().await);
+        let parent = create_task_id(&schema, "Paint bedroom", None).await;
+        let child = create_task_id(&schema, "Buy paint", Some(&parent)).await;
+        create_task_id(&schema, "Pick colour", Some(&child)).await;
+
+        // A fourth level is rejected
+        let grandchild = create_task_id(&schema, "Get samples", Some(&child)).await;
+        let response = create_task(&schema, "Too deep", Some(&grandchild)).await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\") { totalCount items { title subtaskProgress { done total } subtasks { title subtasks { title } } } } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["tasks"]["totalCount"], 1);
+        let item = &data["tasks"]["items"][0];
+        assert_eq!(item["subtaskProgress"], json!({ "done": 0, "total": 1 }));
+        assert_eq!(item["subtasks"][0]["title"], "Buy paint");
+        assert_eq!(
+            item["subtasks"][0]["subtasks"],
+            json!([{ "title": "Pick colour" }, { "title": "Get samples" }])
+        );
+
+        let response = execute(
+            &schema,
+            "{ tasks(projectId: \"p1\", timezone: \"UTC\", includeSubtasks: true) { totalCount } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["tasks"]["totalCount"], 4);
+    }
+
+    #[tokio::test]
+    async fn complete_refuses_or_completes_open_subtasks() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let parent = create_task_id(&schema, "Paint bedroom", None).await;
+        let child = create_task_id(&schema, "Buy paint", Some(&parent)).await;
+        let grandchild = create_task_id(&schema, "Pick colour", Some(&child)).await;
+
+        let query = "mutation($id: String!, $at: String!, $mode: SubtaskCompletion) { completeTask(id: $id, lastKnownUpdatedAt: $at, subtasks: $mode) { status } }";
+
+        let response = execute(
+            &schema,
+            query,
+            json!({ "id": parent, "at": updated_at(&pool, &parent).await, "mode": null }),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(
+            &schema,
+            query,
+            json!({ "id": parent, "at": updated_at(&pool, &parent).await, "mode": "COMPLETE_ALL" }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        for id in [&child, &grandchild] {
+            let status: (String,) = sqlx::query_as("SELECT status FROM tasks WHERE id = ?1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(status.0, "done");
+        }
+    }
+
+    #[tokio::test]
+    async fn reparenting_rejects_cycles() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        let parent = create_task_id(&schema, "Paint bedroom", None).await;
+        let child = create_task_id(&schema, "Buy paint", Some(&parent)).await;
+
+        let query = "mutation($id: String!, $parent: String, $at: String!) { updateTask(id: $id, input: { parentTaskId: $parent }, lastKnownUpdatedAt: $at) { parentTaskId } }";
+
+        let response = execute(
+            &schema,
+            query,
+            json!({ "id": parent, "parent": child, "at": updated_at(&pool, &parent).await }),
+        )
+        .await;
+        assert!(!response.errors.is_empty());
+
+        // Promoting the subtask back to the top level is fine
+        let response = execute(
+            &schema,
+            query,
+            json!({ "id": child, "parent": null, "at": updated_at(&pool, &child).await }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        assert_eq!(data["updateTask"]["parentTaskId"], json!(null));
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        for (id, username) in [("user1", "testuser"), ("user2", "other")] {
+            sqlx::query(
+                "INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)",
+            )
+            .bind(id)
+            .bind(username)
+            .bind("password")
+            .bind(username)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> serde_json::Value {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn task_mutations_log_field_changes() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let data = execute(
+            &schema,
+            "testuser",
+            "mutation { createTask(input: { projectId: \"p1\", title: \"Paint fence\", deadlineDate: \"2025-03-01\" }) { id updatedAt } }",
+            json!({}),
+        )
+        .await;
+        let id = data["createTask"]["id"].as_str().unwrap().to_string();
+        let updated_at = data["createTask"]["updatedAt"].clone();
+
+        let data = execute(
+            &schema,
+            "other",
+            "mutation($id: String!, $at: String!) { \
+             updateTask(id: $id, lastKnownUpdatedAt: $at, input: { title: \"Paint fence\", deadlineDate: \"2025-03-08\", assigneeId: \"user2\" }) { \
+             updatedAt activity { totalCount items { action actorId changes { field old new } } } } }",
+            json!({ "id": id, "at": updated_at }),
+        )
+        .await;
+        let activity = &data["updateTask"]["activity"];
+        assert_eq!(activity["totalCount"], 2);
+        assert_eq!(
+            activity["items"][0],
+            json!({
+                "action": "UPDATED",
+                "actorId": "user2",
+                "changes": [
+                    { "field": "assigneeId", "old": null, "new": "user2" },
+                    { "field": "deadlineDate", "old": "2025-03-01", "new": "2025-03-08" },
+                ]
+            })
+        );
+        assert_eq!(activity["items"][1]["action"], "CREATED");
+
+        execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!, $at: String!) { completeTask(id: $id, lastKnownUpdatedAt: $at) { id } }",
+            json!({ "id": id, "at": data["updateTask"]["updatedAt"] }),
+        )
+        .await;
+
+        let feed = "query($actor: String) { projectActivity(projectId: \"p1\", actorId: $actor) { \
+                    totalCount items { action changes { field old new } } } }";
+        let data = execute(&schema, "testuser", feed, json!({})).await;
+        assert_eq!(data["projectActivity"]["totalCount"], 3);
+        // Completing also moves the task into the project's done status
+        let statuses = sqlx::query_as::<_, (String,)>(
+            "SELECT id FROM project_statuses WHERE project_id = 'p1' AND category != 'cancelled' ORDER BY position",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            data["projectActivity"]["items"][0],
+            json!({
+                "action": "COMPLETED",
+                "changes": [
+                    { "field": "status", "old": "todo", "new": "done" },
+                    { "field": "statusId", "old": statuses[0].0, "new": statuses[1].0 },
+                ]
+            })
+        );
+
+        let data = execute(&schema, "testuser", feed, json!({ "actor": "user2" })).await;
+        assert_eq!(data["projectActivity"]["totalCount"], 1);
+    }
+}
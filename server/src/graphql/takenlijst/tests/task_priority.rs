@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password, first_name) VALUES (?, ?, ?, ?)")
+            .bind("user1")
+            .bind("testuser")
+            .bind("password")
+            .bind("Test User")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: "testuser".to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    async fn titles(schema: &crate::graphql::AppSchema, args: &str) -> serde_json::Value {
+        let query = format!(
+            "{{ tasks(projectId: \"p1\", timezone: \"UTC\"{}) {{ totalCount items {{ title }} }} }}",
+            args
+        );
+        let response = execute(schema, &query, json!({})).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let titles: Vec<_> = data["tasks"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["title"].clone())
+            .collect();
+        json!(titles)
+    }
+
+    #[tokio::test]
+    async fn priority_filters_and_sorts_tasks() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        for (title, priority) in [
+            ("Apples", None),
+            ("Bins", Some("HIGH")),
+            ("Cat food", Some("URGENT")),
+            ("Drill", Some("LOW")),
+        ] {
+            let response = execute(
+                &schema,
+                "mutation($input: CreateTaskInput!) { createTask(input: $input) { priority } }",
+                json!({ "input": { "projectId": "p1", "title": title, "priority": priority } }),
+            )
+            .await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            let data = response.data.into_json().unwrap();
+            assert_eq!(
+                data["createTask"]["priority"],
+                json!(priority.unwrap_or("NONE"))
+            );
+        }
+
+        assert_eq!(
+            titles(&schema, "").await,
+            json!(["Apples", "Bins", "Cat food", "Drill"])
+        );
+        assert_eq!(
+            titles(&schema, ", sort: PRIORITY").await,
+            json!(["Cat food", "Bins", "Drill", "Apples"])
+        );
+        assert_eq!(
+            titles(&schema, ", priorities: [HIGH, URGENT]").await,
+            json!(["Bins", "Cat food"])
+        );
+
+        // Lowering a priority re-sorts the task
+        let (id, updated_at): (String, String) =
+            sqlx::query_as("SELECT id, updated_at FROM tasks WHERE title = 'Cat food'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let response = execute(
+            &schema,
+            "mutation($id: String!, $at: String!) { updateTask(id: $id, input: { priority: NONE }, lastKnownUpdatedAt: $at) { priority } }",
+            json!({ "id": id, "at": updated_at }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            titles(&schema, ", sort: PRIORITY").await,
+            json!(["Bins", "Drill", "Apples", "Cat food"])
+        );
+    }
+}
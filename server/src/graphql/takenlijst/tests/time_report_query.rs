@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/time_report_query resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::queries::TimeReportQuery;
+
+    #[tokio::test]
+    async fn compiles_and_links_time_report_query() {
+        let _ = TimeReportQuery::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/unwatch_task resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::unwatch_task::UnwatchTaskMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_unwatch_task() {
+        let _ = UnwatchTaskMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/update_custom_field resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::update_custom_field::UpdateCustomFieldMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_update_custom_field() {
+        let _ = UpdateCustomFieldMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/update_project_appearance resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::update_project_appearance::UpdateProjectAppearanceMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_update_project_appearance() {
+        let _ = UpdateProjectAppearanceMutation::default();
+        assert!(true);
+    }
+}
@@ -0,0 +1,297 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use crate::tasks::series;
+    use async_graphql::{Request, Variables};
+    use chrono::Utc;
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password) VALUES ('user1', 'testuser', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone) \
+             VALUES ('s1', 'p1', 'user1', 'Water plants', 'FREQ=DAILY', '2099-01-01', 480, 0, 'Europe/Amsterdam')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Far enough ahead that only the first few occurrences are materialized
+        series::materialize(&mut *pool.acquire().await.unwrap(), "s1", Utc::now())
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    /// (series id, occurrence date, title) of every task, by date
+    async fn occurrences(pool: &SqlitePool) -> Vec<(String, String, String)> {
+        sqlx::query_as::<_, (String, String, String)>(
+            "SELECT series_id, occurrence_date, title FROM tasks ORDER BY occurrence_date",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn task_on(pool: &SqlitePool, date: &str) -> (String, String) {
+        sqlx::query_as::<_, (String, String)>(
+            "SELECT id, updated_at FROM tasks WHERE occurrence_date = ?1",
+        )
+        .bind(date)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    const UPDATE: &str = "mutation($id: String!, $scope: SeriesEditScope!, $taskId: String, $input: UpdateSeriesInput!) { \
+                          updateRecurringSeries(id: $id, scope: $scope, taskId: $taskId, input: $input) { \
+                          id title rrule dtstartDate } }";
+
+    #[tokio::test]
+    async fn edits_apply_to_one_following_or_all_occurrences() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        assert_eq!(occurrences(&pool).await.len(), 5);
+
+        // A task edited on its own is an exception from then on
+        let (edited, at) = task_on(&pool, "2099-01-02").await;
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!, $at: String!) { updateTask(id: $id, input: { title: \"Water the cactus\" }, lastKnownUpdatedAt: $at) { id } }",
+            json!({ "id": edited, "at": at }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        // This occurrence only
+        let (third, _) = task_on(&pool, "2099-01-03").await;
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": "s1", "scope": "THIS", "taskId": third, "input": { "title": "Skip today" } }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(data["updateRecurringSeries"]["title"], "Water plants");
+
+        // A rule can't change for one occurrence
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": "s1", "scope": "THIS", "taskId": third, "input": { "rrule": "FREQ=WEEKLY" } }),
+        )
+        .await;
+        assert_eq!(res.errors.len(), 1);
+
+        // This and following splits the series, with its untouched tasks regenerated
+        let (fourth, _) = task_on(&pool, "2099-01-04").await;
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({
+                "id": "s1",
+                "scope": "THIS_AND_FOLLOWING",
+                "taskId": fourth,
+                "input": { "title": "Water plants well", "rrule": "FREQ=DAILY;INTERVAL=2" }
+            }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        let split = data["updateRecurringSeries"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_ne!(split, "s1");
+        assert_eq!(data["updateRecurringSeries"]["dtstartDate"], "2099-01-04");
+        let (old_rrule,) =
+            sqlx::query_as::<_, (String,)>("SELECT rrule FROM recurring_series WHERE id = 's1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(old_rrule, "FREQ=DAILY;UNTIL=20990103T225959Z");
+
+        // The whole series; edited occurrences keep their edits
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": "s1", "scope": "ALL", "input": { "title": "Water every plant" } }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        let s = |id: &str, date: &str, title: &str| {
+            (id.to_string(), date.to_string(), title.to_string())
+        };
+        assert_eq!(
+            occurrences(&pool).await,
+            vec![
+                s("s1", "2099-01-01", "Water every plant"),
+                s("s1", "2099-01-02", "Water the cactus"),
+                s("s1", "2099-01-03", "Skip today"),
+                s(&split, "2099-01-04", "Water plants well"),
+                s(&split, "2099-01-06", "Water plants well"),
+                s(&split, "2099-01-08", "Water plants well"),
+                s(&split, "2099-01-10", "Water plants well"),
+                s(&split, "2099-01-12", "Water plants well"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn splitting_a_counted_series_keeps_its_last_occurrence() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone) \
+             VALUES ('s2', 'p1', 'user1', 'Take pills', 'FREQ=DAILY;COUNT=5', '2099-02-01', 480, 0, 'Europe/Amsterdam')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        series::materialize(&mut *pool.acquire().await.unwrap(), "s2", Utc::now())
+            .await
+            .unwrap();
+
+        let (third, _) = task_on(&pool, "2099-02-03").await;
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({
+                "id": "s2",
+                "scope": "THIS_AND_FOLLOWING",
+                "taskId": third,
+                "input": { "title": "Take the pills" }
+            }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        // The copy ends where the five occurrences did rather than counting five again
+        assert_eq!(
+            data["updateRecurringSeries"]["rrule"],
+            "FREQ=DAILY;UNTIL=20990205T225959Z"
+        );
+        let split = data["updateRecurringSeries"]["id"].as_str().unwrap();
+        let dates: Vec<_> = occurrences(&pool)
+            .await
+            .into_iter()
+            .filter(|(series_id, _, _)| series_id == "s2" || series_id == split)
+            .map(|(_, date, title)| (date, title))
+            .collect();
+        assert_eq!(
+            dates,
+            vec![
+                ("2099-02-01".to_string(), "Take pills".to_string()),
+                ("2099-02-02".to_string(), "Take pills".to_string()),
+                ("2099-02-03".to_string(), "Take the pills".to_string()),
+                ("2099-02-04".to_string(), "Take the pills".to_string()),
+                ("2099-02-05".to_string(), "Take the pills".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn series_edits_keep_reordered_tasks_and_log_activity() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        sqlx::query("INSERT INTO users (id, username, password) VALUES ('user2', 'stranger', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (third, _) = task_on(&pool, "2099-01-03").await;
+        let (second, _) = task_on(&pool, "2099-01-02").await;
+        let res = execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!, $anchor: String!) { moveTaskBefore(id: $id, beforeId: $anchor) { id } }",
+            json!({ "id": third, "anchor": second }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        // Only project members can be assigned
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": "s1", "scope": "ALL", "input": { "assigneeId": "user2" } }),
+        )
+        .await;
+        assert_eq!(res.errors.len(), 1);
+
+        let res = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": "s1", "scope": "ALL", "input": { "title": "Water plants well" } }),
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+
+        // The reordered task is an exception and keeps its place
+        let titles: Vec<_> = occurrences(&pool)
+            .await
+            .into_iter()
+            .map(|(_, date, title)| (date, title))
+            .collect();
+        assert_eq!(
+            titles[2],
+            ("2099-01-03".to_string(), "Water plants".to_string())
+        );
+        assert_eq!(titles[3].1, "Water plants well");
+        let (kept,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM tasks WHERE id = ?1")
+            .bind(&third)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(kept, 1);
+
+        // Regenerated tasks show up in the activity feed
+        let (regenerated, _) = task_on(&pool, "2099-01-04").await;
+        let (created,) = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM task_activity WHERE task_id = ?1 AND action = 'created'",
+        )
+        .bind(&regenerated)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(created, 1);
+    }
+}
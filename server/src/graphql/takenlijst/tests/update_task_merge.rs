@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES \
+             ('user1', 'testuser', 'password', 'Test'), ('user2', 'other', 'password', 'Other')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> async_graphql::Response {
+        // Keep consecutive writes on distinct millisecond timestamps
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        schema.execute(request).await
+    }
+
+    const UPDATE: &str = "mutation($id: String!, $at: String!, $input: UpdateTaskInput!) { \
+                          updateTask(id: $id, lastKnownUpdatedAt: $at, input: $input) { \
+                          title deadlineDate assigneeId updatedAt } }";
+
+    #[tokio::test]
+    async fn stale_updates_merge_untouched_fields_and_report_conflicts() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation { createTask(input: { projectId: \"p1\", title: \"Paint fence\", deadlineDate: \"2025-03-01\" }) { id updatedAt } }",
+            json!({}),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let data = response.data.into_json().unwrap();
+        let id = data["createTask"]["id"].as_str().unwrap().to_string();
+        let original = data["createTask"]["updatedAt"].clone();
+
+        // Someone else reassigns the task and moves the deadline
+        let response = execute(
+            &schema,
+            "other",
+            UPDATE,
+            json!({ "id": id, "at": original, "input": { "assigneeId": "user2", "deadlineDate": "2025-03-08" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // An offline rename against the original version is merged in; the untouched
+        // deadline it sends along doesn't undo the other edit
+        let response = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": id, "at": original, "input": { "title": "Paint the fence", "deadlineDate": "2025-03-01" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["updateTask"].clone();
+        assert_eq!(task["title"], "Paint the fence");
+        assert_eq!(task["deadlineDate"], "2025-03-08");
+        assert_eq!(task["assigneeId"], "user2");
+        let latest = task["updatedAt"].clone();
+
+        // Moving the deadline elsewhere from the original version is a true conflict
+        let response = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": id, "at": original, "input": { "title": "Paint fence white", "deadlineDate": "2025-03-02" } }),
+        )
+        .await;
+        assert_eq!(response.errors.len(), 1);
+        let extensions = response.errors[0].extensions.as_ref().unwrap();
+        let value = |key: &str| extensions.get(key).unwrap().clone().into_json().unwrap();
+        assert_eq!(value("code"), "CONFLICT_STALE_WRITE");
+        assert_eq!(value("currentUpdatedAt"), latest);
+        assert_eq!(
+            value("conflicts"),
+            json!([
+                { "field": "deadlineDate", "base": "2025-03-01", "server": "2025-03-08", "client": "2025-03-02" },
+                { "field": "title", "base": "Paint fence", "server": "Paint the fence", "client": "Paint fence white" }
+            ])
+        );
+        assert_eq!(value("current")["assigneeId"], "user2");
+
+        // Nothing of the conflicting write was applied
+        let (title,) = sqlx::query_as::<_, (String,)>("SELECT title FROM tasks WHERE id = ?1")
+            .bind(&id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(title, "Paint the fence");
+    }
+
+    #[tokio::test]
+    async fn reordered_tasks_merge_against_the_reordered_version() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let mut ids = Vec::new();
+        for title in ["Paint fence", "Mow lawn"] {
+            let response = execute(
+                &schema,
+                "testuser",
+                "mutation($title: String!) { createTask(input: { projectId: \"p1\", title: $title }) { id } }",
+                json!({ "title": title }),
+            )
+            .await;
+            assert!(response.errors.is_empty(), "{:?}", response.errors);
+            ids.push(response.data.into_json().unwrap()["createTask"]["id"].clone());
+        }
+
+        let response = execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!, $anchor: String!) { moveTaskBefore(id: $id, beforeId: $anchor) { updatedAt } }",
+            json!({ "id": ids[1], "anchor": ids[0] }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let reordered = response.data.into_json().unwrap()["moveTaskBefore"]["updatedAt"].clone();
+
+        let response = execute(
+            &schema,
+            "other",
+            UPDATE,
+            json!({ "id": ids[1], "at": reordered, "input": { "assigneeId": "user2" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        // The version the reorder produced is the base for the merge
+        let response = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": ids[1], "at": reordered, "input": { "title": "Mow the lawn" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["updateTask"].clone();
+        assert_eq!(task["title"], "Mow the lawn");
+        assert_eq!(task["assigneeId"], "user2");
+    }
+
+    #[tokio::test]
+    async fn generated_tasks_merge_against_the_version_they_were_created_with() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, deadline_offset_minutes) \
+             VALUES ('s1', 'p1', 'user1', 'Water plants', 'FREQ=DAILY', '2099-01-01', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        crate::tasks::series::materialize(
+            &mut *pool.acquire().await.unwrap(),
+            "s1",
+            chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+        let (id, created) = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, updated_at FROM tasks WHERE occurrence_date = '2099-01-01'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let response = execute(
+            &schema,
+            "other",
+            UPDATE,
+            json!({ "id": id, "at": created, "input": { "assigneeId": "user2" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let response = execute(
+            &schema,
+            "testuser",
+            UPDATE,
+            json!({ "id": id, "at": created, "input": { "title": "Water the plants" } }),
+        )
+        .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        let task = response.data.into_json().unwrap()["updateTask"].clone();
+        assert_eq!(task["title"], "Water the plants");
+        assert_eq!(task["assigneeId"], "user2");
+    }
+}
@@ -0,0 +1,12 @@
+// Placeholder unit test for takenlijst/update_time_entry resolver
+
+#[cfg(test)]
+mod tests {
+    use crate::graphql::takenlijst::mutations::update_time_entry::UpdateTimeEntryMutation;
+
+    #[tokio::test]
+    async fn compiles_and_links_update_time_entry() {
+        let _ = UpdateTimeEntryMutation::default();
+        assert!(true);
+    }
+}
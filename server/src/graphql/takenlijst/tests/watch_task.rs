@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::auth::Claims;
+    use crate::graphql::build;
+    use async_graphql::{Request, Variables};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::sync::Arc;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO users (id, username, password, first_name) VALUES \
+             ('user1', 'testuser', 'password', 'Test'), \
+             ('user2', 'rob', 'password', 'Rob'), \
+             ('user3', 'sam', 'password', 'Sam')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1'), ('p2', 'Garden', 'user2')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO project_members (project_id, user_id) VALUES ('p1', 'user2'), ('p1', 'user3'), ('p2', 'user1')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn execute(
+        schema: &crate::graphql::AppSchema,
+        username: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> serde_json::Value {
+        let request = Request::new(query)
+            .variables(Variables::from_json(variables))
+            .data(Arc::new(Claims {
+                sub: username.to_string(),
+                exp: 9999999999,
+            }));
+        let response = schema.execute(request).await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    fn usernames(watchers: &serde_json::Value) -> Vec<String> {
+        watchers
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["username"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn authors_and_assignees_watch_and_others_opt_in() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        let data = execute(
+            &schema,
+            "testuser",
+            "mutation { createTask(input: { projectId: \"p1\", title: \"Fix gutter\", assigneeId: \"user2\" }) { id updatedAt watchers { username } } }",
+            json!({}),
+        )
+        .await;
+        let id = data["createTask"]["id"].as_str().unwrap().to_string();
+        let mut watchers = usernames(&data["createTask"]["watchers"]);
+        watchers.sort();
+        assert_eq!(watchers, ["rob", "testuser"]);
+
+        // Reassigning adds the new assignee; the previous one keeps watching
+        let data = execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!, $at: String!) { updateTask(id: $id, lastKnownUpdatedAt: $at, input: { assigneeId: \"user3\" }) { watchers { username } } }",
+            json!({ "id": id, "at": data["createTask"]["updatedAt"] }),
+        )
+        .await;
+        assert_eq!(usernames(&data["updateTask"]["watchers"]).len(), 3);
+
+        // Watchers can leave and come back; watching twice is harmless
+        let data = execute(
+            &schema,
+            "rob",
+            "mutation($id: String!) { unwatchTask(id: $id) { watchers { username } } }",
+            json!({ "id": id }),
+        )
+        .await;
+        assert!(!usernames(&data["unwatchTask"]["watchers"]).contains(&"rob".to_string()));
+        for _ in 0..2 {
+            let data = execute(
+                &schema,
+                "rob",
+                "mutation($id: String!) { watchTask(id: $id) { watchers { username } } }",
+                json!({ "id": id }),
+            )
+            .await;
+            assert_eq!(usernames(&data["watchTask"]["watchers"]).len(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn watched_tasks_spans_projects() {
+        let pool = setup_test_db().await;
+        let schema = build(pool.clone());
+
+        // Rob creates a task in each project, testuser opts in to the one in p2
+        let mut ids = Vec::new();
+        for project_id in ["p1", "p2"] {
+            let data = execute(
+                &schema,
+                "rob",
+                "mutation($projectId: String!) { createTask(input: { projectId: $projectId, title: \"Weed beds\" }) { id } }",
+                json!({ "projectId": project_id }),
+            )
+            .await;
+            ids.push(data["createTask"]["id"].as_str().unwrap().to_string());
+        }
+        execute(
+            &schema,
+            "testuser",
+            "mutation($id: String!) { watchTask(id: $id) { id } }",
+            json!({ "id": ids[1] }),
+        )
+        .await;
+        sqlx::query("UPDATE tasks SET status = 'done', completed_at = CURRENT_TIMESTAMP, completed_by = 'user2' WHERE id = ?1")
+            .bind(&ids[0])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let query = "query($closed: Boolean!) { watchedTasks(includeClosed: $closed) { totalCount items { id projectId } } }";
+        let data = execute(&schema, "rob", query, json!({ "closed": false })).await;
+        assert_eq!(data["watchedTasks"]["totalCount"], 1);
+        assert_eq!(data["watchedTasks"]["items"][0]["projectId"], "p2");
+        let data = execute(&schema, "rob", query, json!({ "closed": true })).await;
+        assert_eq!(data["watchedTasks"]["totalCount"], 2);
+        let data = execute(&schema, "testuser", query, json!({ "closed": true })).await;
+        assert_eq!(data["watchedTasks"]["items"][0]["id"], ids[1].as_str());
+        assert_eq!(data["watchedTasks"]["totalCount"], 1);
+
+        // Someone outside the project can't watch its tasks
+        let request = Request::new("mutation($id: String!) { watchTask(id: $id) { id } }")
+            .variables(Variables::from_json(json!({ "id": ids[1] })))
+            .data(Arc::new(Claims {
+                sub: "sam".to_string(),
+                exp: 9999999999,
+            }));
+        assert_eq!(schema.execute(request).await.errors.len(), 1);
+    }
+}
@@ -0,0 +1,51 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+
+use crate::graphql::takenlijst::types::Task;
+
+/// Change applied by `bulkUpdateTasks`
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BulkTaskActionKind {
+    Complete,
+    Abandon,
+    Restore,
+    /// Set (or with no `scheduledDate`, clear) the scheduled date and time
+    Reschedule,
+    /// Set (or with no `assigneeId`, clear) the assignee
+    Reassign,
+    AddTags,
+    RemoveTags,
+    MoveToProject,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct BulkTaskActionInput {
+    pub kind: BulkTaskActionKind,
+    #[graphql(name = "scheduledDate")]
+    pub scheduled_date: Option<String>,
+    #[graphql(name = "scheduledTimeMinutes")]
+    pub scheduled_time_minutes: Option<i32>,
+    #[graphql(name = "assigneeId")]
+    pub assignee_id: Option<String>,
+    #[graphql(name = "tagIds")]
+    pub tag_ids: Option<Vec<String>>,
+    #[graphql(name = "projectId")]
+    pub project_id: Option<String>,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct BulkTaskItemInput {
+    pub id: String,
+    #[graphql(name = "lastKnownUpdatedAt")]
+    pub last_known_updated_at: String,
+}
+
+/// Outcome for one task of a bulk update. Failed items carry the error code and message
+/// the single-task mutation would have returned.
+#[derive(SimpleObject)]
+pub struct BulkTaskResult {
+    pub id: String,
+    pub ok: bool,
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub task: Option<Task>,
+}
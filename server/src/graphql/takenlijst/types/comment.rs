@@ -0,0 +1,92 @@
+use async_graphql::{ComplexObject, Context, SimpleObject};
+use sqlx::SqlitePool;
+
+pub const COMMENT_COLUMNS: &str = "id, task_id, author_id, body, edited_at, created_at, updated_at";
+
+pub type CommentRow = (
+    String,
+    String,
+    String,
+    String,
+    Option<String>,
+    String,
+    String,
+);
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Comment {
+    pub id: String,
+    #[graphql(name = "taskId")]
+    pub task_id: String,
+    #[graphql(name = "authorId")]
+    pub author_id: String,
+    pub body: String,
+    #[graphql(name = "editedAt")]
+    pub edited_at: Option<String>,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+#[derive(SimpleObject)]
+pub struct CommentMention {
+    #[graphql(name = "userId")]
+    pub user_id: String,
+    pub username: String,
+}
+
+#[derive(SimpleObject)]
+pub struct PagedComments {
+    pub items: Vec<Comment>,
+    #[graphql(name = "totalCount")]
+    pub total_count: i32,
+}
+
+impl From<CommentRow> for Comment {
+    fn from(row: CommentRow) -> Self {
+        Comment {
+            id: row.0,
+            task_id: row.1,
+            author_id: row.2,
+            body: row.3,
+            edited_at: row.4,
+            created_at: row.5,
+            updated_at: row.6,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Comment {
+    /// Project members mentioned in the body
+    async fn mentions(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<CommentMention>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT u.id, u.username FROM task_comment_mentions m \
+             INNER JOIN users u ON u.id = m.user_id \
+             WHERE m.comment_id = ?1 ORDER BY u.username",
+        )
+        .bind(&self.id)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(user_id, username)| CommentMention { user_id, username })
+            .collect())
+    }
+}
+
+/// Fetch a single comment by id
+pub async fn fetch_comment(pool: &SqlitePool, comment_id: &str) -> Result<Comment, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM task_comments WHERE id = ?1",
+        COMMENT_COLUMNS
+    );
+    let row = sqlx::query_as::<_, CommentRow>(&sql)
+        .bind(comment_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(row.into())
+}
@@ -1,5 +1,9 @@
 use async_graphql::InputObject;
 
+use super::ReminderInput;
+use crate::tasks::TaskPriority;
+use crate::tasks::series::SeriesMode;
+
 #[derive(InputObject)]
 pub struct CreateSeriesInput {
     #[graphql(name = "projectId")]
@@ -10,7 +14,24 @@ pub struct CreateSeriesInput {
     pub assignee_id: Option<String>,
     #[graphql(name = "defaultTagIds")]
     pub default_tag_ids: Option<Vec<String>>,
-    pub rrule: String,
+    /// Priority given to every generated task
+    pub priority: Option<TaskPriority>,
+    /// Estimate given to every generated task
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: Option<i32>,
+    /// Relative reminders given to every generated task
+    #[graphql(name = "defaultReminders")]
+    pub default_reminders: Option<Vec<ReminderInput>>,
+    /// Defaults to CALENDAR
+    pub mode: Option<SeriesMode>,
+    /// Required for CALENDAR series
+    pub rrule: Option<String>,
+    /// Days from one occurrence being done to the next; required for AFTER_COMPLETION series
+    #[graphql(name = "intervalDays")]
+    pub interval_days: Option<i32>,
+    /// Whether abandoning an AFTER_COMPLETION occurrence also schedules the next one
+    #[graphql(name = "advanceOnAbandon")]
+    pub advance_on_abandon: Option<bool>,
     #[graphql(name = "dtstartDate")]
     pub dtstart_date: String,
     #[graphql(name = "dtstartTimeMinutes")]
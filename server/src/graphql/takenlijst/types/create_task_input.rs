@@ -1,5 +1,8 @@
 use async_graphql::InputObject;
 
+use super::CustomFieldValueInput;
+use crate::tasks::TaskPriority;
+
 #[derive(InputObject, Clone, Debug)]
 pub struct CreateTaskInput {
     #[graphql(name = "projectId")]
@@ -8,6 +11,13 @@ pub struct CreateTaskInput {
     pub description: Option<String>,
     #[graphql(name = "assigneeId")]
     pub assignee_id: Option<String>,
+    #[graphql(name = "sectionId")]
+    pub section_id: Option<String>,
+    #[graphql(name = "parentTaskId")]
+    pub parent_task_id: Option<String>,
+    pub priority: Option<TaskPriority>,
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: Option<i32>,
     #[graphql(name = "scheduledDate")]
     pub scheduled_date: Option<String>,
     #[graphql(name = "scheduledTimeMinutes")]
@@ -18,4 +28,7 @@ pub struct CreateTaskInput {
     pub deadline_time_minutes: Option<i32>,
     #[graphql(name = "tagIds")]
     pub tag_ids: Option<Vec<String>>,
+    // Values for the project's custom fields; fields left out are unchanged
+    #[graphql(name = "customFields")]
+    pub custom_fields: Option<Vec<CustomFieldValueInput>>,
 }
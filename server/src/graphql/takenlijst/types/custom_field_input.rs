@@ -0,0 +1,59 @@
+use async_graphql::{InputObject, MaybeUndefined};
+
+use crate::tasks::custom_fields::{CustomFieldKind, CustomFieldOperator};
+
+#[derive(InputObject)]
+pub struct CustomFieldInput {
+    pub name: String,
+    pub kind: CustomFieldKind,
+    /// Choices, for single-select fields
+    pub options: Option<Vec<String>>,
+    /// Bounds, for number fields
+    #[graphql(name = "minValue")]
+    pub min_value: Option<f64>,
+    #[graphql(name = "maxValue")]
+    pub max_value: Option<f64>,
+    /// For text fields
+    #[graphql(name = "maxLength")]
+    pub max_length: Option<i32>,
+}
+
+/// A field's kind can't be changed. Values set before a rule was tightened are kept.
+#[derive(InputObject)]
+pub struct UpdateCustomFieldInput {
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+    // null removes the bound
+    #[graphql(name = "minValue")]
+    pub min_value: MaybeUndefined<f64>,
+    #[graphql(name = "maxValue")]
+    pub max_value: MaybeUndefined<f64>,
+    #[graphql(name = "maxLength")]
+    pub max_length: MaybeUndefined<i32>,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct CustomFieldValueInput {
+    #[graphql(name = "fieldId")]
+    pub field_id: String,
+    /// Numbers in decimal, dates as YYYY-MM-DD, checkboxes as true or false; null clears
+    /// the value
+    pub value: Option<String>,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct CustomFieldFilterInput {
+    #[graphql(name = "fieldId")]
+    pub field_id: String,
+    pub op: CustomFieldOperator,
+    /// Not needed for isSet and isNotSet
+    pub value: Option<String>,
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct CustomFieldSortInput {
+    #[graphql(name = "fieldId")]
+    pub field_id: String,
+    #[graphql(default)]
+    pub descending: bool,
+}
@@ -1,12 +1,27 @@
+pub mod comment;
+pub use comment::{Comment, PagedComments};
+
 pub mod project;
 pub use project::Project;
 
+pub mod project_template;
+pub use project_template::ProjectTemplate;
+
 pub mod tag;
 pub use tag::Tag;
 
 pub mod task;
 pub use task::Task;
 
+pub mod section;
+pub use section::Section;
+
+pub mod task_group;
+pub use task_group::TaskGroup;
+
+pub mod status_column;
+pub use status_column::StatusColumn;
+
 pub mod paged_tasks;
 pub use paged_tasks::PagedTasks;
 
@@ -20,7 +35,7 @@ pub mod saved_view;
 pub use saved_view::SavedView;
 
 pub mod recurring_series;
-pub use recurring_series::RecurringSeries;
+pub use recurring_series::{RecurringSeries, fetch_series};
 
 pub mod create_series_input;
 pub use create_series_input::CreateSeriesInput;
@@ -30,3 +45,29 @@ pub use create_task_input::CreateTaskInput;
 
 pub mod update_task_input;
 pub use update_task_input::UpdateTaskInput;
+
+pub mod update_series_input;
+pub use update_series_input::UpdateSeriesInput;
+
+pub mod bulk_task_update;
+pub use bulk_task_update::{
+    BulkTaskActionInput, BulkTaskActionKind, BulkTaskItemInput, BulkTaskResult,
+};
+
+pub mod task_activity;
+pub use task_activity::PagedActivity;
+
+pub mod time_report;
+pub use time_report::TimeReport;
+
+pub mod reminder_input;
+pub use reminder_input::ReminderInput;
+
+pub mod quick_add;
+pub use quick_add::{QuickAddPreview, QuickAddResult};
+
+pub mod custom_field_input;
+pub use custom_field_input::{
+    CustomFieldFilterInput, CustomFieldInput, CustomFieldSortInput, CustomFieldValueInput,
+    UpdateCustomFieldInput,
+};
@@ -1,10 +1,12 @@
 use async_graphql::SimpleObject;
 
-use super::Task;
+use super::{Task, TaskGroup};
 
 #[derive(SimpleObject)]
 pub struct PagedTasks {
     pub items: Vec<Task>,
     #[graphql(name = "totalCount")]
     pub total_count: i32,
+    // Only populated when grouping by section was requested
+    pub groups: Option<Vec<TaskGroup>>,
 }
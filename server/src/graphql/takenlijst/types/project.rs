@@ -1,4 +1,11 @@
 use async_graphql::SimpleObject;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Columns selected for a `Project`. Queries alias `projects` as `p` and LEFT JOIN the
+/// requesting user's `project_user_preferences` row as `pup`.
+pub const PROJECT_COLUMNS: &str = "p.id, p.name, p.owner_id, p.archived_at, p.created_at, p.updated_at, \
+     p.color, p.icon, p.enforce_dependencies, pup.position, COALESCE(pup.is_favorite, 0) AS is_favorite";
 
 #[derive(SimpleObject)]
 pub struct Project {
@@ -12,4 +19,52 @@ pub struct Project {
     pub created_at: String,
     #[graphql(name = "updatedAt")]
     pub updated_at: String,
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    // Settings
+    #[graphql(name = "enforceDependencies")]
+    pub enforce_dependencies: bool,
+    // Per-user arrangement
+    pub position: Option<i32>,
+    #[graphql(name = "isFavorite")]
+    pub is_favorite: bool,
+}
+
+impl Project {
+    /// Build a `Project` from a row selected with `PROJECT_COLUMNS`
+    pub fn from_row(row: &SqliteRow) -> Self {
+        Project {
+            id: row.get("id"),
+            name: row.get("name"),
+            owner_id: row.get("owner_id"),
+            archived_at: row.get("archived_at"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            color: row.get("color"),
+            icon: row.get("icon"),
+            enforce_dependencies: row.get("enforce_dependencies"),
+            position: row.get("position"),
+            is_favorite: row.get("is_favorite"),
+        }
+    }
+}
+
+/// Fetch a single project as seen by `user_id`
+pub async fn fetch_project(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+    user_id: &str,
+) -> Result<Project, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM projects p \
+         LEFT JOIN project_user_preferences pup ON pup.project_id = p.id AND pup.user_id = ?2 \
+         WHERE p.id = ?1",
+        PROJECT_COLUMNS
+    );
+    let row = sqlx::query(&sql)
+        .bind(project_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+    Ok(Project::from_row(&row))
 }
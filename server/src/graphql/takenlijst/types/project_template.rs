@@ -0,0 +1,53 @@
+use async_graphql::SimpleObject;
+
+use crate::tasks::template::ProjectSnapshot;
+
+#[derive(SimpleObject)]
+pub struct ProjectTemplate {
+    pub id: String,
+    pub name: String,
+    #[graphql(name = "ownerId")]
+    pub owner_id: String,
+    #[graphql(name = "sourceProjectId")]
+    pub source_project_id: Option<String>,
+    #[graphql(name = "anchorDate")]
+    pub anchor_date: Option<String>,
+    #[graphql(name = "taskCount")]
+    pub task_count: i32,
+    #[graphql(name = "savedViewCount")]
+    pub saved_view_count: i32,
+    #[graphql(name = "seriesCount")]
+    pub series_count: i32,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl ProjectTemplate {
+    pub fn from_row(
+        row: (
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+            String,
+            String,
+        ),
+    ) -> Result<Self, serde_json::Error> {
+        let snapshot: ProjectSnapshot = serde_json::from_str(&row.4)?;
+        Ok(ProjectTemplate {
+            id: row.0,
+            name: row.1,
+            owner_id: row.2,
+            source_project_id: row.3,
+            anchor_date: snapshot.anchor_date,
+            task_count: snapshot.tasks.len() as i32,
+            saved_view_count: snapshot.saved_views.len() as i32,
+            series_count: snapshot.series.len() as i32,
+            created_at: row.5,
+            updated_at: row.6,
+        })
+    }
+}
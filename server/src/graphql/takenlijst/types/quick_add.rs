@@ -0,0 +1,84 @@
+use async_graphql::SimpleObject;
+use chrono::NaiveDateTime;
+use sqlx::SqlitePool;
+
+use super::{RecurringSeries, Task};
+use crate::tasks::TaskPriority;
+use crate::tasks::quick_add::{self, find_member, find_tags};
+
+/// What a quick-add line would create
+#[derive(SimpleObject, Clone, Debug)]
+pub struct QuickAddPreview {
+    pub title: String,
+    /// For a series, the date of its first occurrence
+    #[graphql(name = "scheduledDate")]
+    pub scheduled_date: Option<String>,
+    #[graphql(name = "scheduledTimeMinutes")]
+    pub scheduled_time_minutes: Option<i32>,
+    pub priority: Option<TaskPriority>,
+    pub tags: Vec<QuickAddTag>,
+    /// Username as typed, without the `@`
+    #[graphql(name = "assigneeUsername")]
+    pub assignee_username: Option<String>,
+    /// Null when no project member has that username
+    #[graphql(name = "assigneeId")]
+    pub assignee_id: Option<String>,
+    /// Set when the line creates a recurring series rather than a single task
+    pub rrule: Option<String>,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct QuickAddTag {
+    pub name: String,
+    /// Null for a tag that will be created
+    pub id: Option<String>,
+}
+
+/// The task or, for a line with a recurrence, the series a quick-add created
+#[derive(SimpleObject)]
+pub struct QuickAddResult {
+    pub task: Option<Task>,
+    pub series: Option<RecurringSeries>,
+}
+
+/// Parse a line and look up its tags and assignee, against the local time `now`
+pub async fn preview(
+    pool: &SqlitePool,
+    project_id: &str,
+    text: &str,
+    now: NaiveDateTime,
+) -> Result<QuickAddPreview, sqlx::Error> {
+    let parsed = quick_add::parse(text, now.date());
+
+    let scheduled_date = match &parsed.recurrence {
+        Some(recurrence) => Some(quick_add::series_start(
+            recurrence,
+            parsed.date,
+            parsed.time_minutes,
+            now,
+        )),
+        None => parsed.date,
+    };
+    let assignee_id = match &parsed.assignee {
+        Some(username) => find_member(pool, project_id, username).await?,
+        None => None,
+    };
+    let tags = parsed
+        .tags
+        .iter()
+        .cloned()
+        .zip(find_tags(pool, &parsed.tags).await?)
+        .map(|(name, id)| QuickAddTag { name, id })
+        .collect();
+
+    Ok(QuickAddPreview {
+        title: parsed.title,
+        scheduled_date: scheduled_date.map(|d| d.format("%Y-%m-%d").to_string()),
+        scheduled_time_minutes: parsed.time_minutes,
+        priority: parsed.priority,
+        tags,
+        assignee_username: parsed.assignee,
+        assignee_id,
+        rrule: parsed.recurrence.map(|r| r.rrule),
+    })
+}
@@ -1,4 +1,9 @@
 use async_graphql::SimpleObject;
+use sqlx::{Row, SqlitePool};
+
+use crate::tasks::TaskPriority;
+use crate::tasks::reminders::{Reminder, ReminderKind};
+use crate::tasks::series::{self, SeriesMode};
 
 #[derive(SimpleObject)]
 pub struct RecurringSeries {
@@ -11,17 +16,106 @@ pub struct RecurringSeries {
     pub description: Option<String>,
     #[graphql(name = "assigneeId")]
     pub assignee_id: Option<String>,
+    pub priority: TaskPriority,
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: Option<i32>,
+    pub mode: SeriesMode,
     pub rrule: String,
+    /// Days from one occurrence being done to the next, for AFTER_COMPLETION series
+    #[graphql(name = "intervalDays")]
+    pub interval_days: Option<i32>,
+    #[graphql(name = "advanceOnAbandon")]
+    pub advance_on_abandon: bool,
     #[graphql(name = "dtstartDate")]
     pub dtstart_date: String,
     #[graphql(name = "dtstartTimeMinutes")]
     pub dtstart_time_minutes: Option<i32>,
     #[graphql(name = "deadlineOffsetMinutes")]
     pub deadline_offset_minutes: i32,
+    /// Timezone occurrences are computed in
+    pub timezone: String,
+    /// Set while the series is paused and generates no tasks
+    #[graphql(name = "pausedAt")]
+    pub paused_at: Option<String>,
+    /// Last date the series has occurrences on, once it has been ended
+    #[graphql(name = "untilDate")]
+    pub until_date: Option<String>,
     #[graphql(name = "createdAt")]
     pub created_at: String,
     #[graphql(name = "updatedAt")]
     pub updated_at: String,
     #[graphql(name = "defaultTagIds")]
     pub default_tag_ids: Vec<String>,
+    #[graphql(name = "defaultReminders")]
+    pub default_reminders: Vec<Reminder>,
+}
+
+/// Fetch a series with its default tags and reminders
+pub async fn fetch_series(
+    pool: &SqlitePool,
+    series_id: &str,
+) -> Result<RecurringSeries, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT id, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, mode, rrule, \
+         interval_days, advance_on_abandon, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, \
+         paused_at, created_at, updated_at \
+         FROM recurring_series WHERE id = ?1",
+    )
+    .bind(series_id)
+    .fetch_one(pool)
+    .await?;
+    let default_tag_ids = sqlx::query_as::<_, (String,)>(
+        "SELECT tag_id FROM recurring_series_tags WHERE series_id = ?1 ORDER BY tag_id",
+    )
+    .bind(series_id)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(id,)| id)
+    .collect();
+    let default_reminders = sqlx::query(
+        "SELECT id, kind, offset_minutes, timezone, created_by FROM series_reminders \
+         WHERE series_id = ?1 ORDER BY created_at ASC, id ASC",
+    )
+    .bind(series_id)
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(|r| Reminder {
+        id: r.get("id"),
+        kind: ReminderKind::from_db(r.get("kind")),
+        remind_at: None,
+        offset_minutes: r.get("offset_minutes"),
+        timezone: r.get("timezone"),
+        created_by: r.get("created_by"),
+        fire_at: None,
+    })
+    .collect();
+
+    let rrule: String = row.get("rrule");
+    let timezone: String = row.get("timezone");
+    Ok(RecurringSeries {
+        id: row.get("id"),
+        project_id: row.get("project_id"),
+        created_by: row.get("created_by"),
+        title: row.get("title"),
+        description: row.get("description"),
+        assignee_id: row.get("assignee_id"),
+        priority: TaskPriority::from_db(row.get("priority")),
+        estimate_minutes: row.get("estimate_minutes"),
+        mode: SeriesMode::from_db(row.get::<String, _>("mode").as_str()),
+        until_date: series::until_date(&rrule, &timezone).map(|d| d.format("%Y-%m-%d").to_string()),
+        rrule,
+        interval_days: row.get("interval_days"),
+        advance_on_abandon: row.get("advance_on_abandon"),
+        dtstart_date: row.get("dtstart_date"),
+        dtstart_time_minutes: row.get("dtstart_time_minutes"),
+        deadline_offset_minutes: row.get("deadline_offset_minutes"),
+        timezone,
+        paused_at: row.get("paused_at"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        default_tag_ids,
+        default_reminders,
+    })
 }
@@ -0,0 +1,14 @@
+use async_graphql::InputObject;
+
+use crate::tasks::reminders::ReminderKind;
+
+#[derive(InputObject, Clone)]
+pub struct ReminderInput {
+    pub kind: ReminderKind,
+    /// RFC 3339 timestamp, for absolute reminders
+    #[graphql(name = "remindAt")]
+    pub remind_at: Option<String>,
+    /// Minutes before the scheduled time or deadline, for relative reminders
+    #[graphql(name = "offsetMinutes")]
+    pub offset_minutes: Option<i32>,
+}
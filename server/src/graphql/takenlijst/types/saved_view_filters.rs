@@ -1,7 +1,9 @@
 use async_graphql::SimpleObject;
 use serde::{Deserialize, Serialize};
 
-use crate::tasks::TaskStatus;
+use super::CustomFieldFilterInput;
+use crate::tasks::custom_fields::CustomFieldOperator;
+use crate::tasks::{TaskPriority, TaskSort, TaskStatus};
 
 #[derive(SimpleObject, Serialize, Deserialize)]
 pub struct SavedViewFilters {
@@ -16,4 +18,38 @@ pub struct SavedViewFilters {
     #[graphql(name = "tagIds")]
     #[serde(rename = "tagIds")]
     pub tag_ids: Vec<String>,
+    // Added after the first saved views were stored, so older JSON omits them
+    #[graphql(name = "sectionIds")]
+    #[serde(rename = "sectionIds", default)]
+    pub section_ids: Vec<String>,
+    #[graphql(name = "includeUnsectioned")]
+    #[serde(rename = "includeUnsectioned", default)]
+    pub include_unsectioned: bool,
+    #[serde(default)]
+    pub priorities: Vec<TaskPriority>,
+    #[serde(default)]
+    pub sort: TaskSort,
+    #[graphql(name = "customFields")]
+    #[serde(rename = "customFields", default)]
+    pub custom_fields: Vec<CustomFieldFilter>,
+}
+
+/// A predicate on one of the project's custom fields
+#[derive(SimpleObject, Serialize, Deserialize, Clone)]
+pub struct CustomFieldFilter {
+    #[graphql(name = "fieldId")]
+    #[serde(rename = "fieldId")]
+    pub field_id: String,
+    pub op: CustomFieldOperator,
+    pub value: Option<String>,
+}
+
+impl From<CustomFieldFilterInput> for CustomFieldFilter {
+    fn from(input: CustomFieldFilterInput) -> Self {
+        CustomFieldFilter {
+            field_id: input.field_id,
+            op: input.op,
+            value: input.value,
+        }
+    }
 }
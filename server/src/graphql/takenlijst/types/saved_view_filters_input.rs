@@ -1,6 +1,7 @@
 use async_graphql::InputObject;
 
-use crate::tasks::TaskStatus;
+use super::CustomFieldFilterInput;
+use crate::tasks::{TaskPriority, TaskSort, TaskStatus};
 
 #[derive(InputObject)]
 pub struct SavedViewFiltersInput {
@@ -12,4 +13,14 @@ pub struct SavedViewFiltersInput {
     pub assigned_to_me: bool,
     #[graphql(name = "tagIds")]
     pub tag_ids: Vec<String>,
+    #[graphql(name = "sectionIds", default)]
+    pub section_ids: Vec<String>,
+    #[graphql(name = "includeUnsectioned", default)]
+    pub include_unsectioned: bool,
+    #[graphql(default)]
+    pub priorities: Vec<TaskPriority>,
+    #[graphql(default)]
+    pub sort: TaskSort,
+    #[graphql(name = "customFields", default)]
+    pub custom_fields: Vec<CustomFieldFilterInput>,
 }
@@ -0,0 +1,58 @@
+use async_graphql::SimpleObject;
+
+pub type SectionRow = (String, String, String, i32, String, String);
+
+#[derive(SimpleObject, Clone)]
+pub struct Section {
+    pub id: String,
+    #[graphql(name = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    pub position: i32,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl From<SectionRow> for Section {
+    fn from(row: SectionRow) -> Self {
+        Section {
+            id: row.0,
+            project_id: row.1,
+            name: row.2,
+            position: row.3,
+            created_at: row.4,
+            updated_at: row.5,
+        }
+    }
+}
+
+/// Fetch a single section by id
+pub async fn fetch_section(
+    pool: &sqlx::SqlitePool,
+    section_id: &str,
+) -> Result<Section, sqlx::Error> {
+    let row = sqlx::query_as::<_, SectionRow>(
+        "SELECT id, project_id, name, position, created_at, updated_at FROM sections WHERE id = ?1",
+    )
+    .bind(section_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(row.into())
+}
+
+/// All sections of a project in display order
+pub async fn fetch_sections(
+    pool: &sqlx::SqlitePool,
+    project_id: &str,
+) -> Result<Vec<Section>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, SectionRow>(
+        "SELECT id, project_id, name, position, created_at, updated_at FROM sections \
+         WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+    )
+    .bind(project_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(Section::from).collect())
+}
@@ -0,0 +1,13 @@
+use async_graphql::SimpleObject;
+
+use super::Task;
+use crate::tasks::workflow::ProjectStatus;
+
+/// One column of a project's board: a workflow status and the tasks in it
+#[derive(SimpleObject)]
+pub struct StatusColumn {
+    pub status: ProjectStatus,
+    pub items: Vec<Task>,
+    #[graphql(name = "totalCount")]
+    pub total_count: i32,
+}
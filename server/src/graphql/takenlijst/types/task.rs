@@ -1,2 +1,262 @@
 // Re-export the Task GraphQL object from the tasks module
 pub use crate::tasks::Task;
+
+use async_graphql::{ComplexObject, Context};
+use sqlx::SqlitePool;
+
+use super::comment::{COMMENT_COLUMNS, CommentRow};
+use super::task_activity::{ACTIVITY_COLUMNS, TaskActivity};
+use super::{PagedActivity, PagedComments, RecurringSeries, fetch_series};
+use crate::tasks::attachments::{ATTACHMENT_COLUMNS, Attachment};
+use crate::tasks::custom_fields::{self, CustomFieldValue};
+use crate::tasks::reminders::{REMINDER_COLUMNS, Reminder};
+use crate::tasks::time_tracking::{TIME_ENTRY_COLUMNS, TimeEntry, entry_seconds, to_minutes};
+use crate::tasks::watchers::{self, TaskWatcher};
+use crate::tasks::workflow::{self, ProjectStatus};
+use crate::tasks::{SubtaskProgress, TASK_COLUMNS, TaskTimes};
+
+#[ComplexObject]
+impl Task {
+    /// Direct subtasks, oldest first. A task in the trash lists the subtasks deleted with it.
+    async fn subtasks(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Task>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM tasks t WHERE t.parent_task_id = ?1 AND t.deleted_at IS ?2 ORDER BY t.created_at ASC",
+            TASK_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&self.id)
+            .bind(&self.deleted_at)
+            .fetch_all(pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| Task::from_row(row, self.tz))
+            .collect())
+    }
+
+    /// Completion of direct subtasks; abandoned subtasks are not counted
+    #[graphql(name = "subtaskProgress")]
+    async fn subtask_progress(&self, ctx: &Context<'_>) -> async_graphql::Result<SubtaskProgress> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let (done, total) = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COALESCE(SUM(CASE WHEN status = 'done' THEN 1 ELSE 0 END), 0), COUNT(*) \
+             FROM tasks WHERE parent_task_id = ?1 AND status != 'abandoned' AND deleted_at IS ?2",
+        )
+        .bind(&self.id)
+        .bind(&self.deleted_at)
+        .fetch_one(pool)
+        .await?;
+        Ok(SubtaskProgress {
+            done: done as i32,
+            total: total as i32,
+        })
+    }
+
+    /// Whether any task this one is blocked by is still open
+    #[graphql(name = "isBlocked")]
+    async fn is_blocked(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let count = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM task_dependencies d \
+             INNER JOIN tasks b ON b.id = d.blocked_by_task_id \
+             WHERE d.task_id = ?1 AND b.status = 'todo' AND b.deleted_at IS NULL",
+        )
+        .bind(&self.id)
+        .fetch_one(pool)
+        .await?
+        .0;
+        Ok(count > 0)
+    }
+
+    /// Tasks this one waits on
+    #[graphql(name = "blockedBy")]
+    async fn blocked_by(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Task>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM task_dependencies d INNER JOIN tasks t ON t.id = d.blocked_by_task_id \
+             WHERE d.task_id = ?1 AND t.deleted_at IS NULL ORDER BY d.created_at ASC",
+            TASK_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(&self.id).fetch_all(pool).await?;
+        Ok(rows
+            .iter()
+            .map(|row| Task::from_row(row, self.tz))
+            .collect())
+    }
+
+    /// Tasks waiting on this one
+    async fn blocking(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Task>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM task_dependencies d INNER JOIN tasks t ON t.id = d.task_id \
+             WHERE d.blocked_by_task_id = ?1 AND t.deleted_at IS NULL ORDER BY d.created_at ASC",
+            TASK_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(&self.id).fetch_all(pool).await?;
+        Ok(rows
+            .iter()
+            .map(|row| Task::from_row(row, self.tz))
+            .collect())
+    }
+
+    /// Comments, oldest first
+    async fn comments(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<PagedComments> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let total_count =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM task_comments WHERE task_id = ?1")
+                .bind(&self.id)
+                .fetch_one(pool)
+                .await?
+                .0;
+        let sql = format!(
+            "SELECT {} FROM task_comments WHERE task_id = ?1 ORDER BY created_at ASC, id ASC LIMIT ?2 OFFSET ?3",
+            COMMENT_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, CommentRow>(&sql)
+            .bind(&self.id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+        Ok(PagedComments {
+            items: rows.into_iter().map(Into::into).collect(),
+            total_count: total_count as i32,
+        })
+    }
+
+    /// Attached files, oldest first
+    async fn attachments(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Attachment>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM task_attachments WHERE task_id = ?1 ORDER BY created_at ASC, id ASC",
+            ATTACHMENT_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(&self.id).fetch_all(pool).await?;
+        Ok(rows.iter().map(Attachment::from_row).collect())
+    }
+
+    /// Change history, newest first
+    async fn activity(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 0)] offset: i32,
+        #[graphql(default = 20)] limit: i32,
+    ) -> async_graphql::Result<PagedActivity> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let total_count =
+            sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM task_activity WHERE task_id = ?1")
+                .bind(&self.id)
+                .fetch_one(pool)
+                .await?
+                .0;
+        let sql = format!(
+            "SELECT {} FROM task_activity WHERE task_id = ?1 ORDER BY created_at DESC, rowid DESC LIMIT ?2 OFFSET ?3",
+            ACTIVITY_COLUMNS
+        );
+        let rows = sqlx::query(&sql)
+            .bind(&self.id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+        Ok(PagedActivity {
+            items: rows.iter().map(TaskActivity::from_row).collect(),
+            total_count: total_count as i32,
+        })
+    }
+
+    /// Minutes tracked by everyone, including time so far on running timers
+    #[graphql(name = "trackedMinutes")]
+    async fn tracked_minutes(&self, ctx: &Context<'_>) -> async_graphql::Result<i32> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let rows = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT started_at, ended_at FROM time_entries WHERE task_id = ?1",
+        )
+        .bind(&self.id)
+        .fetch_all(pool)
+        .await?;
+        let seconds = rows
+            .iter()
+            .map(|(started_at, ended_at)| entry_seconds(started_at, ended_at.as_deref()))
+            .sum();
+        Ok(to_minutes(seconds))
+    }
+
+    /// Recorded time, most recent first
+    #[graphql(name = "timeEntries")]
+    async fn time_entries(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TimeEntry>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM time_entries WHERE task_id = ?1 ORDER BY started_at DESC, id ASC",
+            TIME_ENTRY_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(&self.id).fetch_all(pool).await?;
+        Ok(rows.iter().map(TimeEntry::from_row).collect())
+    }
+
+    /// Values set for the project's custom fields, in the project's field order
+    #[graphql(name = "customFields")]
+    async fn custom_fields(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<CustomFieldValue>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        Ok(custom_fields::task_values(&mut *pool.acquire().await?, &self.id).await?)
+    }
+
+    /// The task's status in its project's workflow
+    #[graphql(name = "workflowStatus")]
+    async fn workflow_status(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<ProjectStatus>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        match &self.status_id {
+            Some(status_id) => Ok(Some(
+                workflow::fetch_status(&mut *pool.acquire().await?, status_id).await?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// The recurring series that generated the task, including one that has since ended
+    async fn series(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<RecurringSeries>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        match &self.series_id {
+            Some(series_id) => Ok(Some(fetch_series(pool, series_id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Project members following the task, longest watching first
+    async fn watchers(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<TaskWatcher>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        Ok(watchers::list(&mut *pool.acquire().await?, &self.id).await?)
+    }
+
+    /// Reminders, with when each fires as the task is now scheduled
+    async fn reminders(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Reminder>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let sql = format!(
+            "SELECT {} FROM task_reminders WHERE task_id = ?1 ORDER BY created_at ASC, id ASC",
+            REMINDER_COLUMNS
+        );
+        let rows = sqlx::query(&sql).bind(&self.id).fetch_all(pool).await?;
+        let times = TaskTimes {
+            scheduled_date: self.scheduled_date.clone(),
+            scheduled_time_minutes: self.scheduled_time_minutes,
+            deadline_date: self.deadline_date.clone(),
+            deadline_time_minutes: self.deadline_time_minutes,
+        };
+        Ok(rows
+            .iter()
+            .map(|row| Reminder::from_row(row, &times))
+            .collect())
+    }
+}
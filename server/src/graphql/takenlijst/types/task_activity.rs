@@ -0,0 +1,62 @@
+use async_graphql::{Json, SimpleObject};
+use serde_json::Value;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+use crate::tasks::activity::{ActivityAction, FieldChange};
+
+pub const ACTIVITY_COLUMNS: &str = "id, task_id, project_id, actor_id, action, changes, created_at";
+
+#[derive(SimpleObject)]
+pub struct ActivityChange {
+    pub field: String,
+    pub old: Json<Value>,
+    pub new: Json<Value>,
+}
+
+#[derive(SimpleObject)]
+pub struct TaskActivity {
+    pub id: String,
+    #[graphql(name = "taskId")]
+    pub task_id: String,
+    #[graphql(name = "projectId")]
+    pub project_id: String,
+    #[graphql(name = "actorId")]
+    pub actor_id: String,
+    pub action: ActivityAction,
+    pub changes: Vec<ActivityChange>,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+}
+
+#[derive(SimpleObject)]
+pub struct PagedActivity {
+    pub items: Vec<TaskActivity>,
+    #[graphql(name = "totalCount")]
+    pub total_count: i32,
+}
+
+impl TaskActivity {
+    /// Build a `TaskActivity` from a row selected with `ACTIVITY_COLUMNS`
+    pub fn from_row(row: &SqliteRow) -> Self {
+        let action: String = row.get("action");
+        let changes: String = row.get("changes");
+        let changes: Vec<FieldChange> = serde_json::from_str(&changes).unwrap_or_default();
+        TaskActivity {
+            id: row.get("id"),
+            task_id: row.get("task_id"),
+            project_id: row.get("project_id"),
+            actor_id: row.get("actor_id"),
+            action: ActivityAction::from_db(&action),
+            changes: changes
+                .into_iter()
+                .map(|c| ActivityChange {
+                    field: c.field,
+                    old: Json(c.old),
+                    new: Json(c.new),
+                })
+                .collect(),
+            created_at: row.get("created_at"),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use async_graphql::SimpleObject;
+
+use super::{Section, Task};
+
+/// A run of tasks sharing a section; `section` is null for unsectioned tasks
+#[derive(SimpleObject)]
+pub struct TaskGroup {
+    pub section: Option<Section>,
+    pub items: Vec<Task>,
+}
@@ -0,0 +1,35 @@
+use async_graphql::SimpleObject;
+
+/// Time tracked in a project over a range of local dates
+#[derive(SimpleObject, Clone, Debug)]
+pub struct TimeReport {
+    pub from: String,
+    pub to: String,
+    #[graphql(name = "totalMinutes")]
+    pub total_minutes: i32,
+    /// Time on tasks without tags
+    #[graphql(name = "untaggedMinutes")]
+    pub untagged_minutes: i32,
+    /// Most time first
+    #[graphql(name = "byUser")]
+    pub by_user: Vec<UserTime>,
+    /// Most time first. Time on a task with several tags counts towards each of them.
+    #[graphql(name = "byTag")]
+    pub by_tag: Vec<TagTime>,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct UserTime {
+    #[graphql(name = "userId")]
+    pub user_id: String,
+    pub username: String,
+    pub minutes: i32,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct TagTime {
+    #[graphql(name = "tagId")]
+    pub tag_id: String,
+    pub name: String,
+    pub minutes: i32,
+}
@@ -0,0 +1,22 @@
+use async_graphql::{InputObject, MaybeUndefined};
+
+use crate::tasks::TaskPriority;
+
+#[derive(InputObject, Clone, Debug, Default)]
+pub struct UpdateSeriesInput {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[graphql(name = "assigneeId")]
+    pub assignee_id: Option<String>,
+    pub priority: Option<TaskPriority>,
+    // null clears the estimate
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: MaybeUndefined<i32>,
+    #[graphql(name = "defaultTagIds")]
+    pub default_tag_ids: Option<Vec<String>>,
+    // Calendar series only; not for a single occurrence
+    pub rrule: Option<String>,
+    // After-completion series only; not for a single occurrence
+    #[graphql(name = "intervalDays")]
+    pub interval_days: Option<i32>,
+}
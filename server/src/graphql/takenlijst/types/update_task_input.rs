@@ -1,4 +1,10 @@
-use async_graphql::InputObject;
+use async_graphql::{InputObject, MaybeUndefined};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use super::CustomFieldValueInput;
+use crate::tasks::TaskPriority;
+use crate::tasks::custom_fields::{StoredValue, snapshot_key};
 
 #[derive(InputObject, Clone, Debug, Default)]
 pub struct UpdateTaskInput {
@@ -6,6 +12,16 @@ pub struct UpdateTaskInput {
     pub description: Option<String>,
     #[graphql(name = "assigneeId")]
     pub assignee_id: Option<String>,
+    // null moves the task out of its section
+    #[graphql(name = "sectionId")]
+    pub section_id: MaybeUndefined<String>,
+    // null promotes a subtask to a top-level task
+    #[graphql(name = "parentTaskId")]
+    pub parent_task_id: MaybeUndefined<String>,
+    pub priority: Option<TaskPriority>,
+    // null clears the estimate
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: MaybeUndefined<i32>,
     #[graphql(name = "scheduledDate")]
     pub scheduled_date: Option<String>,
     #[graphql(name = "scheduledTimeMinutes")]
@@ -16,4 +32,106 @@ pub struct UpdateTaskInput {
     pub deadline_time_minutes: Option<i32>,
     #[graphql(name = "tagIds")]
     pub tag_ids: Option<Vec<String>>,
+    // Values for the project's custom fields; fields left out are unchanged
+    #[graphql(name = "customFields")]
+    pub custom_fields: Option<Vec<CustomFieldValueInput>>,
+}
+
+impl UpdateTaskInput {
+    /// The fields this input sets, keyed and encoded like an activity snapshot, along with
+    /// its checked custom field values
+    pub fn changes(&self, custom_values: &[(String, Option<StoredValue>)]) -> Map<String, Value> {
+        fn maybe<T: Serialize>(value: &MaybeUndefined<T>) -> Option<Value> {
+            match value {
+                MaybeUndefined::Value(v) => Some(json!(v)),
+                MaybeUndefined::Null => Some(Value::Null),
+                MaybeUndefined::Undefined => None,
+            }
+        }
+
+        let mut tag_ids = self.tag_ids.clone();
+        if let Some(tag_ids) = tag_ids.as_mut() {
+            tag_ids.sort();
+            tag_ids.dedup();
+        }
+        [
+            ("title", self.title.as_ref().map(|t| json!(t.trim()))),
+            ("description", self.description.as_ref().map(|d| json!(d))),
+            ("assigneeId", self.assignee_id.as_ref().map(|a| json!(a))),
+            ("sectionId", maybe(&self.section_id)),
+            ("parentTaskId", maybe(&self.parent_task_id)),
+            ("priority", self.priority.map(|p| json!(p))),
+            ("estimateMinutes", maybe(&self.estimate_minutes)),
+            (
+                "scheduledDate",
+                self.scheduled_date.as_ref().map(|d| json!(d)),
+            ),
+            (
+                "scheduledTimeMinutes",
+                self.scheduled_time_minutes.map(|m| json!(m)),
+            ),
+            (
+                "deadlineDate",
+                self.deadline_date.as_ref().map(|d| json!(d)),
+            ),
+            (
+                "deadlineTimeMinutes",
+                self.deadline_time_minutes.map(|m| json!(m)),
+            ),
+            ("tagIds", tag_ids.map(|t| json!(t))),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| Some((field.to_string(), value?)))
+        .chain(custom_values.iter().map(|(field_id, value)| {
+            (
+                snapshot_key(field_id),
+                value.as_ref().map_or(Value::Null, StoredValue::to_json),
+            )
+        }))
+        .collect()
+    }
+
+    /// Unset every field not named in `fields`
+    pub fn retain(&mut self, fields: &[String]) {
+        let keep = |field: &str| fields.iter().any(|f| f == field);
+        if !keep("title") {
+            self.title = None;
+        }
+        if !keep("description") {
+            self.description = None;
+        }
+        if !keep("assigneeId") {
+            self.assignee_id = None;
+        }
+        if !keep("sectionId") {
+            self.section_id = MaybeUndefined::Undefined;
+        }
+        if !keep("parentTaskId") {
+            self.parent_task_id = MaybeUndefined::Undefined;
+        }
+        if !keep("priority") {
+            self.priority = None;
+        }
+        if !keep("estimateMinutes") {
+            self.estimate_minutes = MaybeUndefined::Undefined;
+        }
+        if !keep("scheduledDate") {
+            self.scheduled_date = None;
+        }
+        if !keep("scheduledTimeMinutes") {
+            self.scheduled_time_minutes = None;
+        }
+        if !keep("deadlineDate") {
+            self.deadline_date = None;
+        }
+        if !keep("deadlineTimeMinutes") {
+            self.deadline_time_minutes = None;
+        }
+        if !keep("tagIds") {
+            self.tag_ids = None;
+        }
+        if let Some(values) = self.custom_fields.as_mut() {
+            values.retain(|v| keep(&snapshot_key(&v.field_id)));
+        }
+    }
 }
@@ -0,0 +1,67 @@
+//! Background work that runs alongside the HTTP server.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+use crate::tasks::reminders::{self, LogSink};
+use crate::{config, tasks};
+
+/// How often the trash is checked for tasks past their retention period
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// How often reminders are checked for and delivered
+const REMINDER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often recurring series are topped up to their horizon
+const SERIES_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Start all background jobs
+pub fn spawn(pool: SqlitePool) {
+    tokio::spawn(purge_trash(pool.clone()));
+    tokio::spawn(send_reminders(pool.clone()));
+    tokio::spawn(materialize_series(pool));
+}
+
+async fn purge_trash(pool: SqlitePool) {
+    let root = PathBuf::from(config::attachments_dir());
+    let mut interval = tokio::time::interval(PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        match tasks::trash::purge_expired(&pool, &root, config::TRASH_RETENTION_DAYS).await {
+            Ok(0) => {}
+            Ok(purged) => tracing::info!("Purged {} tasks from the trash", purged),
+            Err(e) => tracing::error!("Trash purge failed: {}", e),
+        }
+    }
+}
+
+async fn send_reminders(pool: SqlitePool) {
+    let mut interval = tokio::time::interval(REMINDER_INTERVAL);
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        if let Err(e) = reminders::enqueue_due(&pool, now).await {
+            tracing::error!("Queueing reminders failed: {}", e);
+        }
+        match reminders::deliver_pending(&pool, &LogSink, now).await {
+            Ok(0) => {}
+            Ok(sent) => tracing::info!("Delivered {} reminders", sent),
+            Err(e) => tracing::error!("Delivering reminders failed: {}", e),
+        }
+    }
+}
+
+async fn materialize_series(pool: SqlitePool) {
+    let mut interval = tokio::time::interval(SERIES_INTERVAL);
+    loop {
+        interval.tick().await;
+        match tasks::series::materialize_all(&pool, Utc::now()).await {
+            Ok(0) => {}
+            Ok(created) => tracing::info!("Created {} tasks for recurring series", created),
+            Err(e) => tracing::error!("Materializing recurring series failed: {}", e),
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub mod db;
 mod error;
 mod error_codes;
 mod graphql;
+mod jobs;
 mod server;
 pub mod tasks;
 
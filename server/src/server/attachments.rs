@@ -0,0 +1,284 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::Json;
+use axum::body::Bytes;
+use axum::extract::{Extension, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+use super::AppState;
+use crate::auth::Claims;
+use crate::auth::guard::is_member;
+use crate::error_codes::ErrorCode;
+use crate::tasks::attachments::{
+    self, ATTACHMENT_COLUMNS, Attachment, fetch_attachment, project_usage_bytes,
+    remove_if_unreferenced,
+};
+use crate::{AppError, config};
+
+#[derive(Deserialize)]
+pub struct UploadParams {
+    filename: String,
+}
+
+fn error(code: ErrorCode, msg: &str) -> AppError {
+    AppError {
+        code,
+        msg: msg.into(),
+    }
+}
+
+fn internal<E: std::fmt::Display>(e: E) -> AppError {
+    tracing::error!("attachment request failed: {}", e);
+    error(ErrorCode::Internal, "Internal error")
+}
+
+/// Resolve the caller's user id and check they can see `project_id`
+async fn require_member(
+    pool: &SqlitePool,
+    claims: Option<Extension<Arc<Claims>>>,
+    project_id: &str,
+) -> Result<String, AppError> {
+    let Some(Extension(claims)) = claims else {
+        return Err(error(
+            ErrorCode::AuthenticationRequired,
+            "Authentication required",
+        ));
+    };
+
+    let user_id = sqlx::query_as::<_, (String,)>("SELECT id FROM users WHERE username = ?1")
+        .bind(&claims.sub)
+        .fetch_optional(pool)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| error(ErrorCode::AuthenticationRequired, "Authentication required"))?
+        .0;
+
+    if !is_member(pool, &user_id, project_id)
+        .await
+        .map_err(internal)?
+    {
+        return Err(error(
+            ErrorCode::PermissionDenied,
+            "Project not found or access denied",
+        ));
+    }
+
+    Ok(user_id)
+}
+
+/// Upload a file to a task. The request body is the raw file and `Content-Type` its type:
+///
+/// `POST /v1/tasks/:task_id/attachments?filename=receipt.pdf`
+pub async fn upload(
+    State(state): State<AppState>,
+    claims: Option<Extension<Arc<Claims>>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<UploadParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    let pool = &state.pool;
+
+    let (project_id, archived_at) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT t.project_id, p.archived_at FROM tasks t JOIN projects p ON p.id = t.project_id WHERE t.id = ?1 AND t.deleted_at IS NULL",
+    )
+    .bind(&task_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(internal)?
+    .ok_or_else(|| error(ErrorCode::NotFound, "Task not found"))?;
+
+    let user_id = require_member(pool, claims, &project_id).await?;
+
+    if archived_at.is_some() {
+        return Err(error(
+            ErrorCode::PermissionDenied,
+            "Project is archived; tasks are read-only",
+        ));
+    }
+
+    let filename = attachments::sanitize_filename(&params.filename)
+        .ok_or_else(|| error(ErrorCode::ValidationFailed, "Invalid filename"))?;
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(attachments::normalize_content_type)
+        .ok_or_else(|| {
+            error(
+                ErrorCode::ValidationFailed,
+                "Unsupported file type; upload a JPEG, PNG, WebP, HEIC image or a PDF",
+            )
+        })?;
+    if body.is_empty() {
+        return Err(error(ErrorCode::ValidationFailed, "File is empty"));
+    }
+    if !attachments::content_matches(&content_type, &body) {
+        return Err(error(
+            ErrorCode::ValidationFailed,
+            "File contents don't match its Content-Type",
+        ));
+    }
+
+    // Hashing, writing and thumbnailing are blocking work
+    let root = PathBuf::from(config::attachments_dir());
+    let stored_type = content_type.clone();
+    let stored_body = body.clone();
+    let (sha256, has_thumbnail) =
+        tokio::task::spawn_blocking(move || attachments::store(&root, &stored_body, &stored_type))
+            .await
+            .map_err(internal)?
+            .map_err(internal)?;
+
+    // The quota is checked in the same transaction as the insert, so concurrent uploads
+    // can't both fit in the last of the space
+    let mut tx = pool.begin().await.map_err(internal)?;
+    let used = project_usage_bytes(&mut tx, &project_id)
+        .await
+        .map_err(internal)?;
+    if used + body.len() as i64 > config::PROJECT_ATTACHMENT_QUOTA_BYTES {
+        drop(tx);
+        let root = PathBuf::from(config::attachments_dir());
+        remove_if_unreferenced(pool, &root, &sha256)
+            .await
+            .map_err(internal)?;
+        return Err(error(
+            ErrorCode::QuotaExceeded,
+            "Project attachment storage is full",
+        ));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO task_attachments (id, task_id, uploaded_by, filename, content_type, size_bytes, sha256, has_thumbnail) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )
+    .bind(&id)
+    .bind(&task_id)
+    .bind(&user_id)
+    .bind(&filename)
+    .bind(&content_type)
+    .bind(body.len() as i64)
+    .bind(&sha256)
+    .bind(has_thumbnail)
+    .execute(&mut *tx)
+    .await
+    .map_err(internal)?;
+    tx.commit().await.map_err(internal)?;
+
+    let attachment = fetch_attachment(pool, &id).await.map_err(internal)?;
+    Ok((StatusCode::CREATED, Json(attachment)).into_response())
+}
+
+/// Load an attachment the caller is allowed to see. Attachments of trashed tasks aren't served.
+async fn authorized_attachment(
+    state: &AppState,
+    claims: Option<Extension<Arc<Claims>>>,
+    id: &str,
+) -> Result<Attachment, AppError> {
+    let pool = &state.pool;
+    let sql = format!(
+        "SELECT {}, (SELECT project_id FROM tasks WHERE id = task_id) AS project_id \
+         FROM task_attachments \
+         WHERE id = ?1 AND task_id IN (SELECT id FROM tasks WHERE deleted_at IS NULL)",
+        ATTACHMENT_COLUMNS
+    );
+    let row = sqlx::query(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| error(ErrorCode::NotFound, "Attachment not found"))?;
+    let project_id: String = sqlx::Row::get(&row, "project_id");
+
+    require_member(pool, claims, &project_id).await?;
+
+    Ok(Attachment::from_row(&row))
+}
+
+async fn file_response(content_type: &str, disposition: String, path: PathBuf) -> Response {
+    match tokio::fs::read(&path).await {
+        Ok(bytes) => (
+            StatusCode::OK,
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (header::CONTENT_DISPOSITION, disposition),
+                (header::CACHE_CONTROL, "private, max-age=86400".to_string()),
+                // Browsers must not second-guess the stored type
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("attachment file {:?} unreadable: {}", path, e);
+            (StatusCode::NOT_FOUND, "Not Found").into_response()
+        }
+    }
+}
+
+/// `inline` disposition with an ASCII fallback name and the original as `filename*` (RFC 6266)
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let encoded: String = filename
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect();
+    format!(
+        "inline; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+/// `GET /v1/attachments/:id`
+pub async fn download(
+    State(state): State<AppState>,
+    claims: Option<Extension<Arc<Claims>>>,
+    Path(id): Path<String>,
+) -> Result<Response, AppError> {
+    let attachment = authorized_attachment(&state, claims, &id).await?;
+    let root = PathBuf::from(config::attachments_dir());
+    Ok(file_response(
+        &attachment.content_type,
+        content_disposition(&attachment.filename),
+        attachments::blob_path(&root, &attachment.sha256),
+    )
+    .await)
+}
+
+/// `GET /v1/attachments/:id/thumbnail`
+pub async fn thumbnail(
+    State(state): State<AppState>,
+    claims: Option<Extension<Arc<Claims>>>,
+    Path(id): Path<String>,
+) -> Result<Response, AppError> {
+    let attachment = authorized_attachment(&state, claims, &id).await?;
+    if !attachment.has_thumbnail {
+        return Err(error(ErrorCode::NotFound, "Attachment has no thumbnail"));
+    }
+    let root = PathBuf::from(config::attachments_dir());
+    Ok(file_response(
+        "image/jpeg",
+        "inline".to_string(),
+        attachments::thumbnail_path(&root, &attachment.sha256),
+    )
+    .await)
+}
@@ -1,7 +1,7 @@
 use crate::error_codes::ErrorCode;
 use crate::{AppError, config, graphql};
 use axum::body::Bytes;
-use axum::extract::{ConnectInfo, Extension, Request};
+use axum::extract::{ConnectInfo, DefaultBodyLimit, Extension, Request};
 use axum::http::{Method, StatusCode};
 use axum::middleware::{self, Next};
 use axum::{
@@ -25,6 +25,7 @@ use tower_http::limit::RequestBodyLimitLayer;
 use tower_http::trace::{self, TraceLayer};
 use tracing::Span;
 
+mod attachments;
 pub mod logging;
 pub mod rate_limit;
 
@@ -47,6 +48,8 @@ pub async fn run(port: u16) {
 
     let schema = graphql::build(pool.clone());
 
+    crate::jobs::spawn(pool.clone());
+
     let app_state = AppState {
         schema,
         pool: pool.clone(),
@@ -55,7 +58,7 @@ pub async fn run(port: u16) {
     // Configure CORS
     let cors = CorsLayer::new()
         .allow_origin(Any)
-        .allow_methods([Method::POST])
+        .allow_methods([Method::GET, Method::POST])
         .allow_headers([
             axum::http::header::CONTENT_TYPE,
             axum::http::header::AUTHORIZATION,
@@ -86,7 +89,22 @@ pub async fn run(port: u16) {
         )
         .layer(middleware::from_fn(jwt_middleware))
         .layer(middleware::from_fn(content_type_middleware))
+        .with_state(app_state.clone())
+        .layer(RequestBodyLimitLayer::new(1 * 1024 * 1024)); // 1MB limit
+
+    // Attachment uploads carry raw file bytes rather than JSON, so they skip the
+    // content-type check and get their own body limit
+    let attachment_routes = Router::new()
+        .route("/v1/tasks/:task_id/attachments", post(attachments::upload))
+        .route("/v1/attachments/:id", get(attachments::download))
+        .route("/v1/attachments/:id/thumbnail", get(attachments::thumbnail))
+        .layer(middleware::from_fn(jwt_middleware))
         .with_state(app_state)
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(config::MAX_ATTACHMENT_BYTES));
+
+    let app = app
+        .merge(attachment_routes)
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(trace::DefaultMakeSpan::new().include_headers(true))
@@ -108,8 +126,7 @@ pub async fn run(port: u16) {
                     },
                 ),
         )
-        .layer(cors)
-        .layer(RequestBodyLimitLayer::new(1 * 1024 * 1024)); // 1MB limit
+        .layer(cors);
 
     let addr = SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 0], port));
     tracing::info!("listening on {}", addr);
@@ -0,0 +1,208 @@
+//! Change history of tasks.
+//!
+//! Mutations take a `snapshot` of a task before writing and pass it to `record` afterwards,
+//! which snapshots the task again and stores the fields that differ as one activity entry.
+
+use async_graphql::Enum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::SqliteConnection;
+
+use super::{custom_fields, versions};
+
+/// Fields tracked in the activity log, keyed by their GraphQL names
+const SNAPSHOT_SQL: &str = "SELECT json_object( \
+     'projectId', project_id, \
+     'title', title, \
+     'description', description, \
+     'status', status, \
+     'statusId', status_id, \
+     'priority', CASE priority WHEN 1 THEN 'low' WHEN 2 THEN 'medium' WHEN 3 THEN 'high' WHEN 4 THEN 'urgent' ELSE 'none' END, \
+     'estimateMinutes', estimate_minutes, \
+     'assigneeId', assignee_id, \
+     'sectionId', section_id, \
+     'parentTaskId', parent_task_id, \
+     'seriesId', series_id, \
+     'position', position, \
+     'scheduledDate', scheduled_date, \
+     'scheduledTimeMinutes', scheduled_time_minutes, \
+     'deadlineDate', deadline_date, \
+     'deadlineTimeMinutes', deadline_time_minutes, \
+     'tagIds', json((SELECT json_group_array(tag_id) FROM task_tags WHERE task_id = t.id)), \
+     'customFields', json((SELECT json_group_object(field_id, value) FROM task_custom_values WHERE task_id = t.id)) \
+ ) FROM tasks t WHERE id = ?1";
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ActivityAction {
+    Created,
+    Updated,
+    Completed,
+    Abandoned,
+    Restored,
+    Moved,
+    Deleted,
+    Undeleted,
+}
+
+impl ActivityAction {
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Completed => "completed",
+            Self::Abandoned => "abandoned",
+            Self::Restored => "restored",
+            Self::Moved => "moved",
+            Self::Deleted => "deleted",
+            Self::Undeleted => "undeleted",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "created" => Self::Created,
+            "completed" => Self::Completed,
+            "abandoned" => Self::Abandoned,
+            "restored" => Self::Restored,
+            "moved" => Self::Moved,
+            "deleted" => Self::Deleted,
+            "undeleted" => Self::Undeleted,
+            _ => Self::Updated,
+        }
+    }
+}
+
+/// One field's value before and after a change
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// Tracked fields of a task; empty when the task doesn't exist
+pub async fn snapshot(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Map<String, Value>, sqlx::Error> {
+    let row = sqlx::query_as::<_, (String,)>(SNAPSHOT_SQL)
+        .bind(task_id)
+        .fetch_optional(conn)
+        .await?;
+    let Some((json,)) = row else {
+        return Ok(Map::new());
+    };
+    let mut fields: Map<String, Value> =
+        serde_json::from_str(&json).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+    // Each custom field is tracked as a field of its own, so edits to different ones merge
+    if let Some(Value::Object(values)) = fields.remove("customFields") {
+        for (field_id, value) in values {
+            fields.insert(custom_fields::snapshot_key(&field_id), value);
+        }
+    }
+    // Tag order isn't meaningful, so don't let it show up as a change
+    if let Some(Value::Array(tags)) = fields.get_mut("tagIds") {
+        tags.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+    }
+    Ok(fields)
+}
+
+/// Fields whose values differ between two snapshots, by field name
+pub fn diff(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<FieldChange> {
+    let mut changes: Vec<FieldChange> = after
+        .iter()
+        .filter_map(|(field, new)| {
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let empty = |v: &Value| v.is_null() || v.as_array().is_some_and(|a| a.is_empty());
+            if old == *new || (empty(&old) && empty(new)) {
+                return None;
+            }
+            Some(FieldChange {
+                field: field.clone(),
+                old,
+                new: new.clone(),
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+/// Log what `actor_id` changed on a task since `before` was taken. Updates that leave every
+/// tracked field as it was aren't logged.
+pub async fn record(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    actor_id: &str,
+    action: ActivityAction,
+    before: &Map<String, Value>,
+) -> Result<(), sqlx::Error> {
+    let after = snapshot(&mut *conn, task_id).await?;
+    versions::save(&mut *conn, task_id, &after).await?;
+    let changes = diff(before, &after);
+    if changes.is_empty() && action == ActivityAction::Updated {
+        return Ok(());
+    }
+    let changes =
+        serde_json::to_string(&changes).map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
+
+    sqlx::query(
+        "INSERT INTO task_activity (id, task_id, project_id, actor_id, action, changes) \
+         SELECT ?1, id, project_id, ?3, ?4, ?5 FROM tasks WHERE id = ?2",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(task_id)
+    .bind(actor_id)
+    .bind(action.as_db())
+    .bind(changes)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn diff_reports_changed_fields_only() {
+        let before = fields(json!({
+            "title": "Paint fence",
+            "assigneeId": null,
+            "deadlineDate": "2025-03-01",
+            "tagIds": []
+        }));
+        let after = fields(json!({
+            "title": "Paint fence",
+            "assigneeId": "user2",
+            "deadlineDate": "2025-03-08",
+            "tagIds": []
+        }));
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                FieldChange {
+                    field: "assigneeId".into(),
+                    old: Value::Null,
+                    new: json!("user2"),
+                },
+                FieldChange {
+                    field: "deadlineDate".into(),
+                    old: json!("2025-03-01"),
+                    new: json!("2025-03-08"),
+                },
+            ]
+        );
+
+        // A new task reports only the fields it was created with
+        let created = diff(&Map::new(), &after);
+        let names: Vec<_> = created.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(names, vec!["assigneeId", "deadlineDate", "title"]);
+    }
+}
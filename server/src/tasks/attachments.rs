@@ -0,0 +1,300 @@
+//! Files attached to tasks.
+//!
+//! Contents are stored once per SHA-256 under the attachments directory
+//! (`<root>/<first two hex chars>/<hash>`), so the same photo attached twice takes the
+//! space of one. Image uploads also get a JPEG thumbnail under `<root>/thumbnails/`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use async_graphql::SimpleObject;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+/// Content types accepted for upload
+pub const ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "image/heic",
+    "application/pdf",
+];
+
+/// Longest edge of a generated thumbnail, in pixels
+pub const THUMBNAIL_SIZE: u32 = 256;
+
+pub const ATTACHMENT_COLUMNS: &str = "id, task_id, uploaded_by, filename, content_type, size_bytes, sha256, has_thumbnail, created_at";
+
+#[derive(SimpleObject, Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    #[graphql(name = "taskId")]
+    pub task_id: String,
+    #[graphql(name = "uploadedBy")]
+    pub uploaded_by: String,
+    pub filename: String,
+    #[graphql(name = "contentType")]
+    pub content_type: String,
+    #[graphql(name = "sizeBytes")]
+    pub size_bytes: i64,
+    pub sha256: String,
+    #[graphql(name = "hasThumbnail")]
+    pub has_thumbnail: bool,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    /// Authenticated download path, relative to the API host
+    pub url: String,
+    #[graphql(name = "thumbnailUrl")]
+    pub thumbnail_url: Option<String>,
+}
+
+impl Attachment {
+    /// Build an `Attachment` from a row selected with `ATTACHMENT_COLUMNS`
+    pub fn from_row(row: &SqliteRow) -> Self {
+        let id: String = row.get("id");
+        let has_thumbnail: bool = row.get("has_thumbnail");
+        Attachment {
+            url: format!("/v1/attachments/{}", id),
+            thumbnail_url: has_thumbnail.then(|| format!("/v1/attachments/{}/thumbnail", id)),
+            id,
+            task_id: row.get("task_id"),
+            uploaded_by: row.get("uploaded_by"),
+            filename: row.get("filename"),
+            content_type: row.get("content_type"),
+            size_bytes: row.get("size_bytes"),
+            sha256: row.get("sha256"),
+            has_thumbnail,
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+/// Fetch a single attachment
+pub async fn fetch_attachment(
+    pool: &sqlx::SqlitePool,
+    id: &str,
+) -> Result<Attachment, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM task_attachments WHERE id = ?1",
+        ATTACHMENT_COLUMNS
+    );
+    let row = sqlx::query(&sql).bind(id).fetch_one(pool).await?;
+    Ok(Attachment::from_row(&row))
+}
+
+/// Bytes of attachments currently stored against tasks in a project
+pub async fn project_usage_bytes(
+    conn: &mut sqlx::SqliteConnection,
+    project_id: &str,
+) -> Result<i64, sqlx::Error> {
+    let (used,): (i64,) = sqlx::query_as(
+        "SELECT COALESCE(SUM(a.size_bytes), 0) FROM task_attachments a \
+         JOIN tasks t ON t.id = a.task_id WHERE t.project_id = ?1",
+    )
+    .bind(project_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(used)
+}
+
+/// Normalise a client supplied content type (`image/JPEG; foo=bar` → `image/jpeg`) and
+/// check it is one we accept.
+pub fn normalize_content_type(content_type: &str) -> Option<String> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    ALLOWED_CONTENT_TYPES
+        .contains(&essence.as_str())
+        .then_some(essence)
+}
+
+/// Whether `bytes` start the way a file of `content_type` does, so a mislabelled upload
+/// can't be served back as something it isn't
+pub fn content_matches(content_type: &str, bytes: &[u8]) -> bool {
+    match content_type {
+        "application/pdf" => bytes.starts_with(b"%PDF-"),
+        // An ISO media file whose `ftyp` box names a HEIF brand
+        "image/heic" => {
+            bytes.get(4..8) == Some(b"ftyp")
+                && bytes
+                    .get(8..12)
+                    .is_some_and(|brand| [&b"heic"[..], b"heix", b"mif1", b"msf1"].contains(&brand))
+        }
+        _ => {
+            let expected = match content_type {
+                "image/jpeg" => image::ImageFormat::Jpeg,
+                "image/png" => image::ImageFormat::Png,
+                "image/webp" => image::ImageFormat::WebP,
+                _ => return false,
+            };
+            image::guess_format(bytes).is_ok_and(|format| format == expected)
+        }
+    }
+}
+
+/// Reduce a client supplied filename to its last path component, without control
+/// characters and at most 255 bytes long.
+pub fn sanitize_filename(filename: &str) -> Option<String> {
+    let name = filename.rsplit(['/', '\\']).next().unwrap_or("");
+    let mut cleaned: String = name.chars().filter(|c| !c.is_control()).collect();
+    cleaned = cleaned.trim().to_string();
+    while cleaned.len() > 255 {
+        cleaned.pop();
+    }
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        return None;
+    }
+    Some(cleaned)
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub fn blob_path(root: &Path, sha256: &str) -> PathBuf {
+    root.join(&sha256[..2]).join(sha256)
+}
+
+pub fn thumbnail_path(root: &Path, sha256: &str) -> PathBuf {
+    root.join("thumbnails")
+        .join(&sha256[..2])
+        .join(format!("{}.jpg", sha256))
+}
+
+/// Scale an image down to fit `THUMBNAIL_SIZE` and encode it as JPEG. `None` when the
+/// bytes can't be decoded (e.g. HEIC, which we store but can't read).
+pub fn make_thumbnail(bytes: &[u8]) -> Option<Vec<u8>> {
+    let image = image::load_from_memory(bytes).ok()?;
+    let thumbnail = image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8();
+    let mut out = io::Cursor::new(Vec::new());
+    thumbnail
+        .write_to(&mut out, image::ImageFormat::Jpeg)
+        .ok()?;
+    Some(out.into_inner())
+}
+
+/// Write `path` via a temporary file so readers never see a partial file.
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Store file contents under `root`, returning their hash and whether a thumbnail is
+/// available. Contents already on disk are not written again.
+pub fn store(root: &Path, bytes: &[u8], content_type: &str) -> io::Result<(String, bool)> {
+    let sha256 = sha256_hex(bytes);
+
+    let blob = blob_path(root, &sha256);
+    if !blob.exists() {
+        write_atomically(&blob, bytes)?;
+    }
+
+    let mut has_thumbnail = false;
+    if content_type.starts_with("image/") {
+        let thumb = thumbnail_path(root, &sha256);
+        if thumb.exists() {
+            has_thumbnail = true;
+        } else if let Some(jpeg) = make_thumbnail(bytes) {
+            write_atomically(&thumb, &jpeg)?;
+            has_thumbnail = true;
+        }
+    }
+
+    Ok((sha256, has_thumbnail))
+}
+
+/// Remove the stored contents for `sha256` once no attachment row refers to them.
+pub async fn remove_if_unreferenced(
+    pool: &sqlx::SqlitePool,
+    root: &Path,
+    sha256: &str,
+) -> Result<(), sqlx::Error> {
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM task_attachments WHERE sha256 = ?1")
+            .bind(sha256)
+            .fetch_one(pool)
+            .await?;
+    if count == 0 {
+        for path in [blob_path(root, sha256), thumbnail_path(root, sha256)] {
+            if let Err(e) = std::fs::remove_file(&path) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::warn!("Failed to remove attachment file {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbImage::from_pixel(width, height, image::Rgb([200, 30, 30]));
+        let mut out = io::Cursor::new(Vec::new());
+        image.write_to(&mut out, image::ImageFormat::Png).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn filenames_and_content_types_are_normalised() {
+        assert_eq!(
+            sanitize_filename("../../etc/passwd").as_deref(),
+            Some("passwd")
+        );
+        assert_eq!(
+            sanitize_filename("C:\\Users\\me\\receipt.pdf").as_deref(),
+            Some("receipt.pdf")
+        );
+        assert_eq!(sanitize_filename(" .. "), None);
+        assert_eq!(sanitize_filename("dir/"), None);
+
+        assert_eq!(
+            normalize_content_type("Image/JPEG; charset=binary").as_deref(),
+            Some("image/jpeg")
+        );
+        assert_eq!(normalize_content_type("text/html"), None);
+
+        assert!(content_matches("image/png", &png(2, 2)));
+        assert!(content_matches("application/pdf", b"%PDF-1.4"));
+        assert!(content_matches("image/heic", b"\0\0\0\x18ftypheic\0\0\0\0"));
+        assert!(!content_matches("image/jpeg", &png(2, 2)));
+        assert!(!content_matches("application/pdf", b"<html><script>"));
+    }
+
+    #[test]
+    fn store_dedupes_contents_and_thumbnails_images() {
+        let root = std::env::temp_dir().join(format!("attachments-{}", uuid::Uuid::new_v4()));
+        let bytes = png(800, 400);
+
+        let (sha, has_thumbnail) = store(&root, &bytes, "image/png").unwrap();
+        assert!(has_thumbnail);
+        assert_eq!(std::fs::read(blob_path(&root, &sha)).unwrap(), bytes);
+        let thumb =
+            image::load_from_memory(&std::fs::read(thumbnail_path(&root, &sha)).unwrap()).unwrap();
+        assert_eq!(
+            (thumb.width(), thumb.height()),
+            (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2)
+        );
+
+        let (again, _) = store(&root, &bytes, "image/png").unwrap();
+        assert_eq!(again, sha);
+
+        let (pdf_sha, pdf_thumbnail) = store(&root, b"%PDF-1.4", "application/pdf").unwrap();
+        assert_ne!(pdf_sha, sha);
+        assert!(!pdf_thumbnail);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
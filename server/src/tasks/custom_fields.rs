@@ -0,0 +1,540 @@
+//! Typed fields that project owners define for their project's tasks.
+//!
+//! Values are given and returned as text: numbers in decimal, dates as `YYYY-MM-DD` and
+//! checkboxes as `true`/`false`. `CustomField::parse` checks a value against its field's kind
+//! and rules and turns it into a `StoredValue`, which keeps the kind's SQLite storage class
+//! so that filters and sorting compare numbers as numbers.
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection};
+
+pub const CUSTOM_FIELD_COLUMNS: &str = "id, project_id, name, kind, options, min_value, max_value, max_length, position, created_at, updated_at";
+
+/// Longest text value, and the most a text field's `maxLength` can be set to
+pub const MAX_TEXT_LENGTH: i32 = 5000;
+
+/// Most choices a single-select field can have
+pub const MAX_OPTIONS: usize = 50;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CustomFieldKind {
+    Text,
+    Number,
+    /// A calendar date, `YYYY-MM-DD`
+    Date,
+    /// One of the field's `options`
+    SingleSelect,
+    Checkbox,
+    /// An http or https address
+    Url,
+}
+
+impl CustomFieldKind {
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Number => "number",
+            Self::Date => "date",
+            Self::SingleSelect => "single_select",
+            Self::Checkbox => "checkbox",
+            Self::Url => "url",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "number" => Self::Number,
+            "date" => Self::Date,
+            "single_select" => Self::SingleSelect,
+            "checkbox" => Self::Checkbox,
+            "url" => Self::Url,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// How a custom-field filter compares a task's value
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldOperator {
+    Equals,
+    /// Tasks without a value match too
+    NotEquals,
+    /// Number and date fields only
+    LessThan,
+    /// Number and date fields only
+    GreaterThan,
+    /// Case-insensitive substring match, for text and URL fields
+    Contains,
+    IsSet,
+    IsNotSet,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct CustomField {
+    pub id: String,
+    #[graphql(name = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    pub kind: CustomFieldKind,
+    /// Choices of a single-select field
+    pub options: Vec<String>,
+    #[graphql(name = "minValue")]
+    pub min_value: Option<f64>,
+    #[graphql(name = "maxValue")]
+    pub max_value: Option<f64>,
+    #[graphql(name = "maxLength")]
+    pub max_length: Option<i32>,
+    pub position: i32,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// A task's value for one custom field
+#[derive(SimpleObject, Clone, Debug)]
+pub struct CustomFieldValue {
+    #[graphql(name = "fieldId")]
+    pub field_id: String,
+    pub name: String,
+    pub kind: CustomFieldKind,
+    pub value: String,
+}
+
+/// A checked value, bound with the storage class of its kind
+#[derive(Clone, Debug, PartialEq)]
+pub enum StoredValue {
+    Text(String),
+    Number(f64),
+    Flag(bool),
+}
+
+impl StoredValue {
+    /// The value as a task snapshot holds it; SQLite's JSON has checkboxes as 0/1
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Text(text) => serde_json::json!(text),
+            Self::Number(number) => serde_json::json!(number),
+            Self::Flag(flag) => serde_json::json!(*flag as i32),
+        }
+    }
+}
+
+/// Key of a custom field's value in task snapshots, activity and versions
+pub fn snapshot_key(field_id: &str) -> String {
+    format!("customFields.{}", field_id)
+}
+
+impl CustomField {
+    pub fn from_row(row: &SqliteRow) -> Self {
+        let options: Option<String> = row.get("options");
+        CustomField {
+            id: row.get("id"),
+            project_id: row.get("project_id"),
+            name: row.get("name"),
+            kind: CustomFieldKind::from_db(row.get::<String, _>("kind").as_str()),
+            options: options
+                .and_then(|o| serde_json::from_str(&o).ok())
+                .unwrap_or_default(),
+            min_value: row.get("min_value"),
+            max_value: row.get("max_value"),
+            max_length: row.get("max_length"),
+            position: row.get("position"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    /// Check a value given as text against the field's kind and rules
+    pub fn parse(&self, raw: &str) -> Result<StoredValue, String> {
+        let raw = raw.trim();
+        match self.kind {
+            CustomFieldKind::Text => {
+                let max_length = self.max_length.unwrap_or(MAX_TEXT_LENGTH);
+                if raw.is_empty() {
+                    Err("must not be empty; use null to clear it".to_string())
+                } else if raw.chars().count() > max_length as usize {
+                    Err(format!("must be at most {} characters", max_length))
+                } else {
+                    Ok(StoredValue::Text(raw.to_string()))
+                }
+            }
+            CustomFieldKind::Number => {
+                let number = raw
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|n| n.is_finite())
+                    .ok_or_else(|| "must be a number".to_string())?;
+                if let Some(min) = self.min_value.filter(|min| number < *min) {
+                    return Err(format!("must be at least {}", format_number(min)));
+                }
+                if let Some(max) = self.max_value.filter(|max| number > *max) {
+                    return Err(format!("must be at most {}", format_number(max)));
+                }
+                Ok(StoredValue::Number(number))
+            }
+            CustomFieldKind::Date => NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .map(|date| StoredValue::Text(date.format("%Y-%m-%d").to_string()))
+                .map_err(|_| "must be a date as YYYY-MM-DD".to_string()),
+            CustomFieldKind::SingleSelect => self
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw))
+                .map(|option| StoredValue::Text(option.clone()))
+                .ok_or_else(|| format!("must be one of: {}", self.options.join(", "))),
+            CustomFieldKind::Checkbox => match raw.to_ascii_lowercase().as_str() {
+                "true" => Ok(StoredValue::Flag(true)),
+                "false" => Ok(StoredValue::Flag(false)),
+                _ => Err("must be true or false".to_string()),
+            },
+            CustomFieldKind::Url => {
+                let host = raw
+                    .strip_prefix("https://")
+                    .or_else(|| raw.strip_prefix("http://"))
+                    .unwrap_or_default();
+                if host.is_empty() || host.starts_with('/') || raw.contains(char::is_whitespace) {
+                    Err("must be an http or https URL".to_string())
+                } else if raw.len() > MAX_TEXT_LENGTH as usize {
+                    Err(format!("must be at most {} characters", MAX_TEXT_LENGTH))
+                } else {
+                    Ok(StoredValue::Text(raw.to_string()))
+                }
+            }
+        }
+    }
+
+    /// SQL condition on tasks `t` matching the filter, with the values to bind in order
+    pub fn filter(
+        &self,
+        op: CustomFieldOperator,
+        value: Option<&str>,
+    ) -> Result<(String, Vec<StoredValue>), String> {
+        let field_id = StoredValue::Text(self.id.clone());
+        let exists = |comparison: &str| {
+            format!(
+                "EXISTS (SELECT 1 FROM task_custom_values cv WHERE cv.task_id = t.id AND cv.field_id = ?{})",
+                comparison
+            )
+        };
+        let operand = || {
+            value
+                .ok_or_else(|| format!("{} needs a value to compare with", self.name))
+                .and_then(|v| self.parse(v).map_err(|e| format!("{} {}", self.name, e)))
+        };
+        let ordered = matches!(self.kind, CustomFieldKind::Number | CustomFieldKind::Date);
+        let textual = matches!(self.kind, CustomFieldKind::Text | CustomFieldKind::Url);
+
+        match op {
+            CustomFieldOperator::IsSet => Ok((exists(""), vec![field_id])),
+            CustomFieldOperator::IsNotSet => Ok((format!("NOT {}", exists("")), vec![field_id])),
+            CustomFieldOperator::Equals => {
+                Ok((exists(" AND cv.value = ?"), vec![field_id, operand()?]))
+            }
+            CustomFieldOperator::NotEquals => Ok((
+                format!("NOT {}", exists(" AND cv.value = ?")),
+                vec![field_id, operand()?],
+            )),
+            CustomFieldOperator::LessThan | CustomFieldOperator::GreaterThan if ordered => {
+                let comparison = if op == CustomFieldOperator::LessThan {
+                    " AND cv.value < ?"
+                } else {
+                    " AND cv.value > ?"
+                };
+                Ok((exists(comparison), vec![field_id, operand()?]))
+            }
+            CustomFieldOperator::Contains if textual => {
+                let needle = value.map(str::trim).unwrap_or_default();
+                Ok((
+                    exists(" AND cv.value LIKE ?"),
+                    vec![field_id, StoredValue::Text(format!("%{}%", needle))],
+                ))
+            }
+            _ => Err(format!(
+                "{} fields can't be filtered with {:?}",
+                self.kind.as_db(),
+                op
+            )),
+        }
+    }
+}
+
+/// Text form of a stored value, as read back with `CAST(value AS TEXT)`
+pub fn display_value(kind: CustomFieldKind, text: &str) -> String {
+    match kind {
+        CustomFieldKind::Number => text
+            .parse::<f64>()
+            .map(format_number)
+            .unwrap_or_else(|_| text.to_string()),
+        CustomFieldKind::Checkbox => (text != "0").to_string(),
+        _ => text.to_string(),
+    }
+}
+
+/// Whole numbers without a trailing `.0`
+fn format_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        format!("{}", number as i64)
+    } else {
+        number.to_string()
+    }
+}
+
+/// Trimmed choices with blanks and case-insensitive duplicates removed, in the given order
+pub fn clean_options(options: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for option in options.iter().map(|o| o.trim()).filter(|o| !o.is_empty()) {
+        if !cleaned.iter().any(|c| c.eq_ignore_ascii_case(option)) {
+            cleaned.push(option.to_string());
+        }
+    }
+    cleaned
+}
+
+/// Check that a field's rules fit its kind
+pub fn check_rules(
+    kind: CustomFieldKind,
+    options: &[String],
+    min_value: Option<f64>,
+    max_value: Option<f64>,
+    max_length: Option<i32>,
+) -> Result<(), String> {
+    if kind == CustomFieldKind::SingleSelect {
+        if options.is_empty() || options.len() > MAX_OPTIONS {
+            return Err(format!(
+                "Single-select fields need between 1 and {} options",
+                MAX_OPTIONS
+            ));
+        }
+        if options.iter().any(|o| o.chars().count() > 60) {
+            return Err("Options cannot exceed 60 characters".to_string());
+        }
+    } else if !options.is_empty() {
+        return Err("Only single-select fields have options".to_string());
+    }
+
+    if kind != CustomFieldKind::Number && (min_value.is_some() || max_value.is_some()) {
+        return Err("Only number fields have a minimum or maximum".to_string());
+    }
+    if [min_value, max_value]
+        .iter()
+        .flatten()
+        .any(|v| !v.is_finite())
+    {
+        return Err("Minimum and maximum must be finite numbers".to_string());
+    }
+    if let (Some(min), Some(max)) = (min_value, max_value)
+        && min > max
+    {
+        return Err("Minimum cannot be greater than maximum".to_string());
+    }
+
+    if let Some(max_length) = max_length {
+        if kind != CustomFieldKind::Text {
+            return Err("Only text fields have a maximum length".to_string());
+        }
+        if !(1..=MAX_TEXT_LENGTH).contains(&max_length) {
+            return Err(format!(
+                "Maximum length must be between 1 and {}",
+                MAX_TEXT_LENGTH
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The project's fields in display order
+pub async fn project_fields(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+) -> Result<Vec<CustomField>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM custom_fields WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        CUSTOM_FIELD_COLUMNS
+    );
+    let rows = sqlx::query(&sql).bind(project_id).fetch_all(conn).await?;
+    Ok(rows.iter().map(CustomField::from_row).collect())
+}
+
+pub async fn fetch_field(
+    conn: &mut SqliteConnection,
+    field_id: &str,
+) -> Result<CustomField, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM custom_fields WHERE id = ?1",
+        CUSTOM_FIELD_COLUMNS
+    );
+    let row = sqlx::query(&sql).bind(field_id).fetch_one(conn).await?;
+    Ok(CustomField::from_row(&row))
+}
+
+/// Check values given for a task against the fields of its project. A `None` value clears
+/// the field.
+pub fn parse_values(
+    fields: &[CustomField],
+    values: &[(String, Option<String>)],
+) -> Result<Vec<(String, Option<StoredValue>)>, String> {
+    values
+        .iter()
+        .map(|(field_id, value)| {
+            let field = fields
+                .iter()
+                .find(|f| &f.id == field_id)
+                .ok_or_else(|| "Custom field not found in this project".to_string())?;
+            let stored = value
+                .as_deref()
+                .map(|v| field.parse(v))
+                .transpose()
+                .map_err(|e| format!("{} {}", field.name, e))?;
+            Ok((field_id.clone(), stored))
+        })
+        .collect()
+}
+
+/// Write checked values for a task
+pub async fn store_values(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    values: &[(String, Option<StoredValue>)],
+) -> Result<(), sqlx::Error> {
+    for (field_id, value) in values {
+        let query = match value {
+            None => {
+                sqlx::query("DELETE FROM task_custom_values WHERE task_id = ?1 AND field_id = ?2")
+                    .bind(task_id)
+                    .bind(field_id)
+            }
+            Some(value) => {
+                let query = sqlx::query(
+                    "INSERT INTO task_custom_values (task_id, field_id, value) VALUES (?1, ?2, ?3) \
+                     ON CONFLICT (task_id, field_id) DO UPDATE SET value = excluded.value",
+                )
+                .bind(task_id)
+                .bind(field_id);
+                match value {
+                    StoredValue::Text(text) => query.bind(text.clone()),
+                    StoredValue::Number(number) => query.bind(*number),
+                    StoredValue::Flag(flag) => query.bind(*flag),
+                }
+            }
+        };
+        query.execute(&mut *conn).await?;
+    }
+    // Values live outside the tasks table, so bump the task's version by hand
+    if !values.is_empty() {
+        sqlx::query("UPDATE tasks SET updated_at = updated_at WHERE id = ?1")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// A task's values, in the order of the project's fields
+pub async fn task_values(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Vec<CustomFieldValue>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String, String, String)>(
+        "SELECT f.id, f.name, f.kind, CAST(v.value AS TEXT) FROM task_custom_values v \
+         INNER JOIN custom_fields f ON f.id = v.field_id \
+         INNER JOIN tasks t ON t.id = v.task_id AND t.project_id = f.project_id \
+         WHERE v.task_id = ?1 ORDER BY f.position ASC, f.created_at ASC",
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(field_id, name, kind, value)| {
+            let kind = CustomFieldKind::from_db(&kind);
+            CustomFieldValue {
+                field_id,
+                name,
+                kind,
+                value: display_value(kind, &value),
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(kind: CustomFieldKind) -> CustomField {
+        CustomField {
+            id: "f1".to_string(),
+            project_id: "p1".to_string(),
+            name: "Field".to_string(),
+            kind,
+            options: vec!["Small".to_string(), "Large".to_string()],
+            min_value: Some(0.0),
+            max_value: Some(10.0),
+            max_length: Some(5),
+            position: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn parse_checks_each_kind() {
+        use CustomFieldKind::*;
+        let cases = [
+            (Text, " hello ", Some(StoredValue::Text("hello".into()))),
+            (Text, "too long", None),
+            (Number, "2.5", Some(StoredValue::Number(2.5))),
+            (Number, "11", None),
+            (Number, "NaN", None),
+            (
+                Date,
+                "2025-02-28",
+                Some(StoredValue::Text("2025-02-28".into())),
+            ),
+            (Date, "2025-02-30", None),
+            (
+                SingleSelect,
+                "large",
+                Some(StoredValue::Text("Large".into())),
+            ),
+            (SingleSelect, "Medium", None),
+            (Checkbox, "TRUE", Some(StoredValue::Flag(true))),
+            (Checkbox, "yes", None),
+            (
+                Url,
+                "https://example.com/a",
+                Some(StoredValue::Text("https://example.com/a".into())),
+            ),
+            (Url, "ftp://example.com", None),
+            (Url, "https:///path", None),
+        ];
+        for (kind, raw, expected) in cases {
+            assert_eq!(field(kind).parse(raw).ok(), expected, "{:?} {}", kind, raw);
+        }
+    }
+
+    #[test]
+    fn rules_must_fit_the_kind() {
+        use CustomFieldKind::*;
+        let options = vec!["A".to_string()];
+        assert!(check_rules(SingleSelect, &options, None, None, None).is_ok());
+        assert!(check_rules(SingleSelect, &[], None, None, None).is_err());
+        assert!(check_rules(Text, &options, None, None, None).is_err());
+        assert!(check_rules(Number, &[], Some(1.0), Some(2.0), None).is_ok());
+        assert!(check_rules(Number, &[], Some(3.0), Some(2.0), None).is_err());
+        assert!(check_rules(Date, &[], Some(1.0), None, None).is_err());
+        assert!(check_rules(Text, &[], None, None, Some(100)).is_ok());
+        assert!(check_rules(Number, &[], None, None, Some(100)).is_err());
+
+        assert_eq!(
+            clean_options(&[" A ".into(), "a".into(), "".into(), "B".into()]),
+            ["A", "B"]
+        );
+        assert_eq!(display_value(Number, "3.0"), "3");
+        assert_eq!(display_value(Checkbox, "0"), "false");
+    }
+}
@@ -0,0 +1,72 @@
+//! "Blocked by" relations between tasks. An edge `task_id -> blocked_by_task_id` means the task
+//! cannot sensibly start until the blocker is closed.
+
+use sqlx::SqlitePool;
+
+/// Whether making `task_id` blocked by `blocked_by_task_id` would close a loop, i.e. the
+/// blocker already waits (directly or transitively) on the task
+pub async fn would_create_cycle(
+    pool: &SqlitePool,
+    task_id: &str,
+    blocked_by_task_id: &str,
+) -> Result<bool, sqlx::Error> {
+    if task_id == blocked_by_task_id {
+        return Ok(true);
+    }
+    let count = sqlx::query_as::<_, (i64,)>(
+        "WITH RECURSIVE upstream(id) AS ( \
+             SELECT blocked_by_task_id FROM task_dependencies WHERE task_id = ?1 \
+             UNION \
+             SELECT d.blocked_by_task_id FROM task_dependencies d INNER JOIN upstream u ON d.task_id = u.id \
+         ) SELECT COUNT(*) FROM upstream WHERE id = ?2",
+    )
+    .bind(blocked_by_task_id)
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?
+    .0;
+    Ok(count > 0)
+}
+
+/// Ids of the open blockers of the given tasks, ignoring blockers that are themselves in the
+/// set or in the trash
+pub async fn open_blockers(
+    pool: &SqlitePool,
+    task_ids: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    if task_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; task_ids.len()].join(",");
+    let sql = format!(
+        "SELECT DISTINCT b.id FROM task_dependencies d \
+         INNER JOIN tasks b ON b.id = d.blocked_by_task_id \
+         WHERE d.task_id IN ({0}) AND b.status = 'todo' AND b.deleted_at IS NULL AND b.id NOT IN ({0}) \
+         ORDER BY b.id",
+        placeholders
+    );
+    let mut query = sqlx::query_as::<_, (String,)>(&sql);
+    for _ in 0..2 {
+        for id in task_ids {
+            query = query.bind(id);
+        }
+    }
+    let rows = query.fetch_all(pool).await?;
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+/// Ids of the open, live tasks blocking a task
+pub async fn open_blocker_ids(
+    pool: &SqlitePool,
+    task_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String,)>(
+        "SELECT b.id FROM task_dependencies d \
+         INNER JOIN tasks b ON b.id = d.blocked_by_task_id \
+         WHERE d.task_id = ?1 AND b.status = 'todo' AND b.deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
@@ -0,0 +1,95 @@
+//! `@username` mentions in comment bodies.
+
+use sqlx::SqliteConnection;
+
+/// Usernames mentioned in `body`, in order of first appearance and without duplicates.
+///
+/// A mention is `@` at the start of the text or after a character that cannot be part of a
+/// username (so email addresses are ignored), followed by letters, digits, `_`, `.` or `-`.
+/// Trailing dots and dashes are treated as punctuation.
+pub fn parse_mentions(body: &str) -> Vec<String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '.' || c == '-';
+    let mut mentions: Vec<String> = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = body.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '@' && !prev.is_some_and(is_name_char) {
+            let start = i + 1;
+            let mut end = start;
+            while let Some(&(j, n)) = chars.peek() {
+                if !is_name_char(n) {
+                    break;
+                }
+                end = j + n.len_utf8();
+                chars.next();
+            }
+            let name = body[start..end].trim_end_matches(['.', '-']);
+            if !name.is_empty() && !mentions.iter().any(|m| m.eq_ignore_ascii_case(name)) {
+                mentions.push(name.to_string());
+            }
+            prev = body[..end].chars().last();
+            continue;
+        }
+        prev = Some(c);
+    }
+
+    mentions
+}
+
+/// Replace the stored mentions of a comment with the project members named in `body`.
+/// Names that don't match a member are left as plain text.
+pub async fn store_mentions(
+    conn: &mut SqliteConnection,
+    comment_id: &str,
+    project_id: &str,
+    body: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM task_comment_mentions WHERE comment_id = ?1")
+        .bind(comment_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for username in parse_mentions(body) {
+        sqlx::query(
+            "INSERT OR IGNORE INTO task_comment_mentions (comment_id, user_id) \
+             SELECT ?1, u.id FROM users u \
+             WHERE LOWER(u.username) = LOWER(?2) AND ( \
+                 u.id = (SELECT owner_id FROM projects WHERE id = ?3) \
+                 OR u.id IN (SELECT user_id FROM project_members WHERE project_id = ?3) \
+             )",
+        )
+        .bind(comment_id)
+        .bind(&username)
+        .bind(project_id)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions_basic() {
+        assert_eq!(parse_mentions("@rob can you check?"), vec!["rob"]);
+        assert_eq!(
+            parse_mentions("Thanks @anna and @rob_2."),
+            vec!["anna", "rob_2"]
+        );
+    }
+
+    #[test]
+    fn test_parse_mentions_ignores_emails_and_bare_at() {
+        assert!(parse_mentions("mail rob@example.com").is_empty());
+        assert!(parse_mentions("meet @ 5").is_empty());
+    }
+
+    #[test]
+    fn test_parse_mentions_deduplicates_case_insensitively() {
+        assert_eq!(parse_mentions("@Anna, @anna (@ANNA)"), vec!["Anna"]);
+    }
+}
@@ -2,6 +2,27 @@ use async_graphql::{Enum, SimpleObject};
 use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+pub mod activity;
+pub mod attachments;
+pub mod custom_fields;
+pub mod dependencies;
+pub mod mentions;
+pub mod moving;
+pub mod ordering;
+pub mod quick_add;
+pub mod reminders;
+pub mod series;
+pub mod snooze;
+pub mod subtasks;
+pub mod template;
+pub mod time_tracking;
+pub mod trash;
+pub mod versions;
+pub mod watchers;
+pub mod workflow;
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub enum TaskStatus {
@@ -13,6 +34,31 @@ pub enum TaskStatus {
     Abandoned,
 }
 
+/// How urgent a task is. Stored as 0 (none) to 4 (urgent) so it sorts numerically.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskPriority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+/// Ordering of a task list
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskSort {
+    /// Undated tasks by title, then by scheduled, deadline and created dates
+    #[default]
+    Default,
+    /// Highest priority first, ties ordered as `Default`
+    Priority,
+    /// The project's drag-and-drop order
+    Manual,
+}
+
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
 pub enum TaskBucket {
     Overdue,
@@ -22,7 +68,26 @@ pub enum TaskBucket {
     NoDate,
 }
 
-#[derive(SimpleObject, Debug)]
+/// What `completeTask` does when the task still has open subtasks
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SubtaskCompletion {
+    /// Reject the completion until every subtask is closed
+    Refuse,
+    /// Complete all open subtasks along with the task
+    CompleteAll,
+}
+
+/// What moving a task to another project does when it was generated by a recurring series
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SeriesMovePolicy {
+    /// Reject the move
+    Refuse,
+    /// Move the task on its own; the series stays and keeps generating tasks
+    Detach,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+#[graphql(complex)]
 pub struct Task {
     pub id: String,
     #[graphql(name = "projectId")]
@@ -33,9 +98,22 @@ pub struct Task {
     pub assignee_id: Option<String>,
     #[graphql(name = "seriesId")]
     pub series_id: Option<String>,
+    /// For a task generated by a series, the occurrence it stands for
+    #[graphql(name = "occurrenceDate")]
+    pub occurrence_date: Option<String>,
+    #[graphql(name = "sectionId")]
+    pub section_id: Option<String>,
+    /// Workflow status within the project; `status` is its category
+    #[graphql(name = "statusId")]
+    pub status_id: Option<String>,
+    #[graphql(name = "parentTaskId")]
+    pub parent_task_id: Option<String>,
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    pub priority: TaskPriority,
+    #[graphql(name = "estimateMinutes")]
+    pub estimate_minutes: Option<i32>,
     #[graphql(name = "scheduledDate")]
     pub scheduled_date: Option<String>,
     #[graphql(name = "scheduledTimeMinutes")]
@@ -52,6 +130,18 @@ pub struct Task {
     pub abandoned_at: Option<String>,
     #[graphql(name = "abandonedBy")]
     pub abandoned_by: Option<String>,
+    // Set while the task is in the trash
+    #[graphql(name = "deletedAt")]
+    pub deleted_at: Option<String>,
+    #[graphql(name = "deletedBy")]
+    pub deleted_by: Option<String>,
+    /// Times the task has been snoozed
+    #[graphql(name = "snoozeCount")]
+    pub snooze_count: i32,
+    #[graphql(name = "lastSnoozedAt")]
+    pub last_snoozed_at: Option<String>,
+    /// Rank in the project's manual order
+    pub position: Option<String>,
     #[graphql(name = "createdAt")]
     pub created_at: String,
     #[graphql(name = "updatedAt")]
@@ -60,6 +150,155 @@ pub struct Task {
     #[graphql(name = "isOverdue")]
     pub is_overdue: bool,
     pub bucket: TaskBucket,
+    /// Notices from the mutation that returned the task, such as completing it while open
+    /// tasks still block it. Always empty when the task is read any other way.
+    pub warnings: Vec<String>,
+    // Timezone the derived fields were computed in, reused for nested tasks
+    #[graphql(skip)]
+    pub tz: Tz,
+}
+
+#[derive(SimpleObject, Clone, Copy, Debug)]
+pub struct SubtaskProgress {
+    pub done: i32,
+    pub total: i32,
+}
+
+/// Warning for a task completed while the given open tasks still block it
+pub fn blocked_completion_warning(blocker_ids: &[String]) -> String {
+    format!(
+        "Completed while blocked by open tasks: {}",
+        blocker_ids.join(", ")
+    )
+}
+
+/// Columns selected for a `Task`. Queries alias `tasks` as `t`.
+pub const TASK_COLUMNS: &str = "t.id, t.project_id, t.author_id, t.assignee_id, t.series_id, t.occurrence_date, t.section_id, t.status_id, t.parent_task_id, \
+     t.title, t.description, t.status, t.priority, t.estimate_minutes, t.scheduled_date, t.scheduled_time_minutes, t.deadline_date, \
+     t.deadline_time_minutes, t.completed_at, t.completed_by, t.abandoned_at, t.abandoned_by, \
+     t.deleted_at, t.deleted_by, t.snooze_count, t.last_snoozed_at, t.position, t.created_at, t.updated_at";
+
+impl TaskStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Todo => "todo",
+            TaskStatus::Done => "done",
+            TaskStatus::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "done" => TaskStatus::Done,
+            "abandoned" => TaskStatus::Abandoned,
+            _ => TaskStatus::Todo,
+        }
+    }
+}
+
+impl TaskPriority {
+    pub fn as_db(&self) -> i32 {
+        match self {
+            TaskPriority::None => 0,
+            TaskPriority::Low => 1,
+            TaskPriority::Medium => 2,
+            TaskPriority::High => 3,
+            TaskPriority::Urgent => 4,
+        }
+    }
+
+    pub fn from_db(value: i32) -> Self {
+        match value {
+            1 => TaskPriority::Low,
+            2 => TaskPriority::Medium,
+            3 => TaskPriority::High,
+            4 => TaskPriority::Urgent,
+            _ => TaskPriority::None,
+        }
+    }
+}
+
+/// A task's scheduled date and deadline, with their optional times
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct TaskTimes {
+    pub scheduled_date: Option<String>,
+    pub scheduled_time_minutes: Option<i32>,
+    pub deadline_date: Option<String>,
+    pub deadline_time_minutes: Option<i32>,
+}
+
+impl Task {
+    /// Build a `Task` from a row selected with `TASK_COLUMNS`, deriving
+    /// `isOverdue` and `bucket` in the caller's timezone
+    pub fn from_row(row: &SqliteRow, tz: Tz) -> Self {
+        let status: String = row.get("status");
+        let scheduled_date: Option<String> = row.get("scheduled_date");
+        let scheduled_time_minutes: Option<i32> = row.get("scheduled_time_minutes");
+        let deadline_date: Option<String> = row.get("deadline_date");
+        let deadline_time_minutes: Option<i32> = row.get("deadline_time_minutes");
+
+        let is_overdue = time_utils::is_task_overdue(
+            scheduled_date.as_deref(),
+            scheduled_time_minutes,
+            deadline_date.as_deref(),
+            deadline_time_minutes,
+            tz,
+        );
+        let bucket = time_utils::get_task_bucket(
+            scheduled_date.as_deref(),
+            scheduled_time_minutes,
+            deadline_date.as_deref(),
+            deadline_time_minutes,
+            tz,
+        );
+
+        Task {
+            id: row.get("id"),
+            project_id: row.get("project_id"),
+            author_id: row.get("author_id"),
+            assignee_id: row.get("assignee_id"),
+            series_id: row.get("series_id"),
+            occurrence_date: row.get("occurrence_date"),
+            section_id: row.get("section_id"),
+            status_id: row.get("status_id"),
+            parent_task_id: row.get("parent_task_id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            status: TaskStatus::from_db(&status),
+            priority: TaskPriority::from_db(row.get("priority")),
+            estimate_minutes: row.get("estimate_minutes"),
+            scheduled_date,
+            scheduled_time_minutes,
+            deadline_date,
+            deadline_time_minutes,
+            completed_at: row.get("completed_at"),
+            completed_by: row.get("completed_by"),
+            abandoned_at: row.get("abandoned_at"),
+            abandoned_by: row.get("abandoned_by"),
+            deleted_at: row.get("deleted_at"),
+            deleted_by: row.get("deleted_by"),
+            snooze_count: row.get("snooze_count"),
+            last_snoozed_at: row.get("last_snoozed_at"),
+            position: row.get("position"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            is_overdue,
+            bucket,
+            warnings: Vec::new(),
+            tz,
+        }
+    }
+}
+
+/// Fetch a single task by id
+pub async fn fetch_task(
+    pool: &sqlx::SqlitePool,
+    task_id: &str,
+    tz: Tz,
+) -> Result<Task, sqlx::Error> {
+    let sql = format!("SELECT {} FROM tasks t WHERE t.id = ?1", TASK_COLUMNS);
+    let row = sqlx::query(&sql).bind(task_id).fetch_one(pool).await?;
+    Ok(Task::from_row(&row, tz))
 }
 
 pub mod time_utils {
@@ -106,6 +345,48 @@ pub mod time_utils {
         }
     }
 
+    /// Like `combine_date_time`, but a time that falls in a DST gap resolves to the moment
+    /// the clocks skip to, so a task due at 02:30 on the night the clocks go forward is
+    /// still due that night
+    pub fn resolve_local_time(
+        date_str: &str,
+        time_minutes: Option<i32>,
+        tz: Tz,
+    ) -> Option<DateTime<Tz>> {
+        if let Some(dt) = combine_date_time(date_str, time_minutes, tz) {
+            return Some(dt);
+        }
+        // Gaps are at most an hour; the first valid minute after one is where it ends
+        let minutes = time_minutes.unwrap_or(0);
+        (1..=60).find_map(|step| {
+            let later = minutes + step;
+            if later >= 24 * 60 {
+                return None;
+            }
+            combine_date_time(date_str, Some(later), tz)
+        })
+    }
+
+    /// Format an instant the way timestamp columns store it
+    pub fn to_db(instant: DateTime<Utc>) -> String {
+        instant.format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+    }
+
+    /// Parse a timestamp column value as UTC
+    pub fn parse_db(value: &str) -> Option<DateTime<Utc>> {
+        chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()
+            .map(|naive| naive.and_utc())
+    }
+
+    /// Parse a client supplied instant: RFC 3339, or the timestamp column format as UTC
+    pub fn parse_instant(value: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value.trim())
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok()
+            .or_else(|| parse_db(value.trim()))
+    }
+
     /// Determine if a task is overdue based on scheduled/deadline dates and times
     pub fn is_task_overdue(
         scheduled_date: Option<&str>,
@@ -0,0 +1,134 @@
+//! Moving tasks between projects.
+//!
+//! A task moves together with its subtasks, including any in the trash, so a hierarchy never
+//! spans two projects. Anything that only makes sense inside the old project is dropped on
+//! the way: the section, the recurring series, assignees who aren't members of the new
+//! project, values of the old project's custom fields and dependencies on tasks that stay
+//! behind. Tags aren't scoped to a project, so
+//! they move unchanged.
+
+use sqlx::{SqliteConnection, SqlitePool};
+
+use super::SeriesMovePolicy;
+use super::ordering::append_position;
+use super::versions;
+
+/// Why a task can't be moved as it is, if anything stops it
+pub async fn move_refusal(
+    pool: &SqlitePool,
+    task_id: &str,
+    series_policy: SeriesMovePolicy,
+) -> Result<Option<&'static str>, sqlx::Error> {
+    let (parent_task_id, series_id) = sqlx::query_as::<_, (Option<String>, Option<String>)>(
+        "SELECT parent_task_id, series_id FROM tasks WHERE id = ?1",
+    )
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?;
+
+    if parent_task_id.is_some() {
+        return Ok(Some("Subtasks move with their parent task"));
+    }
+    if series_id.is_some() && series_policy == SeriesMovePolicy::Refuse {
+        return Ok(Some("Task belongs to a recurring series"));
+    }
+    Ok(None)
+}
+
+/// Bytes of attachments stored against a task and its subtasks
+pub async fn subtree_attachment_bytes(
+    pool: &SqlitePool,
+    task_id: &str,
+) -> Result<i64, sqlx::Error> {
+    let (bytes,): (i64,) = sqlx::query_as(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+         ) SELECT COALESCE(SUM(size_bytes), 0) FROM task_attachments \
+         WHERE task_id IN (SELECT id FROM descendants)",
+    )
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(bytes)
+}
+
+/// Move a task and everything below it into `target_project_id`
+pub async fn move_subtree(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    target_project_id: &str,
+) -> Result<(), sqlx::Error> {
+    let ids = sqlx::query_as::<_, (String,)>(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+         ) SELECT id FROM descendants",
+    )
+    .bind(task_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|r| r.0)
+    .collect::<Vec<_>>();
+    let placeholders = vec!["?"; ids.len()].join(",");
+
+    let sql = format!(
+        "UPDATE tasks SET project_id = ?, section_id = NULL, series_id = NULL, position = NULL, \
+         assignee_id = CASE WHEN assignee_id IN ( \
+             SELECT owner_id FROM projects WHERE id = ? \
+             UNION SELECT user_id FROM project_members WHERE project_id = ? \
+         ) THEN assignee_id END \
+         WHERE id IN ({})",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for _ in 0..3 {
+        query = query.bind(target_project_id);
+    }
+    for id in &ids {
+        query = query.bind(id);
+    }
+    query.execute(&mut *conn).await?;
+
+    // The moved tasks join the end of the new project's manual order
+    for id in &ids {
+        let position = append_position(&mut *conn, target_project_id).await?;
+        sqlx::query("UPDATE tasks SET position = ?1 WHERE id = ?2")
+            .bind(&position)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    let sql = format!(
+        "DELETE FROM task_custom_values WHERE task_id IN ({}) AND field_id NOT IN ( \
+             SELECT id FROM custom_fields WHERE project_id = ? \
+         )",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for id in &ids {
+        query = query.bind(id);
+    }
+    query.bind(target_project_id).execute(&mut *conn).await?;
+
+    let sql = format!(
+        "DELETE FROM task_dependencies WHERE (task_id IN ({0})) <> (blocked_by_task_id IN ({0}))",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for _ in 0..2 {
+        for id in &ids {
+            query = query.bind(id);
+        }
+    }
+    query.execute(&mut *conn).await?;
+
+    for id in &ids {
+        versions::save_current(conn, id).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! Manual ordering of tasks within a project.
+//!
+//! Every task carries a base62 rank, and tasks sort by comparing ranks as plain text. A
+//! moved task gets a fresh rank between its new neighbours, so the rest of the list is never
+//! rewritten. Ranks don't end in the lowest digit, which always leaves room before one.
+
+use sqlx::SqliteConnection;
+
+use super::versions;
+
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Manual order for a query over `tasks t`; tasks without a rank follow, oldest first
+pub const MANUAL_ORDER: &str =
+    "CASE WHEN t.position IS NULL THEN 1 ELSE 0 END, t.position ASC, t.created_at ASC, t.id ASC";
+
+/// Which side of the anchor task a task is moved to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    Before,
+    After,
+}
+
+fn digit(c: u8) -> usize {
+    DIGITS.iter().position(|&d| d == c).unwrap_or(0)
+}
+
+fn is_valid(rank: &str) -> bool {
+    !rank.is_empty() && rank.bytes().all(|c| DIGITS.contains(&c)) && !rank.ends_with('0')
+}
+
+/// Shortest rank between `lower` and `upper`, reading a missing digit of `lower` as zero
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> Vec<u8> {
+    if let Some(upper) = upper {
+        let shared = upper
+            .iter()
+            .enumerate()
+            .take_while(|&(i, &c)| lower.get(i).copied().unwrap_or(b'0') == c)
+            .count();
+        if shared > 0 {
+            let mut rank = upper[..shared].to_vec();
+            rank.extend(midpoint(
+                lower.get(shared..).unwrap_or_default(),
+                Some(&upper[shared..]),
+            ));
+            return rank;
+        }
+    }
+
+    let low = lower.first().map_or(0, |&c| digit(c));
+    let high = upper.map_or(DIGITS.len(), |u| digit(u[0]));
+    if high - low > 1 {
+        vec![DIGITS[(low + high).div_ceil(2)]]
+    } else if let Some(upper) = upper.filter(|u| u.len() > 1) {
+        vec![upper[0]]
+    } else {
+        let mut rank = vec![DIGITS[low]];
+        rank.extend(midpoint(lower.get(1..).unwrap_or_default(), None));
+        rank
+    }
+}
+
+/// A rank after `rank`, growing by a digit only once every position is at the top, so
+/// appending over and over keeps ranks short
+fn after(rank: &[u8]) -> Vec<u8> {
+    match rank.iter().position(|&c| c != b'z') {
+        Some(i) => {
+            let mut next = rank[..i].to_vec();
+            next.push(DIGITS[digit(rank[i]) + 1]);
+            next
+        }
+        None => {
+            let mut next = rank.to_vec();
+            next.push(DIGITS[1]);
+            next
+        }
+    }
+}
+
+/// A rank strictly between two others, where a missing bound leaves that end open. `None`
+/// if a bound isn't a valid rank or the bounds are out of order.
+pub fn rank_between(lower: Option<&str>, upper: Option<&str>) -> Option<String> {
+    if lower.is_some_and(|r| !is_valid(r)) || upper.is_some_and(|r| !is_valid(r)) {
+        return None;
+    }
+    let rank = match (lower, upper) {
+        (Some(lower), Some(upper)) if lower >= upper => return None,
+        (Some(lower), None) => after(lower.as_bytes()),
+        (lower, upper) => midpoint(
+            lower.unwrap_or_default().as_bytes(),
+            upper.map(str::as_bytes),
+        ),
+    };
+    String::from_utf8(rank).ok()
+}
+
+fn invalid_rank() -> sqlx::Error {
+    sqlx::Error::Protocol("task position is not a valid rank".to_string())
+}
+
+/// Rank that puts a new task at the end of a project's manual order
+pub async fn append_position(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+) -> Result<String, sqlx::Error> {
+    let (last,) = sqlx::query_as::<_, (Option<String>,)>(
+        "SELECT MAX(position) FROM tasks WHERE project_id = ?1",
+    )
+    .bind(project_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    rank_between(last.as_deref(), None).ok_or_else(invalid_rank)
+}
+
+/// Rank the unranked tasks of a project, oldest first, up to and including `through_id`,
+/// so they keep their place when a task is moved next to one of them
+async fn rank_unpositioned(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+    through_id: &str,
+) -> Result<(), sqlx::Error> {
+    let sql = format!(
+        "SELECT t.id FROM tasks t WHERE t.project_id = ?1 AND t.position IS NULL ORDER BY {}",
+        MANUAL_ORDER
+    );
+    let unranked = sqlx::query_as::<_, (String,)>(&sql)
+        .bind(project_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    for (id,) in unranked {
+        let position = append_position(conn, project_id).await?;
+        sqlx::query("UPDATE tasks SET position = ?1 WHERE id = ?2")
+            .bind(&position)
+            .bind(&id)
+            .execute(&mut *conn)
+            .await?;
+        versions::save_current(conn, &id).await?;
+        if id == through_id {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Move a task directly before or after another task in the same project
+pub async fn place(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    anchor_id: &str,
+    placement: Placement,
+) -> Result<(), sqlx::Error> {
+    let (project_id, anchor) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT project_id, position FROM tasks WHERE id = ?1",
+    )
+    .bind(anchor_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    let anchor = match anchor {
+        Some(anchor) => anchor,
+        None => {
+            rank_unpositioned(conn, &project_id, anchor_id).await?;
+            sqlx::query_as::<_, (String,)>("SELECT position FROM tasks WHERE id = ?1")
+                .bind(anchor_id)
+                .fetch_one(&mut *conn)
+                .await?
+                .0
+        }
+    };
+
+    // The neighbour on the other side of the anchor, leaving out the task being moved
+    let neighbour_sql = match placement {
+        Placement::Before => {
+            "SELECT MAX(position) FROM tasks WHERE project_id = ?1 AND id != ?2 AND position < ?3"
+        }
+        Placement::After => {
+            "SELECT MIN(position) FROM tasks WHERE project_id = ?1 AND id != ?2 AND position > ?3"
+        }
+    };
+    let (neighbour,) = sqlx::query_as::<_, (Option<String>,)>(neighbour_sql)
+        .bind(&project_id)
+        .bind(task_id)
+        .bind(&anchor)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let position = match placement {
+        Placement::Before => rank_between(neighbour.as_deref(), Some(&anchor)),
+        Placement::After => rank_between(Some(&anchor), neighbour.as_deref()),
+    }
+    .ok_or_else(invalid_rank)?;
+
+    sqlx::query("UPDATE tasks SET position = ?1 WHERE id = ?2")
+        .bind(&position)
+        .bind(task_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_fall_between_their_bounds() {
+        let cases = [
+            (None, None),
+            (Some("V"), None),
+            (None, Some("V")),
+            (Some("000001V"), Some("000002V")),
+            (Some("1"), Some("2")),
+            (Some("1"), Some("11")),
+            (Some("y"), Some("z")),
+            (Some("zz"), None),
+            (None, Some("00001")),
+            (Some("a"), Some("a1")),
+        ];
+        for (lower, upper) in cases {
+            let rank = rank_between(lower, upper).unwrap();
+            assert!(is_valid(&rank), "{rank}");
+            assert!(
+                lower.is_none_or(|l| l < rank.as_str()),
+                "{lower:?} < {rank}"
+            );
+            assert!(
+                upper.is_none_or(|u| rank.as_str() < u),
+                "{rank} < {upper:?}"
+            );
+        }
+
+        assert_eq!(rank_between(Some("2"), Some("1")), None);
+        assert_eq!(rank_between(Some("1"), Some("1")), None);
+        assert_eq!(rank_between(Some("10"), None), None);
+        assert_eq!(rank_between(Some("a-b"), None), None);
+    }
+
+    #[test]
+    fn repeated_inserts_keep_ranks_short() {
+        // Appending at the end
+        let mut last = "000042V".to_string();
+        for _ in 0..200 {
+            let next = rank_between(Some(&last), None).unwrap();
+            assert!(next > last);
+            last = next;
+        }
+        assert!(last.len() <= 5, "{last}");
+
+        // Squeezing into the same gap, always right after the lower bound
+        let upper = "000002V".to_string();
+        let mut lower = "000001V".to_string();
+        for _ in 0..50 {
+            let next = rank_between(Some(&lower), Some(&upper)).unwrap();
+            assert!(lower < next && next < upper);
+            lower = next;
+        }
+        assert!(lower.len() < 20, "{lower}");
+    }
+}
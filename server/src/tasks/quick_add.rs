@@ -0,0 +1,485 @@
+//! Quick-add: a task typed as a single line of text.
+//!
+//! Besides the title, a line can carry a date and time ("tomorrow 7pm", "on fri at 9:30",
+//! "2025-04-01"), a recurrence ("every monday", "daily", "every 2 weeks"), tags (`#chores`),
+//! an assignee (`@rob`) and a priority (`!high`). Only the first date, time, recurrence,
+//! assignee and priority are picked up; anything after that stays part of the title.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Weekday};
+use sqlx::{SqliteConnection, SqlitePool};
+
+use super::TaskPriority;
+use crate::db::helpers::normalize_tag_name;
+
+/// What a quick-add line says, before names are looked up
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub date: Option<NaiveDate>,
+    pub time_minutes: Option<i32>,
+    pub priority: Option<TaskPriority>,
+    /// Normalized tag names, in the order they were typed
+    pub tags: Vec<String>,
+    /// Username, without the `@`
+    pub assignee: Option<String>,
+    pub recurrence: Option<Recurrence>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recurrence {
+    pub rrule: String,
+    /// Days a weekly rule falls on; empty when it isn't tied to weekdays
+    pub weekdays: Vec<Weekday>,
+}
+
+/// Parse a line against the user's local date
+pub fn parse(text: &str, today: NaiveDate) -> QuickAdd {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let lower: Vec<&str> = lower.iter().map(String::as_str).collect();
+
+    let mut parsed = QuickAdd::default();
+    let mut title = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        let rest = &lower[i..];
+
+        if let Some(tag) = words[i].strip_prefix('#') {
+            let name = normalize_tag_name(tag);
+            if !name.is_empty() {
+                if !parsed.tags.contains(&name) {
+                    parsed.tags.push(name);
+                }
+                i += 1;
+                continue;
+            }
+        }
+        if let Some(username) = words[i].strip_prefix('@') {
+            if !username.is_empty() && parsed.assignee.is_none() {
+                parsed.assignee = Some(username.to_string());
+                i += 1;
+                continue;
+            }
+        }
+        if let Some(priority) = rest[0].strip_prefix('!').and_then(priority) {
+            if parsed.priority.is_none() {
+                parsed.priority = Some(priority);
+                i += 1;
+                continue;
+            }
+        }
+        if parsed.recurrence.is_none() {
+            if let Some((recurrence, used)) = recurrence(rest) {
+                parsed.recurrence = Some(recurrence);
+                i += used;
+                continue;
+            }
+        }
+        if parsed.date.is_none() {
+            if let Some((date, used)) = date(rest, today) {
+                parsed.date = Some(date);
+                i += used;
+                continue;
+            }
+        }
+        if parsed.time_minutes.is_none() {
+            if let Some((minutes, used)) = time(rest) {
+                parsed.time_minutes = Some(minutes);
+                i += used;
+                continue;
+            }
+        }
+
+        title.push(words[i]);
+        i += 1;
+    }
+
+    // A time on its own is for today
+    if parsed.time_minutes.is_some() && parsed.date.is_none() && parsed.recurrence.is_none() {
+        parsed.date = Some(today);
+    }
+    parsed.title = title.join(" ");
+    parsed
+}
+
+fn priority(name: &str) -> Option<TaskPriority> {
+    match name {
+        "low" => Some(TaskPriority::Low),
+        "medium" => Some(TaskPriority::Medium),
+        "high" => Some(TaskPriority::High),
+        "urgent" => Some(TaskPriority::Urgent),
+        _ => None,
+    }
+}
+
+fn weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn byday(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// RRULE frequency for a unit such as "week" or "weeks"
+fn frequency(unit: &str) -> Option<&'static str> {
+    match unit.strip_suffix('s').unwrap_or(unit) {
+        "day" => Some("DAILY"),
+        "week" => Some("WEEKLY"),
+        "month" => Some("MONTHLY"),
+        "year" => Some("YEARLY"),
+        _ => None,
+    }
+}
+
+fn count(word: &str) -> Option<u32> {
+    if word.is_empty() || !word.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok().filter(|&n| (1..=999).contains(&n))
+}
+
+/// The first date on or after `from` that falls on `weekday`
+fn next_weekday(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(days as i64)
+}
+
+fn recurrence(words: &[&str]) -> Option<(Recurrence, usize)> {
+    let simple = |freq: &str| Recurrence {
+        rrule: format!("FREQ={}", freq),
+        weekdays: Vec::new(),
+    };
+    let on_days = |days: Vec<Weekday>| Recurrence {
+        rrule: format!(
+            "FREQ=WEEKLY;BYDAY={}",
+            days.iter().map(|d| byday(*d)).collect::<Vec<_>>().join(",")
+        ),
+        weekdays: days,
+    };
+
+    match words {
+        ["daily", ..] => Some((simple("DAILY"), 1)),
+        ["weekly", ..] => Some((simple("WEEKLY"), 1)),
+        ["monthly", ..] => Some((simple("MONTHLY"), 1)),
+        ["yearly" | "annually", ..] => Some((simple("YEARLY"), 1)),
+        ["every", "weekday", ..] => Some((
+            on_days(vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ]),
+            2,
+        )),
+        ["every", n, unit, ..] if count(n).is_some() && frequency(unit).is_some() => {
+            let recurrence = match count(n)? {
+                1 => simple(frequency(unit)?),
+                n => simple(&format!("{};INTERVAL={}", frequency(unit)?, n)),
+            };
+            Some((recurrence, 3))
+        }
+        ["every", word, ..] => {
+            if let Some(day) = weekday(word) {
+                Some((on_days(vec![day]), 2))
+            } else {
+                frequency(word)
+                    .filter(|_| !word.ends_with('s'))
+                    .map(|freq| (simple(freq), 2))
+            }
+        }
+        _ => None,
+    }
+}
+
+fn date(words: &[&str], today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    match words {
+        ["on", word, ..] if weekday(word).is_some() => {
+            weekday(word).map(|day| (next_weekday(today, day), 2))
+        }
+        ["on", rest @ ..] => date(rest, today).map(|(date, used)| (date, used + 1)),
+        ["today", ..] => Some((today, 1)),
+        ["tomorrow", ..] => Some((today + Duration::days(1), 1)),
+        ["next", "week", ..] => Some((next_weekday(today + Duration::days(1), Weekday::Mon), 2)),
+        ["next", "month", ..] => {
+            let next = today.with_day(1)?.checked_add_months(Months::new(1))?;
+            Some((next, 2))
+        }
+        ["next", word, ..] => {
+            weekday(word).map(|day| (next_weekday(today + Duration::days(1), day), 2))
+        }
+        ["in", n, unit, ..] => {
+            let n = count(n)?;
+            let date = match frequency(unit)? {
+                "DAILY" => today + Duration::days(n as i64),
+                "WEEKLY" => today + Duration::weeks(n as i64),
+                "MONTHLY" => today.checked_add_months(Months::new(n))?,
+                _ => today.checked_add_months(Months::new(n * 12))?,
+            };
+            Some((date, 3))
+        }
+        [word, ..] => {
+            // Short day names are ordinary words too ("sun cream"), so on their own only the
+            // full name counts
+            if let Some(day) = weekday(word).filter(|_| word.ends_with("day")) {
+                return Some((next_weekday(today, day), 1));
+            }
+            NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                .ok()
+                .map(|date| (date, 1))
+        }
+        [] => None,
+    }
+}
+
+/// Minutes after midnight for "7pm", "7:30am", "12am" or "19:00"
+fn clock(word: &str) -> Option<i32> {
+    let (body, pm) = if let Some(body) = word.strip_suffix("am") {
+        (body, Some(false))
+    } else if let Some(body) = word.strip_suffix("pm") {
+        (body, Some(true))
+    } else {
+        (word, None)
+    };
+    let digits = |s: &str| !s.is_empty() && s.len() <= 2 && s.bytes().all(|b| b.is_ascii_digit());
+
+    let (hour, minute) = match body.split_once(':') {
+        Some((hour, minute)) if digits(hour) && digits(minute) && minute.len() == 2 => {
+            (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?)
+        }
+        // A bare number is only a time with am or pm after it
+        None if digits(body) && pm.is_some() => (body.parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    if minute >= 60 {
+        return None;
+    }
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None if hour < 24 => hour,
+        None => return None,
+    };
+    Some((hour * 60 + minute) as i32)
+}
+
+fn time(words: &[&str]) -> Option<(i32, usize)> {
+    match words {
+        ["at", rest @ ..] => time(rest).map(|(minutes, used)| (minutes, used + 1)),
+        ["noon", ..] => Some((12 * 60, 1)),
+        [number, meridiem @ ("am" | "pm"), ..] => {
+            clock(&format!("{}{}", number, meridiem)).map(|minutes| (minutes, 2))
+        }
+        [word, ..] => clock(word).map(|minutes| (minutes, 1)),
+        [] => None,
+    }
+}
+
+/// The date a recurring quick-add starts on: the date it was given or today, moved on past
+/// a time that has already gone by and to the first day a weekly rule falls on
+pub fn series_start(
+    recurrence: &Recurrence,
+    date: Option<NaiveDate>,
+    time_minutes: Option<i32>,
+    now: NaiveDateTime,
+) -> NaiveDate {
+    let today = now.date();
+    let mut start = date.unwrap_or(today);
+    let now_minutes = (now.hour() * 60 + now.minute()) as i32;
+    if start == today && time_minutes.is_some_and(|minutes| minutes <= now_minutes) {
+        start += Duration::days(1);
+    }
+    if let Some(next) = recurrence
+        .weekdays
+        .iter()
+        .map(|day| next_weekday(start, *day))
+        .min()
+    {
+        start = next;
+    }
+    start
+}
+
+/// Id of the project member with this username, if there is one
+pub async fn find_member(
+    pool: &SqlitePool,
+    project_id: &str,
+    username: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let row = sqlx::query_as::<_, (String,)>(
+        "SELECT u.id FROM users u WHERE lower(u.username) = lower(?1) AND ( \
+             u.id IN (SELECT owner_id FROM projects WHERE id = ?2) \
+             OR u.id IN (SELECT user_id FROM project_members WHERE project_id = ?2))",
+    )
+    .bind(username)
+    .bind(project_id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.map(|r| r.0))
+}
+
+/// Ids of existing tags, by normalized name; `None` for a tag that doesn't exist yet
+pub async fn find_tags(
+    pool: &SqlitePool,
+    names: &[String],
+) -> Result<Vec<Option<String>>, sqlx::Error> {
+    let mut ids = Vec::with_capacity(names.len());
+    for name in names {
+        let row = sqlx::query_as::<_, (String,)>("SELECT id FROM tags WHERE name = ?1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+        ids.push(row.map(|r| r.0));
+    }
+    Ok(ids)
+}
+
+/// Ids of tags by normalized name, creating the ones that don't exist yet
+pub async fn ensure_tags(
+    conn: &mut SqliteConnection,
+    names: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut ids = Vec::with_capacity(names.len());
+    for name in names {
+        sqlx::query("INSERT OR IGNORE INTO tags (id, name) VALUES (?1, ?2)")
+            .bind(uuid::Uuid::new_v4().to_string())
+            .bind(name)
+            .execute(&mut *conn)
+            .await?;
+        let (id,) = sqlx::query_as::<_, (String,)>("SELECT id FROM tags WHERE name = ?1")
+            .bind(name)
+            .fetch_one(&mut *conn)
+            .await?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    // A Wednesday
+    const TODAY: &str = "2025-03-26";
+
+    #[test]
+    fn parses_everything_out_of_the_title() {
+        let parsed = parse(
+            "Take bins out tomorrow 7pm #chores @rob !high #Chores",
+            day(TODAY),
+        );
+        assert_eq!(
+            parsed,
+            QuickAdd {
+                title: "Take bins out".into(),
+                date: Some(day("2025-03-27")),
+                time_minutes: Some(19 * 60),
+                priority: Some(TaskPriority::High),
+                tags: vec!["chores".into()],
+                assignee: Some("rob".into()),
+                recurrence: None,
+            }
+        );
+
+        let parse = |text| parse(text, day(TODAY));
+        assert_eq!(
+            parse("Dentist on fri at 9:30am").date,
+            Some(day("2025-03-28"))
+        );
+        assert_eq!(
+            parse("Dentist on fri at 9:30am").time_minutes,
+            Some(9 * 60 + 30)
+        );
+        assert_eq!(parse("Standup wednesday").date, Some(day(TODAY)));
+        assert_eq!(parse("Buy sun cream").date, None);
+        assert_eq!(parse("Fix mon itor stand").title, "Fix mon itor stand");
+        assert_eq!(
+            parse("Standup next wednesday").date,
+            Some(day("2025-04-02"))
+        );
+        assert_eq!(parse("Plan next week").date, Some(day("2025-03-31")));
+        assert_eq!(parse("Pay rent next month").date, Some(day("2025-04-01")));
+        assert_eq!(parse("Renew in 2 weeks").date, Some(day("2025-04-09")));
+        assert_eq!(parse("Taxes 2025-04-30").date, Some(day("2025-04-30")));
+        assert_eq!(parse("Call 7 pm").time_minutes, Some(19 * 60));
+        assert_eq!(parse("Call 12am").time_minutes, Some(0));
+        assert_eq!(parse("Lunch at noon").time_minutes, Some(12 * 60));
+        // A time on its own is for today
+        assert_eq!(parse("Call at 17:45").date, Some(day(TODAY)));
+
+        // Only the first date counts, and things that aren't dates or times stay put
+        let parsed = parse("Buy 3 apples at the market today for tuesday");
+        assert_eq!(parsed.title, "Buy 3 apples at the market for tuesday");
+        assert_eq!(parsed.date, Some(day(TODAY)));
+        assert_eq!(parsed.time_minutes, None);
+        assert_eq!(
+            parse("Email bob@example.com !important").title,
+            "Email bob@example.com !important"
+        );
+    }
+
+    #[test]
+    fn parses_recurrences() {
+        let rrule = |text| parse(text, day(TODAY)).recurrence.map(|r| r.rrule);
+        assert_eq!(
+            rrule("Bins every monday").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO")
+        );
+        assert_eq!(rrule("Water plants daily").as_deref(), Some("FREQ=DAILY"));
+        assert_eq!(
+            rrule("Review every 2 weeks").as_deref(),
+            Some("FREQ=WEEKLY;INTERVAL=2")
+        );
+        assert_eq!(
+            rrule("Pay rent every month").as_deref(),
+            Some("FREQ=MONTHLY")
+        );
+        assert_eq!(
+            rrule("Commute every weekday").as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")
+        );
+        assert_eq!(rrule("Read every night"), None);
+
+        let bins = parse("Take bins out 7pm every monday", day(TODAY));
+        assert_eq!(bins.title, "Take bins out");
+        assert_eq!(bins.date, None);
+        let recurrence = bins.recurrence.unwrap();
+        let at = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+        // Starts on the first Monday
+        assert_eq!(
+            series_start(&recurrence, None, bins.time_minutes, at("2025-03-26 12:00")),
+            day("2025-03-31")
+        );
+        // On a Monday after 7pm, the next one
+        assert_eq!(
+            series_start(&recurrence, None, bins.time_minutes, at("2025-03-31 19:30")),
+            day("2025-04-07")
+        );
+        let daily = parse("Stretch daily 8am", day(TODAY)).recurrence.unwrap();
+        assert_eq!(
+            series_start(&daily, None, Some(8 * 60), at("2025-03-26 07:00")),
+            day(TODAY)
+        );
+    }
+}
@@ -0,0 +1,441 @@
+//! Task reminders and their delivery.
+//!
+//! A reminder fires at an absolute time, or a number of minutes before its task's scheduled
+//! time or deadline. Relative reminders keep the timezone they were set in, so the task's
+//! local date and time are turned into an instant with `time_utils` each time the scheduler
+//! looks, and rescheduling the task moves the reminder with it.
+//!
+//! `enqueue_due` copies reminders that have come due into `reminder_outbox`, once per
+//! reminder and fire time. `deliver_pending` hands outbox rows to a `ReminderSink` and only
+//! marks them sent after the sink succeeds, so a crash in between means a row is delivered
+//! again rather than lost.
+
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use super::TaskTimes;
+use super::time_utils::{self, parse_db, parse_instant, to_db};
+
+pub const REMINDER_COLUMNS: &str =
+    "id, task_id, created_by, kind, remind_at, offset_minutes, timezone, created_at";
+
+/// Longest a relative reminder may be set ahead, four weeks
+pub const MAX_OFFSET_MINUTES: i32 = 4 * 7 * 24 * 60;
+
+/// Reminders that came due while the server was down are still sent if they are at most
+/// this old
+const CATCH_UP: Duration = Duration::hours(24);
+
+/// Deliveries are given up on after this many failed attempts
+const MAX_ATTEMPTS: i64 = 5;
+
+/// Outbox rows handed to the sink per run
+const DELIVERY_BATCH: i64 = 100;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ReminderKind {
+    /// At `remindAt`
+    Absolute,
+    /// `offsetMinutes` before the scheduled date and time
+    BeforeScheduled,
+    /// `offsetMinutes` before the deadline
+    BeforeDeadline,
+}
+
+impl ReminderKind {
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::BeforeScheduled => "before_scheduled",
+            Self::BeforeDeadline => "before_deadline",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "before_scheduled" => Self::BeforeScheduled,
+            "before_deadline" => Self::BeforeDeadline,
+            _ => Self::Absolute,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct Reminder {
+    pub id: String,
+    pub kind: ReminderKind,
+    #[graphql(name = "remindAt")]
+    pub remind_at: Option<String>,
+    #[graphql(name = "offsetMinutes")]
+    pub offset_minutes: Option<i32>,
+    pub timezone: String,
+    #[graphql(name = "createdBy")]
+    pub created_by: String,
+    /// When the reminder fires for its task as it is now scheduled; unset when the task has
+    /// no date to count back from, and for series defaults
+    #[graphql(name = "fireAt")]
+    pub fire_at: Option<String>,
+}
+
+impl Reminder {
+    /// Build a `Reminder` from a row selected with `REMINDER_COLUMNS`
+    pub fn from_row(row: &SqliteRow, times: &TaskTimes) -> Self {
+        let kind = ReminderKind::from_db(row.get("kind"));
+        let remind_at: Option<String> = row.get("remind_at");
+        let offset_minutes: Option<i32> = row.get("offset_minutes");
+        let timezone: String = row.get("timezone");
+        let fire_at = fire_at(kind, remind_at.as_deref(), offset_minutes, &timezone, times);
+        Reminder {
+            id: row.get("id"),
+            kind,
+            remind_at,
+            offset_minutes,
+            timezone,
+            created_by: row.get("created_by"),
+            fire_at: fire_at.map(to_db),
+        }
+    }
+}
+
+/// The instant a reminder fires for a task with the given dates. A date without a time
+/// counts from the start of that day; a time skipped by a DST change counts from the
+/// moment the clocks skip to. Offsets are real elapsed minutes.
+pub fn fire_at(
+    kind: ReminderKind,
+    remind_at: Option<&str>,
+    offset_minutes: Option<i32>,
+    timezone: &str,
+    times: &TaskTimes,
+) -> Option<DateTime<Utc>> {
+    let (date, time) = match kind {
+        ReminderKind::Absolute => return remind_at.and_then(parse_db),
+        ReminderKind::BeforeScheduled => (&times.scheduled_date, times.scheduled_time_minutes),
+        ReminderKind::BeforeDeadline => (&times.deadline_date, times.deadline_time_minutes),
+    };
+    let tz: Tz = timezone.parse().ok()?;
+    let local = time_utils::resolve_local_time(date.as_deref()?, time, tz)?;
+    Some(local.with_timezone(&Utc) - Duration::minutes(offset_minutes.unwrap_or(0) as i64))
+}
+
+/// Check a reminder's settings, returning the `remind_at` and `offset_minutes` to store
+pub fn validate(
+    kind: ReminderKind,
+    remind_at: Option<&str>,
+    offset_minutes: Option<i32>,
+) -> Result<(Option<String>, Option<i32>), &'static str> {
+    match kind {
+        ReminderKind::Absolute => {
+            if offset_minutes.is_some() {
+                return Err("offsetMinutes only applies to relative reminders");
+            }
+            let instant = remind_at.ok_or("remindAt is required for absolute reminders")?;
+            let instant = parse_instant(instant).ok_or("Invalid remindAt")?;
+            if instant <= Utc::now() {
+                return Err("remindAt must be in the future");
+            }
+            Ok((Some(to_db(instant)), None))
+        }
+        ReminderKind::BeforeScheduled | ReminderKind::BeforeDeadline => {
+            if remind_at.is_some() {
+                return Err("remindAt only applies to absolute reminders");
+            }
+            let offset =
+                offset_minutes.ok_or("offsetMinutes is required for relative reminders")?;
+            if !(0..=MAX_OFFSET_MINUTES).contains(&offset) {
+                return Err("offsetMinutes must be between 0 and 40320");
+            }
+            Ok((None, Some(offset)))
+        }
+    }
+}
+
+/// Give a task generated by a series copies of the series' default reminders
+pub async fn copy_series_defaults(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    task_id: &str,
+) -> Result<(), sqlx::Error> {
+    let defaults = sqlx::query_as::<_, (String, String, i32, String)>(
+        "SELECT created_by, kind, offset_minutes, timezone FROM series_reminders \
+         WHERE series_id = ?1 ORDER BY created_at ASC, id ASC",
+    )
+    .bind(series_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    for (created_by, kind, offset_minutes, timezone) in defaults {
+        sqlx::query(
+            "INSERT INTO task_reminders (id, task_id, created_by, kind, offset_minutes, timezone) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(task_id)
+        .bind(created_by)
+        .bind(kind)
+        .bind(offset_minutes)
+        .bind(timezone)
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Put reminders on open tasks that are due by `now` into the outbox. Reminders that were
+/// set after their fire time had already passed are skipped. Returns the number of rows
+/// added.
+pub async fn enqueue_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT r.id, r.kind, r.remind_at, r.offset_minutes, r.timezone, r.created_at, r.task_id, \
+                COALESCE(t.assignee_id, r.created_by) AS recipient_id, \
+                t.scheduled_date, t.scheduled_time_minutes, t.deadline_date, t.deadline_time_minutes \
+         FROM task_reminders r INNER JOIN tasks t ON t.id = r.task_id \
+         WHERE t.status = 'todo' AND t.deleted_at IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut added = 0;
+    for row in rows {
+        let times = TaskTimes {
+            scheduled_date: row.get("scheduled_date"),
+            scheduled_time_minutes: row.get("scheduled_time_minutes"),
+            deadline_date: row.get("deadline_date"),
+            deadline_time_minutes: row.get("deadline_time_minutes"),
+        };
+        let remind_at: Option<String> = row.get("remind_at");
+        let timezone: String = row.get("timezone");
+        let Some(fire) = fire_at(
+            ReminderKind::from_db(row.get("kind")),
+            remind_at.as_deref(),
+            row.get("offset_minutes"),
+            &timezone,
+            &times,
+        ) else {
+            continue;
+        };
+
+        let created_at: String = row.get("created_at");
+        let set_too_late = parse_db(&created_at).is_some_and(|created| fire < created);
+        if fire > now || fire < now - CATCH_UP || set_too_late {
+            continue;
+        }
+
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO reminder_outbox (id, reminder_id, task_id, recipient_id, fire_at, next_attempt_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(row.get::<String, _>("id"))
+        .bind(row.get::<String, _>("task_id"))
+        .bind(row.get::<String, _>("recipient_id"))
+        .bind(to_db(fire))
+        .bind(to_db(now))
+        .execute(pool)
+        .await?;
+        added += result.rows_affected();
+    }
+    Ok(added)
+}
+
+/// A due reminder on its way to a user
+#[derive(Clone, Debug)]
+pub struct Delivery {
+    pub id: String,
+    pub reminder_id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub recipient_id: String,
+    pub fire_at: String,
+    pub attempts: i64,
+}
+
+/// Where reminders are delivered to
+pub trait ReminderSink {
+    fn deliver(&self, delivery: &Delivery) -> Result<(), String>;
+}
+
+/// Writes reminders to the server log
+pub struct LogSink;
+
+impl ReminderSink for LogSink {
+    fn deliver(&self, delivery: &Delivery) -> Result<(), String> {
+        tracing::info!(
+            "Reminder for user {}: task {} ({}) at {}",
+            delivery.recipient_id,
+            delivery.task_id,
+            delivery.task_title,
+            delivery.fire_at
+        );
+        Ok(())
+    }
+}
+
+/// Hand pending outbox rows to `sink`. Failed deliveries are retried with a growing delay
+/// and given up on after `MAX_ATTEMPTS`; reminders whose task has since been closed or
+/// deleted are cancelled. Returns the number delivered.
+pub async fn deliver_pending(
+    pool: &SqlitePool,
+    sink: &impl ReminderSink,
+    now: DateTime<Utc>,
+) -> Result<u64, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT o.id, o.reminder_id, o.task_id, o.recipient_id, o.fire_at, o.attempts, \
+                t.title, t.status, t.deleted_at \
+         FROM reminder_outbox o INNER JOIN tasks t ON t.id = o.task_id \
+         WHERE o.status = 'pending' AND o.next_attempt_at <= ?1 \
+         ORDER BY o.fire_at ASC LIMIT ?2",
+    )
+    .bind(to_db(now))
+    .bind(DELIVERY_BATCH)
+    .fetch_all(pool)
+    .await?;
+
+    let mut delivered = 0;
+    for row in rows {
+        let id: String = row.get("id");
+        let status: String = row.get("status");
+        let deleted_at: Option<String> = row.get("deleted_at");
+        if status != "todo" || deleted_at.is_some() {
+            sqlx::query("UPDATE reminder_outbox SET status = 'cancelled' WHERE id = ?1")
+                .bind(&id)
+                .execute(pool)
+                .await?;
+            continue;
+        }
+
+        let delivery = Delivery {
+            id: id.clone(),
+            reminder_id: row.get("reminder_id"),
+            task_id: row.get("task_id"),
+            task_title: row.get("title"),
+            recipient_id: row.get("recipient_id"),
+            fire_at: row.get("fire_at"),
+            attempts: row.get("attempts"),
+        };
+        match sink.deliver(&delivery) {
+            Ok(()) => {
+                sqlx::query(
+                    "UPDATE reminder_outbox SET status = 'sent', sent_at = ?1, attempts = attempts + 1 \
+                     WHERE id = ?2",
+                )
+                .bind(to_db(now))
+                .bind(&id)
+                .execute(pool)
+                .await?;
+                delivered += 1;
+            }
+            Err(error) => {
+                let attempts = delivery.attempts + 1;
+                let status = if attempts >= MAX_ATTEMPTS {
+                    "failed"
+                } else {
+                    "pending"
+                };
+                let retry_at = now + Duration::minutes(attempts * attempts);
+                sqlx::query(
+                    "UPDATE reminder_outbox SET status = ?1, attempts = ?2, last_error = ?3, \
+                     next_attempt_at = ?4 WHERE id = ?5",
+                )
+                .bind(status)
+                .bind(attempts)
+                .bind(&error)
+                .bind(to_db(retry_at))
+                .bind(&id)
+                .execute(pool)
+                .await?;
+            }
+        }
+    }
+    Ok(delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_instant(value).unwrap()
+    }
+
+    fn scheduled(date: &str, minutes: Option<i32>) -> TaskTimes {
+        TaskTimes {
+            scheduled_date: Some(date.to_string()),
+            scheduled_time_minutes: minutes,
+            ..TaskTimes::default()
+        }
+    }
+
+    #[test]
+    fn relative_reminders_follow_local_time_across_dst() {
+        let before = |times: &TaskTimes, offset: i32| {
+            fire_at(
+                ReminderKind::BeforeScheduled,
+                None,
+                Some(offset),
+                "Europe/Amsterdam",
+                times,
+            )
+        };
+
+        // 09:00 local is 08:00 UTC in winter and 07:00 UTC in summer
+        assert_eq!(
+            before(&scheduled("2025-03-29", Some(9 * 60)), 30),
+            Some(at("2025-03-29T07:30:00Z"))
+        );
+        assert_eq!(
+            before(&scheduled("2025-03-30", Some(9 * 60)), 30),
+            Some(at("2025-03-30T06:30:00Z"))
+        );
+
+        // 02:30 doesn't exist on the 30th; it counts from 03:00 CEST, when the clocks skip to
+        assert_eq!(
+            before(&scheduled("2025-03-30", Some(2 * 60 + 30)), 0),
+            Some(at("2025-03-30T01:00:00Z"))
+        );
+
+        // 02:30 happens twice on 26 October; the first one counts, and the offset is real
+        // time, so an hour before it is 01:30 CEST
+        assert_eq!(
+            before(&scheduled("2025-10-26", Some(2 * 60 + 30)), 60),
+            Some(at("2025-10-25T23:30:00Z"))
+        );
+
+        // An all-day task counts from midnight
+        assert_eq!(
+            before(&scheduled("2025-07-01", None), 60),
+            Some(at("2025-06-30T21:00:00Z"))
+        );
+
+        // Nothing to count back from
+        assert_eq!(before(&TaskTimes::default(), 60), None);
+    }
+
+    #[test]
+    fn validate_checks_kind_specific_fields() {
+        assert_eq!(
+            validate(ReminderKind::BeforeDeadline, None, Some(15)),
+            Ok((None, Some(15)))
+        );
+        assert!(validate(ReminderKind::BeforeDeadline, None, None).is_err());
+        assert!(validate(ReminderKind::BeforeDeadline, None, Some(-5)).is_err());
+        assert!(validate(ReminderKind::Absolute, Some("2000-01-01T00:00:00Z"), None).is_err());
+        assert!(
+            validate(
+                ReminderKind::Absolute,
+                Some("2999-01-01T00:00:00Z"),
+                Some(5)
+            )
+            .is_err()
+        );
+        assert_eq!(
+            validate(
+                ReminderKind::Absolute,
+                Some("2999-01-01T09:00:00+01:00"),
+                None
+            ),
+            Ok((Some("2999-01-01 08:00:00.000".to_string()), None))
+        );
+    }
+}
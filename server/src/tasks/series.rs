@@ -0,0 +1,976 @@
+//! Materializing recurring series into tasks.
+//!
+//! A series is kept a rolling horizon ahead: its next `SERIES_HORIZON_OCCURRENCES`
+//! occurrences, and every occurrence in the next `SERIES_HORIZON_DAYS` days, exist as tasks.
+//! Each generated task records its `occurrence_date`, which is unique per series, and the
+//! series remembers the latest occurrence it has generated. Running the materializer again is
+//! a no-op, and a generated task that was deleted or moved away is not brought back.
+//!
+//! A series can instead repeat a number of days after its last occurrence was done. It then
+//! has one open task at a time, and `advance_after_completion` creates the next one when
+//! that task is closed.
+//!
+//! A paused series generates nothing until it's resumed, and an ended one stops at the UNTIL
+//! written into its rule.
+
+use async_graphql::Enum;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+use rrule::{RRule, RRuleSet, Unvalidated};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use super::activity::{self, ActivityAction};
+use super::ordering::append_position;
+use super::{reminders, time_utils, trash};
+use crate::config;
+
+/// How a series decides when its next occurrence is
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum SeriesMode {
+    /// On the dates of its RRULE, whenever earlier ones were done
+    #[default]
+    Calendar,
+    /// `intervalDays` after the previous occurrence was done
+    AfterCompletion,
+}
+
+impl SeriesMode {
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Self::Calendar => "calendar",
+            Self::AfterCompletion => "after_completion",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "after_completion" => Self::AfterCompletion,
+            _ => Self::Calendar,
+        }
+    }
+}
+
+/// Which occurrences an edit to a series applies to
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SeriesEditScope {
+    /// Only the given occurrence's task
+    This,
+    /// The given occurrence and every one after it; the series is split there
+    ThisAndFollowing,
+    /// The whole series; tasks up to today are left as they are
+    All,
+}
+
+/// The occurrences of a series, iterated in local wall-clock time of its timezone so they
+/// keep their time across DST changes. Date-only series start at midnight.
+pub fn rule_set(
+    rrule: &str,
+    dtstart_date: &str,
+    dtstart_time_minutes: Option<i32>,
+    timezone: &str,
+) -> Result<RRuleSet, String> {
+    let tz: Tz = timezone
+        .parse()
+        .map_err(|_| "Invalid timezone".to_string())?;
+    NaiveDate::parse_from_str(dtstart_date, "%Y-%m-%d")
+        .map_err(|_| "Invalid dtstartDate format, expected YYYY-MM-DD".to_string())?;
+    let minutes = dtstart_time_minutes.unwrap_or(0);
+    NaiveTime::from_hms_opt((minutes / 60) as u32, (minutes % 60) as u32, 0)
+        .ok_or_else(|| "Invalid dtstartTimeMinutes".to_string())?;
+    // A start in a DST gap moves to where the gap ends; in an overlap it's the earlier one
+    let start = time_utils::resolve_local_time(dtstart_date, Some(minutes), tz)
+        .ok_or_else(|| "Invalid datetime in series timezone".to_string())?;
+
+    // Built from the resolved instant, since a local DTSTART in an overlap wouldn't parse
+    rrule
+        .parse::<RRule<Unvalidated>>()
+        .and_then(|rule| rule.build(start.with_timezone(&rrule::Tz::Tz(tz))))
+        .map_err(|_| "Invalid RRULE/DTSTART combination".to_string())
+}
+
+/// The fields of a series that generated tasks are made from
+struct SeriesRow {
+    id: String,
+    project_id: String,
+    created_by: String,
+    title: String,
+    description: Option<String>,
+    assignee_id: Option<String>,
+    priority: i32,
+    estimate_minutes: Option<i32>,
+    rrule: String,
+    dtstart_date: String,
+    dtstart_time_minutes: Option<i32>,
+    deadline_offset_minutes: i32,
+    timezone: String,
+    mode: SeriesMode,
+    interval_days: Option<i32>,
+    advance_on_abandon: bool,
+    paused: bool,
+    materialized_through: Option<NaiveDate>,
+}
+
+async fn load(conn: &mut SqliteConnection, series_id: &str) -> Result<SeriesRow, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT id, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, rrule, \
+         dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, mode, interval_days, \
+         advance_on_abandon, paused_at, materialized_through \
+         FROM recurring_series WHERE id = ?1",
+    )
+    .bind(series_id)
+    .fetch_one(conn)
+    .await?;
+    Ok(SeriesRow {
+        id: row.get("id"),
+        project_id: row.get("project_id"),
+        created_by: row.get("created_by"),
+        title: row.get("title"),
+        description: row.get("description"),
+        assignee_id: row.get("assignee_id"),
+        priority: row.get("priority"),
+        estimate_minutes: row.get("estimate_minutes"),
+        rrule: row.get("rrule"),
+        dtstart_date: row.get("dtstart_date"),
+        dtstart_time_minutes: row.get("dtstart_time_minutes"),
+        deadline_offset_minutes: row.get("deadline_offset_minutes"),
+        timezone: row.get("timezone"),
+        mode: SeriesMode::from_db(row.get::<String, _>("mode").as_str()),
+        interval_days: row.get("interval_days"),
+        advance_on_abandon: row.get("advance_on_abandon"),
+        paused: row.get::<Option<String>, _>("paused_at").is_some(),
+        materialized_through: row
+            .get::<Option<String>, _>("materialized_through")
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+    })
+}
+
+/// Create the task for one occurrence, starting at `start`, with the series' tags and
+/// default reminders, and move the series' `materialized_through` up to it. Returns false if
+/// the occurrence already had a task.
+async fn insert_occurrence(
+    conn: &mut SqliteConnection,
+    series: &SeriesRow,
+    start: DateTime<Tz>,
+    tag_ids: &[(String,)],
+) -> Result<bool, sqlx::Error> {
+    let occurrence_date = start.date_naive().format("%Y-%m-%d").to_string();
+    let deadline = start + Duration::minutes(series.deadline_offset_minutes as i64);
+    let deadline_time_minutes = series
+        .dtstart_time_minutes
+        .map(|_| (deadline.hour() as i32) * 60 + (deadline.minute() as i32));
+
+    let task_id = uuid::Uuid::new_v4().to_string();
+    let position = append_position(&mut *conn, &series.project_id).await?;
+    let inserted = sqlx::query(
+        "INSERT INTO tasks (id, project_id, author_id, assignee_id, series_id, occurrence_date, title, description, status, priority, estimate_minutes, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, position) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'todo', ?9, ?10, ?6, ?11, ?12, ?13, ?14) \
+         ON CONFLICT(series_id, occurrence_date) DO NOTHING",
+    )
+    .bind(&task_id)
+    .bind(&series.project_id)
+    .bind(&series.created_by)
+    .bind(&series.assignee_id)
+    .bind(&series.id)
+    .bind(&occurrence_date)
+    .bind(&series.title)
+    .bind(&series.description)
+    .bind(series.priority)
+    .bind(series.estimate_minutes)
+    .bind(series.dtstart_time_minutes)
+    .bind(deadline.date_naive().format("%Y-%m-%d").to_string())
+    .bind(deadline_time_minutes)
+    .bind(&position)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    sqlx::query(
+        "UPDATE recurring_series SET materialized_through = ?1 \
+         WHERE id = ?2 AND (materialized_through IS NULL OR materialized_through < ?1)",
+    )
+    .bind(&occurrence_date)
+    .bind(&series.id)
+    .execute(&mut *conn)
+    .await?;
+    if inserted == 0 {
+        return Ok(false);
+    }
+
+    for (tag_id,) in tag_ids {
+        sqlx::query("INSERT OR IGNORE INTO task_tags (task_id, tag_id) VALUES (?1, ?2)")
+            .bind(&task_id)
+            .bind(tag_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    reminders::copy_series_defaults(&mut *conn, &series.id, &task_id).await?;
+    activity::record(
+        &mut *conn,
+        &task_id,
+        &series.created_by,
+        ActivityAction::Created,
+        &Default::default(),
+    )
+    .await?;
+    Ok(true)
+}
+
+async fn series_tags(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+) -> Result<Vec<(String,)>, sqlx::Error> {
+    sqlx::query_as::<_, (String,)>("SELECT tag_id FROM recurring_series_tags WHERE series_id = ?1")
+        .bind(series_id)
+        .fetch_all(conn)
+        .await
+}
+
+/// Create the tasks for a series' occurrences inside the horizon that haven't been generated
+/// yet. A series that repeats after completion only ever gets its first occurrence here.
+/// Returns the number of tasks created.
+pub async fn materialize(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    now: DateTime<Utc>,
+) -> Result<usize, sqlx::Error> {
+    let series = load(&mut *conn, series_id).await?;
+    let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+    if series.paused {
+        return Ok(0);
+    }
+    let tag_ids = series_tags(&mut *conn, series_id).await?;
+
+    if series.mode == SeriesMode::AfterCompletion {
+        if series.materialized_through.is_some() {
+            return Ok(0);
+        }
+        let Some(start) =
+            time_utils::resolve_local_time(&series.dtstart_date, series.dtstart_time_minutes, tz)
+        else {
+            return Ok(0);
+        };
+        return Ok(insert_occurrence(&mut *conn, &series, start, &tag_ids).await? as usize);
+    }
+
+    let set = match rule_set(
+        &series.rrule,
+        &series.dtstart_date,
+        series.dtstart_time_minutes,
+        &series.timezone,
+    ) {
+        Ok(set) => set,
+        Err(e) => {
+            tracing::warn!("Skipping series {}: {}", series_id, e);
+            return Ok(0);
+        }
+    };
+    let now_in_tz = now.with_timezone(&tz);
+    let horizon = now_in_tz.date_naive() + Duration::days(config::SERIES_HORIZON_DAYS);
+
+    let mut upcoming = 0usize;
+    let mut created = 0usize;
+    for occ in set.into_iter() {
+        let occ_dt = occ.with_timezone(&tz);
+        if occ_dt < now_in_tz {
+            continue;
+        }
+        let occurrence_date = occ_dt.date_naive();
+        if upcoming >= config::SERIES_HORIZON_OCCURRENCES && occurrence_date > horizon {
+            break;
+        }
+        upcoming += 1;
+        if series
+            .materialized_through
+            .is_some_and(|through| occurrence_date <= through)
+        {
+            continue;
+        }
+        if insert_occurrence(&mut *conn, &series, occ_dt, &tag_ids).await? {
+            created += 1;
+        }
+    }
+
+    Ok(created)
+}
+
+/// After a task of a series that repeats after completion is closed, create the next
+/// occurrence `interval_days` after the day it was closed, in the series' timezone.
+/// Abandoning only counts when the series says so, and nothing is created while the series
+/// still has an open task. Returns the id of the new task.
+pub async fn advance_after_completion(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let (series_id, status, completed_at, abandoned_at) =
+        sqlx::query_as::<_, (Option<String>, String, Option<String>, Option<String>)>(
+            "SELECT series_id, status, completed_at, abandoned_at FROM tasks WHERE id = ?1",
+        )
+        .bind(task_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let Some(series_id) = series_id else {
+        return Ok(None);
+    };
+    let series = load(&mut *conn, &series_id).await?;
+    if series.mode != SeriesMode::AfterCompletion || series.paused {
+        return Ok(None);
+    }
+    let closed_at = match status.as_str() {
+        "done" => completed_at,
+        "abandoned" if series.advance_on_abandon => abandoned_at,
+        _ => None,
+    };
+    let Some(closed_at) = closed_at.as_deref().and_then(time_utils::parse_db) else {
+        return Ok(None);
+    };
+
+    let (open,) = sqlx::query_as::<_, (i64,)>(
+        "SELECT COUNT(*) FROM tasks WHERE series_id = ?1 AND status = 'todo' AND deleted_at IS NULL",
+    )
+    .bind(&series_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    if open > 0 {
+        return Ok(None);
+    }
+
+    let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+    let next = closed_at.with_timezone(&tz).date_naive()
+        + Duration::days(series.interval_days.unwrap_or(1) as i64);
+    if until_date(&series.rrule, &series.timezone).is_some_and(|until| next > until) {
+        return Ok(None);
+    }
+    let Some(start) = time_utils::resolve_local_time(
+        &next.format("%Y-%m-%d").to_string(),
+        series.dtstart_time_minutes,
+        tz,
+    ) else {
+        return Ok(None);
+    };
+
+    let tag_ids = series_tags(&mut *conn, &series_id).await?;
+    if !insert_occurrence(&mut *conn, &series, start, &tag_ids).await? {
+        return Ok(None);
+    }
+    let (id,) = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM tasks WHERE series_id = ?1 AND occurrence_date = ?2",
+    )
+    .bind(&series_id)
+    .bind(next.format("%Y-%m-%d").to_string())
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(Some(id))
+}
+
+/// Drop the BYHOUR/BYMINUTE/BYSECOND parts of a date-only series' rule; it has no time of day
+/// for them to pick
+pub fn normalize_rrule(rrule: &str, timed: bool) -> String {
+    if timed {
+        return rrule.to_string();
+    }
+    rrule
+        .split(';')
+        .filter(|p| {
+            let up = p.to_ascii_uppercase();
+            !(up.starts_with("BYHOUR=")
+                || up.starts_with("BYMINUTE=")
+                || up.starts_with("BYSECOND="))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// `rrule` ending with the last occurrence on `until`, replacing any UNTIL or COUNT it had.
+/// UNTIL is the end of that day in the series' timezone, written in UTC as RFC 5545 asks for.
+pub fn with_until(rrule: &str, until: NaiveDate, timezone: &str) -> String {
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    let end_of_day = time_utils::resolve_local_time(
+        &(until + Duration::days(1)).format("%Y-%m-%d").to_string(),
+        Some(0),
+        tz,
+    )
+    .map(|start| start.with_timezone(&Utc) - Duration::seconds(1))
+    .unwrap_or_else(|| until.and_hms_opt(23, 59, 59).unwrap().and_utc());
+    let mut parts: Vec<String> = rrule
+        .split(';')
+        .filter(|p| {
+            let up = p.to_ascii_uppercase();
+            !(up.starts_with("UNTIL=") || up.starts_with("COUNT="))
+        })
+        .map(str::to_string)
+        .collect();
+    parts.push(format!("UNTIL={}", end_of_day.format("%Y%m%dT%H%M%SZ")));
+    parts.join(";")
+}
+
+/// The last date, in the series' timezone, a rule with an UNTIL has occurrences on
+pub fn until_date(rrule: &str, timezone: &str) -> Option<NaiveDate> {
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    let value = rrule
+        .split(';')
+        .find(|p| p.get(..6).is_some_and(|k| k.eq_ignore_ascii_case("UNTIL=")))?
+        .get(6..)?;
+    match value.strip_suffix('Z') {
+        Some(utc) => chrono::NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|dt| dt.and_utc().with_timezone(&tz).date_naive()),
+        None => NaiveDate::parse_from_str(&value[..value.len().min(8)], "%Y%m%d").ok(),
+    }
+}
+
+/// A rule limited by COUNT as one that ends on the day of its last occurrence instead, so a
+/// part of the series that starts later still stops where the whole one would have
+fn count_as_until(
+    rrule: &str,
+    dtstart_date: &str,
+    dtstart_time_minutes: Option<i32>,
+    timezone: &str,
+) -> String {
+    let counted = rrule
+        .split(';')
+        .any(|p| p.get(..6).is_some_and(|k| k.eq_ignore_ascii_case("COUNT=")));
+    if !counted {
+        return rrule.to_string();
+    }
+    let tz: Tz = timezone.parse().unwrap_or(Tz::UTC);
+    match rule_set(rrule, dtstart_date, dtstart_time_minutes, timezone)
+        .ok()
+        .and_then(|set| set.into_iter().last())
+    {
+        Some(last) => with_until(rrule, last.with_timezone(&tz).date_naive(), timezone),
+        None => rrule.to_string(),
+    }
+}
+
+/// Open generated tasks nobody has touched: no edits in their history other than being handed
+/// to the other half of a split series (reordering counts), and no comments, subtasks,
+/// dependencies, attachments or tracked time. Editing a series regenerates these
+/// and leaves every other task as an exception.
+const UNTOUCHED_SQL: &str = "t.status = 'todo' AND t.deleted_at IS NULL \
+     AND NOT EXISTS (SELECT 1 FROM task_activity a WHERE a.task_id = t.id AND a.action <> 'created' \
+         AND EXISTS (SELECT 1 FROM json_each(a.changes) c WHERE json_extract(c.value, '$.field') <> 'seriesId')) \
+     AND NOT EXISTS (SELECT 1 FROM task_comments c WHERE c.task_id = t.id) \
+     AND NOT EXISTS (SELECT 1 FROM tasks s WHERE s.parent_task_id = t.id) \
+     AND NOT EXISTS (SELECT 1 FROM task_dependencies d WHERE d.task_id = t.id OR d.blocked_by_task_id = t.id) \
+     AND NOT EXISTS (SELECT 1 FROM task_attachments f WHERE f.task_id = t.id) \
+     AND NOT EXISTS (SELECT 1 FROM time_entries e WHERE e.task_id = t.id)";
+
+/// Delete the series' untouched tasks after `date` so later runs can generate them again,
+/// returning their ids and occurrence dates
+async fn remove_untouched_after(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    date: &str,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let stale = sqlx::query_as::<_, (String, String)>(&format!(
+        "SELECT t.id, t.occurrence_date FROM tasks t \
+         WHERE t.series_id = ?1 AND t.occurrence_date > ?2 AND {}",
+        UNTOUCHED_SQL
+    ))
+    .bind(series_id)
+    .bind(date)
+    .fetch_all(&mut *conn)
+    .await?;
+    for (task_id, _) in &stale {
+        sqlx::query("DELETE FROM tasks WHERE id = ?1")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+    }
+    sqlx::query(
+        "UPDATE recurring_series SET materialized_through = ?1 \
+         WHERE id = ?2 AND materialized_through > ?1",
+    )
+    .bind(date)
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(stale)
+}
+
+/// Recreate a series' untouched tasks after today from how the series is now, after its
+/// fields or rule changed. Returns the number of tasks created.
+pub async fn regenerate(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    now: DateTime<Utc>,
+) -> Result<usize, sqlx::Error> {
+    let series = load(&mut *conn, series_id).await?;
+    let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+    let today = now
+        .with_timezone(&tz)
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let stale = remove_untouched_after(&mut *conn, series_id, &today).await?;
+
+    // The open task of an after-completion series keeps its date
+    let mut created = 0;
+    if series.mode == SeriesMode::AfterCompletion {
+        let tag_ids = series_tags(&mut *conn, series_id).await?;
+        for (_, occurrence_date) in &stale {
+            if let Some(start) =
+                time_utils::resolve_local_time(occurrence_date, series.dtstart_time_minutes, tz)
+                && insert_occurrence(&mut *conn, &series, start, &tag_ids).await?
+            {
+                created += 1;
+            }
+        }
+    }
+    Ok(created + materialize(&mut *conn, series_id, now).await?)
+}
+
+/// Split a series before `from`: the series ends the day before, and a copy of it starting
+/// on `from` takes over its tasks from then on, logged as `actor_id`'s change. Returns the id
+/// of the new series.
+pub async fn split(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    from: NaiveDate,
+    actor_id: &str,
+) -> Result<String, sqlx::Error> {
+    let (rrule, dtstart_date, dtstart_time_minutes, timezone) =
+        sqlx::query_as::<_, (String, String, Option<i32>, String)>(
+            "SELECT rrule, dtstart_date, dtstart_time_minutes, timezone FROM recurring_series WHERE id = ?1",
+        )
+        .bind(series_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let new_id = uuid::Uuid::new_v4().to_string();
+    let from_date = from.format("%Y-%m-%d").to_string();
+    sqlx::query(
+        "INSERT INTO recurring_series \
+         (id, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, rrule, dtstart_date, dtstart_time_minutes, \
+          deadline_offset_minutes, timezone, mode, interval_days, advance_on_abandon, paused_at) \
+         SELECT ?1, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, ?2, ?3, dtstart_time_minutes, \
+          deadline_offset_minutes, timezone, mode, interval_days, advance_on_abandon, paused_at \
+         FROM recurring_series WHERE id = ?4",
+    )
+    .bind(&new_id)
+    .bind(count_as_until(
+        &rrule,
+        &dtstart_date,
+        dtstart_time_minutes,
+        &timezone,
+    ))
+    .bind(&from_date)
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "INSERT INTO recurring_series_tags (series_id, tag_id) \
+         SELECT ?1, tag_id FROM recurring_series_tags WHERE series_id = ?2",
+    )
+    .bind(&new_id)
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "INSERT INTO series_reminders (id, series_id, created_by, kind, offset_minutes, timezone) \
+         SELECT lower(hex(randomblob(16))), ?1, created_by, kind, offset_minutes, timezone \
+         FROM series_reminders WHERE series_id = ?2",
+    )
+    .bind(&new_id)
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "UPDATE recurring_series SET rrule = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+    )
+    .bind(with_until(&rrule, from - Duration::days(1), &timezone))
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let moved = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM tasks WHERE series_id = ?1 AND occurrence_date >= ?2",
+    )
+    .bind(series_id)
+    .bind(&from_date)
+    .fetch_all(&mut *conn)
+    .await?;
+    for (task_id,) in &moved {
+        let before = activity::snapshot(&mut *conn, task_id).await?;
+        sqlx::query("UPDATE tasks SET series_id = ?1 WHERE id = ?2")
+            .bind(&new_id)
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+        activity::record(
+            &mut *conn,
+            task_id,
+            actor_id,
+            ActivityAction::Updated,
+            &before,
+        )
+        .await?;
+    }
+    sqlx::query(
+        "UPDATE recurring_series SET materialized_through = \
+         (SELECT MAX(occurrence_date) FROM tasks WHERE series_id = ?1) WHERE id = ?1",
+    )
+    .bind(&new_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(new_id)
+}
+
+/// Stop a series generating tasks until it's resumed. Its untouched tasks after today go, so
+/// the days it's paused for stay clear.
+pub async fn pause(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let series = load(&mut *conn, series_id).await?;
+    if series.paused {
+        return Ok(());
+    }
+    sqlx::query(
+        "UPDATE recurring_series SET paused_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+    )
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+
+    // An after-completion series keeps its one open task
+    if series.mode == SeriesMode::Calendar {
+        let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+        let today = now
+            .with_timezone(&tz)
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string();
+        remove_untouched_after(&mut *conn, series_id, &today).await?;
+    }
+    Ok(())
+}
+
+/// Let a paused series generate tasks again from `now` on. Occurrences that fell while it was
+/// paused aren't made up for; an after-completion series whose task was closed in the
+/// meantime is due today. Returns the number of tasks created.
+pub async fn resume(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    now: DateTime<Utc>,
+) -> Result<usize, sqlx::Error> {
+    sqlx::query(
+        "UPDATE recurring_series SET paused_at = NULL, updated_at = CURRENT_TIMESTAMP \
+         WHERE id = ?1 AND paused_at IS NOT NULL",
+    )
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+    let series = load(&mut *conn, series_id).await?;
+
+    if series.mode == SeriesMode::AfterCompletion && series.materialized_through.is_some() {
+        let (open,) = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM tasks WHERE series_id = ?1 AND status = 'todo' AND deleted_at IS NULL",
+        )
+        .bind(series_id)
+        .fetch_one(&mut *conn)
+        .await?;
+        let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+        let today = now.with_timezone(&tz).date_naive();
+        if open > 0 || until_date(&series.rrule, &series.timezone).is_some_and(|u| today > u) {
+            return Ok(0);
+        }
+        let Some(start) = time_utils::resolve_local_time(
+            &today.format("%Y-%m-%d").to_string(),
+            series.dtstart_time_minutes,
+            tz,
+        ) else {
+            return Ok(0);
+        };
+        let tag_ids = series_tags(&mut *conn, series_id).await?;
+        return Ok(insert_occurrence(&mut *conn, &series, start, &tag_ids).await? as usize);
+    }
+    materialize(&mut *conn, series_id, now).await
+}
+
+/// End a series after its occurrences on `until`. Its open tasks for later occurrences move
+/// to the trash; anything already done stays. Returns the number of tasks trashed.
+pub async fn end(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    until: NaiveDate,
+    user_id: &str,
+) -> Result<usize, sqlx::Error> {
+    let series = load(&mut *conn, series_id).await?;
+    sqlx::query(
+        "UPDATE recurring_series SET rrule = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+    )
+    .bind(with_until(&series.rrule, until, &series.timezone))
+    .bind(series_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let open = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM tasks WHERE series_id = ?1 AND occurrence_date > ?2 \
+         AND status = 'todo' AND deleted_at IS NULL",
+    )
+    .bind(series_id)
+    .bind(until.format("%Y-%m-%d").to_string())
+    .fetch_all(&mut *conn)
+    .await?;
+    for (task_id,) in &open {
+        trash::soft_delete(&mut *conn, task_id, user_id).await?;
+    }
+    Ok(open.len())
+}
+
+/// Delete a series. Its open tasks after today move to the trash, or with `purge_history`
+/// every task it generated does; tasks that stay are kept as ordinary tasks.
+pub async fn delete(
+    conn: &mut SqliteConnection,
+    series_id: &str,
+    purge_history: bool,
+    user_id: &str,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let series = load(&mut *conn, series_id).await?;
+    let tz: Tz = series.timezone.parse().unwrap_or(Tz::UTC);
+    let today = now
+        .with_timezone(&tz)
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let doomed = sqlx::query_as::<_, (String,)>(
+        "SELECT id FROM tasks WHERE series_id = ?1 AND deleted_at IS NULL \
+         AND (?2 OR (status = 'todo' AND occurrence_date > ?3))",
+    )
+    .bind(series_id)
+    .bind(purge_history)
+    .bind(&today)
+    .fetch_all(&mut *conn)
+    .await?;
+    for (task_id,) in &doomed {
+        trash::soft_delete(&mut *conn, task_id, user_id).await?;
+    }
+
+    let kept = sqlx::query_as::<_, (String,)>("SELECT id FROM tasks WHERE series_id = ?1")
+        .bind(series_id)
+        .fetch_all(&mut *conn)
+        .await?;
+    for (task_id,) in &kept {
+        let before = activity::snapshot(&mut *conn, task_id).await?;
+        sqlx::query("UPDATE tasks SET series_id = NULL WHERE id = ?1")
+            .bind(task_id)
+            .execute(&mut *conn)
+            .await?;
+        activity::record(
+            &mut *conn,
+            task_id,
+            user_id,
+            ActivityAction::Updated,
+            &before,
+        )
+        .await?;
+    }
+    sqlx::query("DELETE FROM recurring_series WHERE id = ?1")
+        .bind(series_id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+/// Top up every series in a project that isn't archived. Returns the number of tasks
+/// created.
+pub async fn materialize_all(pool: &SqlitePool, now: DateTime<Utc>) -> Result<usize, sqlx::Error> {
+    let series = sqlx::query_as::<_, (String,)>(
+        "SELECT rs.id FROM recurring_series rs INNER JOIN projects p ON p.id = rs.project_id \
+         WHERE p.archived_at IS NULL AND rs.mode = 'calendar' ORDER BY rs.created_at ASC, rs.id ASC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut created = 0;
+    for (series_id,) in series {
+        let mut tx = pool.begin().await?;
+        created += materialize(&mut tx, &series_id, now).await?;
+        tx.commit().await?;
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn rules_start_inside_dst_changes() {
+        let first = |date: &str| {
+            rule_set("FREQ=DAILY;COUNT=2", date, Some(150), "Europe/Amsterdam")
+                .unwrap()
+                .into_iter()
+                .map(|occ| occ.to_rfc3339())
+                .collect::<Vec<_>>()
+        };
+        // 02:30 doesn't exist on the day clocks go forward, so the first occurrence is at 03:00
+        assert_eq!(
+            first("2030-03-31"),
+            vec!["2030-03-31T03:00:00+02:00", "2030-04-01T03:00:00+02:00"]
+        );
+        // ...and happens twice on the day they go back, where the earlier one is taken
+        assert_eq!(
+            first("2030-10-27"),
+            vec!["2030-10-27T02:30:00+02:00", "2030-10-28T02:30:00+01:00"]
+        );
+    }
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password) VALUES ('user1', 'testuser', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone) \
+             VALUES ('s1', 'p1', 'user1', 'Water plants', 'FREQ=DAILY', '2030-03-01', 480, 60, 'Europe/Amsterdam')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        pool
+    }
+
+    async fn occurrences(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_as::<_, (String,)>(
+            "SELECT occurrence_date FROM tasks WHERE series_id = 's1' ORDER BY occurrence_date",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.0)
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn materializing_keeps_a_rolling_horizon() {
+        let pool = setup_test_db().await;
+        let now = Utc.with_ymd_and_hms(2030, 3, 1, 6, 0, 0).unwrap();
+
+        // Every occurrence through the horizon, and nothing more on a second run
+        let created = materialize_all(&pool, now).await.unwrap();
+        assert_eq!(created, config::SERIES_HORIZON_DAYS as usize + 1);
+        assert_eq!(materialize_all(&pool, now).await.unwrap(), 0);
+        let dates = occurrences(&pool).await;
+        assert_eq!(dates.first().unwrap(), "2030-03-01");
+        assert_eq!(dates.last().unwrap(), "2030-03-15");
+
+        // A deleted occurrence stays deleted, and the horizon rolls on with time
+        sqlx::query("DELETE FROM tasks WHERE occurrence_date = '2030-03-03'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let later = now + Duration::days(2);
+        assert_eq!(materialize_all(&pool, later).await.unwrap(), 2);
+        let dates = occurrences(&pool).await;
+        assert!(!dates.contains(&"2030-03-03".to_string()));
+        assert_eq!(dates.last().unwrap(), "2030-03-17");
+
+        let (time, deadline) = sqlx::query_as::<_, (i32, String)>(
+            "SELECT scheduled_time_minutes, deadline_date FROM tasks WHERE occurrence_date = '2030-03-17'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((time, deadline.as_str()), (480, "2030-03-17"));
+    }
+
+    #[tokio::test]
+    async fn completing_schedules_the_next_occurrence_after_the_interval() {
+        let pool = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO recurring_series (id, project_id, created_by, title, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, mode, interval_days) \
+             VALUES ('s2', 'p1', 'user1', 'Clean filter', 'FREQ=DAILY;INTERVAL=3', '2030-03-01', 480, 0, 'Europe/Amsterdam', 'after_completion', 3)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let conn = &mut *pool.acquire().await.unwrap();
+
+        // Only the first occurrence exists up front, and the horizon job leaves it alone
+        assert_eq!(
+            materialize(
+                conn,
+                "s2",
+                Utc.with_ymd_and_hms(2030, 3, 1, 6, 0, 0).unwrap()
+            )
+            .await
+            .unwrap(),
+            1
+        );
+        assert_eq!(
+            materialize(
+                conn,
+                "s2",
+                Utc.with_ymd_and_hms(2030, 3, 1, 6, 0, 0).unwrap()
+            )
+            .await
+            .unwrap(),
+            0
+        );
+        let (first,) =
+            sqlx::query_as::<_, (String,)>("SELECT id FROM tasks WHERE series_id = 's2'")
+                .fetch_one(&mut *conn)
+                .await
+                .unwrap();
+        assert_eq!(advance_after_completion(conn, &first).await.unwrap(), None);
+
+        // Done just after midnight in Amsterdam, so three days after March 3rd
+        sqlx::query(
+            "UPDATE tasks SET status = 'done', completed_at = '2030-03-02 23:30:00.000' WHERE id = ?1",
+        )
+        .bind(&first)
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        let next = advance_after_completion(conn, &first)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(advance_after_completion(conn, &first).await.unwrap(), None);
+        let (date, time) = sqlx::query_as::<_, (String, i32)>(
+            "SELECT scheduled_date, scheduled_time_minutes FROM tasks WHERE id = ?1",
+        )
+        .bind(&next)
+        .fetch_one(&mut *conn)
+        .await
+        .unwrap();
+        assert_eq!((date.as_str(), time), ("2030-03-06", 480));
+
+        // Abandoning doesn't count unless the series says so
+        sqlx::query(
+            "UPDATE tasks SET status = 'abandoned', abandoned_at = '2030-03-06 09:00:00.000' WHERE id = ?1",
+        )
+        .bind(&next)
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        assert_eq!(advance_after_completion(conn, &next).await.unwrap(), None);
+        sqlx::query("UPDATE recurring_series SET advance_on_abandon = 1 WHERE id = 's2'")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        assert!(
+            advance_after_completion(conn, &next)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+}
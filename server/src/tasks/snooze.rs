@@ -0,0 +1,280 @@
+//! Postponing tasks with presets.
+//!
+//! A preset is resolved against the current local time into a new date and time for the
+//! task's scheduled date, or its deadline when it only has one. When a task has both, the
+//! deadline moves with the scheduled date so the gap between them stays the same.
+
+use async_graphql::Enum;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+use super::TaskTimes;
+
+/// Time of day that the day-based presets snooze to
+pub const MORNING_MINUTES: i32 = 9 * 60;
+
+/// How far ahead `LATER_TODAY` looks
+const LATER_TODAY: Duration = Duration::hours(3);
+
+/// Longest custom snooze, a year
+pub const MAX_SNOOZE_MINUTES: i32 = 525600;
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SnoozePreset {
+    /// In three hours, rounded up to the half hour; tomorrow morning if that's past midnight
+    LaterToday,
+    TomorrowMorning,
+    /// Saturday morning, or Sunday if it's already Saturday
+    ThisWeekend,
+    /// Monday morning
+    NextWeek,
+    /// `durationMinutes` from now
+    Custom,
+}
+
+/// Where a preset snoozes to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnoozeTarget {
+    pub date: NaiveDate,
+    pub time_minutes: i32,
+    /// Whether the time matters; day-based presets leave all-day tasks all-day
+    pub timed: bool,
+}
+
+/// Resolve a preset against the current local date and time
+pub fn resolve(
+    preset: SnoozePreset,
+    now: NaiveDateTime,
+    duration_minutes: Option<i32>,
+) -> Result<SnoozeTarget, &'static str> {
+    if preset != SnoozePreset::Custom && duration_minutes.is_some() {
+        return Err("durationMinutes only applies to CUSTOM");
+    }
+
+    let today = now.date();
+    let morning = |date: NaiveDate| SnoozeTarget {
+        date,
+        time_minutes: MORNING_MINUTES,
+        timed: false,
+    };
+    let at = |moment: NaiveDateTime| SnoozeTarget {
+        date: moment.date(),
+        time_minutes: (moment.hour() * 60 + moment.minute()) as i32,
+        timed: true,
+    };
+
+    let target = match preset {
+        SnoozePreset::LaterToday => {
+            let later = now + LATER_TODAY;
+            let minutes = later.hour() * 60 + later.minute();
+            let rounded = later.date().and_time(NaiveTime::MIN)
+                + Duration::minutes((minutes.div_ceil(30) * 30) as i64);
+            if rounded.date() == today {
+                at(rounded)
+            } else {
+                SnoozeTarget {
+                    timed: true,
+                    ..morning(today + Duration::days(1))
+                }
+            }
+        }
+        SnoozePreset::TomorrowMorning => morning(today + Duration::days(1)),
+        SnoozePreset::ThisWeekend => {
+            let days = match today.weekday() {
+                Weekday::Sat => 1,
+                Weekday::Sun => 6,
+                weekday => 5 - weekday.num_days_from_monday() as i64,
+            };
+            morning(today + Duration::days(days))
+        }
+        SnoozePreset::NextWeek => {
+            morning(today + Duration::days(7 - today.weekday().num_days_from_monday() as i64))
+        }
+        SnoozePreset::Custom => {
+            let minutes = duration_minutes.ok_or("durationMinutes is required for CUSTOM")?;
+            if !(1..=MAX_SNOOZE_MINUTES).contains(&minutes) {
+                return Err("durationMinutes must be between 1 and 525600");
+            }
+            at(now + Duration::minutes(minutes as i64))
+        }
+    };
+    Ok(target)
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn local(date: NaiveDate, minutes: i32) -> NaiveDateTime {
+    date.and_time(NaiveTime::MIN) + Duration::minutes(minutes as i64)
+}
+
+/// The task's dates after snoozing to `target`. The scheduled date moves, or the deadline
+/// for a task that only has a deadline; a task without either gets a scheduled date. When
+/// a task has both, the deadline keeps its distance from the scheduled date: the exact
+/// local gap when both have times, otherwise the same number of days.
+pub fn apply(target: SnoozeTarget, times: &TaskTimes) -> TaskTimes {
+    let moves_deadline = times.scheduled_date.is_none() && times.deadline_date.is_some();
+    let anchor_time = if moves_deadline {
+        times.deadline_time_minutes
+    } else {
+        times.scheduled_time_minutes
+    };
+    let time = (target.timed || anchor_time.is_some()).then_some(target.time_minutes);
+
+    if moves_deadline {
+        return TaskTimes {
+            deadline_date: Some(format_date(target.date)),
+            deadline_time_minutes: time,
+            ..TaskTimes::default()
+        };
+    }
+
+    let mut snoozed = TaskTimes {
+        scheduled_date: Some(format_date(target.date)),
+        scheduled_time_minutes: time,
+        deadline_date: times.deadline_date.clone(),
+        deadline_time_minutes: times.deadline_time_minutes,
+    };
+    let old_scheduled = times.scheduled_date.as_deref().and_then(parse_date);
+    let old_deadline = times.deadline_date.as_deref().and_then(parse_date);
+    if let (Some(old_scheduled), Some(old_deadline)) = (old_scheduled, old_deadline) {
+        match (
+            times.scheduled_time_minutes,
+            times.deadline_time_minutes,
+            time,
+        ) {
+            (Some(scheduled_time), Some(deadline_time), Some(new_time)) => {
+                let gap = local(old_deadline, deadline_time) - local(old_scheduled, scheduled_time);
+                let deadline = local(target.date, new_time) + gap;
+                snoozed.deadline_date = Some(format_date(deadline.date()));
+                snoozed.deadline_time_minutes =
+                    Some((deadline.hour() * 60 + deadline.minute()) as i32);
+            }
+            _ => {
+                let days = target.date - old_scheduled;
+                snoozed.deadline_date = Some(format_date(old_deadline + days));
+            }
+        }
+    }
+    snoozed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn target(date: &str, time_minutes: i32, timed: bool) -> SnoozeTarget {
+        SnoozeTarget {
+            date: parse_date(date).unwrap(),
+            time_minutes,
+            timed,
+        }
+    }
+
+    #[test]
+    fn presets_resolve_from_local_time() {
+        // Wednesday afternoon
+        let wednesday = now("2025-03-26 14:10");
+        let resolve = |preset, duration| resolve(preset, wednesday, duration).unwrap();
+        assert_eq!(
+            resolve(SnoozePreset::LaterToday, None),
+            target("2025-03-26", 17 * 60 + 30, true)
+        );
+        assert_eq!(
+            resolve(SnoozePreset::TomorrowMorning, None),
+            target("2025-03-27", MORNING_MINUTES, false)
+        );
+        assert_eq!(
+            resolve(SnoozePreset::ThisWeekend, None),
+            target("2025-03-29", MORNING_MINUTES, false)
+        );
+        assert_eq!(
+            resolve(SnoozePreset::NextWeek, None),
+            target("2025-03-31", MORNING_MINUTES, false)
+        );
+        assert_eq!(
+            resolve(SnoozePreset::Custom, Some(24 * 60 + 5)),
+            target("2025-03-27", 14 * 60 + 15, true)
+        );
+
+        // Late in the evening, later today is tomorrow morning
+        assert_eq!(
+            super::resolve(SnoozePreset::LaterToday, now("2025-03-26 22:00"), None).unwrap(),
+            target("2025-03-27", MORNING_MINUTES, true)
+        );
+        // On Saturday the weekend continues on Sunday; on Sunday it's the next one
+        assert_eq!(
+            super::resolve(SnoozePreset::ThisWeekend, now("2025-03-29 10:00"), None)
+                .unwrap()
+                .date,
+            parse_date("2025-03-30").unwrap()
+        );
+        assert_eq!(
+            super::resolve(SnoozePreset::ThisWeekend, now("2025-03-30 10:00"), None)
+                .unwrap()
+                .date,
+            parse_date("2025-04-05").unwrap()
+        );
+        assert!(super::resolve(SnoozePreset::Custom, wednesday, None).is_err());
+        assert!(super::resolve(SnoozePreset::NextWeek, wednesday, Some(5)).is_err());
+    }
+
+    #[test]
+    fn snoozing_keeps_the_deadline_gap() {
+        // Timed: scheduled 16:00, due the next day at 10:00
+        let timed = TaskTimes {
+            scheduled_date: Some("2025-03-26".into()),
+            scheduled_time_minutes: Some(16 * 60),
+            deadline_date: Some("2025-03-27".into()),
+            deadline_time_minutes: Some(10 * 60),
+        };
+        assert_eq!(
+            apply(target("2025-03-29", MORNING_MINUTES, false), &timed),
+            TaskTimes {
+                scheduled_date: Some("2025-03-29".into()),
+                scheduled_time_minutes: Some(MORNING_MINUTES),
+                deadline_date: Some("2025-03-30".into()),
+                deadline_time_minutes: Some(3 * 60),
+            }
+        );
+
+        // All-day tasks stay all-day and keep their deadline days apart
+        let all_day = TaskTimes {
+            scheduled_date: Some("2025-03-26".into()),
+            deadline_date: Some("2025-03-28".into()),
+            deadline_time_minutes: Some(17 * 60),
+            ..TaskTimes::default()
+        };
+        assert_eq!(
+            apply(target("2025-03-31", MORNING_MINUTES, false), &all_day),
+            TaskTimes {
+                scheduled_date: Some("2025-03-31".into()),
+                scheduled_time_minutes: None,
+                deadline_date: Some("2025-04-02".into()),
+                deadline_time_minutes: Some(17 * 60),
+            }
+        );
+
+        // Only a deadline: the deadline itself is postponed
+        let deadline_only = TaskTimes {
+            deadline_date: Some("2025-03-26".into()),
+            ..TaskTimes::default()
+        };
+        assert_eq!(
+            apply(target("2025-03-26", 17 * 60 + 30, true), &deadline_only),
+            TaskTimes {
+                deadline_date: Some("2025-03-26".into()),
+                deadline_time_minutes: Some(17 * 60 + 30),
+                ..TaskTimes::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Task hierarchy helpers. A top-level task is level 1; its subtasks are level 2, and so on.
+
+use sqlx::SqlitePool;
+
+/// Deepest level a task may sit at
+pub const MAX_TASK_LEVELS: i64 = 3;
+
+/// Level of a task in its hierarchy (1 for a top-level task)
+pub async fn task_level(pool: &SqlitePool, task_id: &str) -> Result<i64, sqlx::Error> {
+    let level = sqlx::query_as::<_, (Option<i64>,)>(
+        "WITH RECURSIVE ancestors(id, parent_task_id, level) AS ( \
+             SELECT id, parent_task_id, 1 FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id, t.parent_task_id, a.level + 1 FROM tasks t \
+             INNER JOIN ancestors a ON t.id = a.parent_task_id \
+         ) SELECT MAX(level) FROM ancestors",
+    )
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?
+    .0;
+    Ok(level.unwrap_or(0))
+}
+
+/// Ids of a task and everything below it, leaving out subtasks in the trash
+pub async fn subtree_ids(pool: &SqlitePool, task_id: &str) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String,)>(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+             WHERE t.deleted_at IS NULL \
+         ) SELECT id FROM descendants",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+/// Number of levels in the subtree rooted at a task (1 for a task without subtasks)
+pub async fn subtree_height(pool: &SqlitePool, task_id: &str) -> Result<i64, sqlx::Error> {
+    let height = sqlx::query_as::<_, (Option<i64>,)>(
+        "WITH RECURSIVE descendants(id, depth) AS ( \
+             SELECT id, 1 FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id, d.depth + 1 FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+         ) SELECT MAX(depth) FROM descendants",
+    )
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?
+    .0;
+    Ok(height.unwrap_or(0))
+}
+
+/// Open tasks anywhere below a task
+pub async fn open_descendant_count(pool: &SqlitePool, task_id: &str) -> Result<i64, sqlx::Error> {
+    let count = sqlx::query_as::<_, (i64,)>(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE parent_task_id = ?1 AND deleted_at IS NULL \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+             WHERE t.deleted_at IS NULL \
+         ) SELECT COUNT(*) FROM tasks WHERE id IN (SELECT id FROM descendants) AND status = 'todo'",
+    )
+    .bind(task_id)
+    .fetch_one(pool)
+    .await?
+    .0;
+    Ok(count)
+}
+
+/// Ids of the open tasks anywhere below a task
+pub async fn open_descendant_ids(
+    pool: &SqlitePool,
+    task_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String,)>(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE parent_task_id = ?1 AND deleted_at IS NULL \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+             WHERE t.deleted_at IS NULL \
+         ) SELECT id FROM tasks WHERE id IN (SELECT id FROM descendants) AND status = 'todo'",
+    )
+    .bind(task_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+/// Whether a subtree of `height` levels fits under a parent at `parent_level`
+pub fn fits_under(parent_level: i64, height: i64) -> bool {
+    parent_level + height <= MAX_TASK_LEVELS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fits_under() {
+        // A leaf can go under a top-level task or a subtask, but not a sub-subtask
+        assert!(fits_under(1, 1));
+        assert!(fits_under(2, 1));
+        assert!(!fits_under(3, 1));
+
+        // A task with its own subtasks needs room for them too
+        assert!(fits_under(1, 2));
+        assert!(!fits_under(2, 2));
+    }
+}
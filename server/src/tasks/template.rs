@@ -0,0 +1,825 @@
+//! Project snapshots, used both to duplicate a project and to store reusable templates.
+//!
+//! A snapshot holds the settings, workflow statuses, custom fields, sections, open tasks and
+//! their dependencies, saved views, default view and recurring series of a project.
+//! Tags are global, so tag ids are copied as-is; tags deleted since the snapshot was taken are
+//! skipped when the snapshot is instantiated.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+use super::activity::{self, ActivityAction};
+use super::ordering::{MANUAL_ORDER, append_position};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSnapshot {
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub enforce_dependencies: bool,
+    /// Earliest task or series date; shifting moves this date onto a new anchor
+    pub anchor_date: Option<String>,
+    // Templates saved before sections existed have none
+    #[serde(default)]
+    pub sections: Vec<SnapshotSection>,
+    // Templates saved before workflows existed have none, and get the default workflow
+    #[serde(default)]
+    pub statuses: Vec<SnapshotStatus>,
+    #[serde(default)]
+    pub custom_fields: Vec<SnapshotCustomField>,
+    pub tasks: Vec<SnapshotTask>,
+    pub saved_views: Vec<SnapshotSavedView>,
+    /// Source id of the saved view that is the project's default
+    pub default_saved_view_id: Option<String>,
+    pub series: Vec<SnapshotSeries>,
+    /// "Blocked by" relations between captured tasks, as (task id, blocker id)
+    #[serde(default)]
+    pub dependencies: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotTask {
+    pub id: String,
+    pub series_id: Option<String>,
+    /// Occurrence of the series the task was generated for
+    #[serde(default)]
+    pub occurrence_date: Option<String>,
+    #[serde(default)]
+    pub section_id: Option<String>,
+    #[serde(default)]
+    pub parent_task_id: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_id: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub estimate_minutes: Option<i32>,
+    pub scheduled_date: Option<String>,
+    pub scheduled_time_minutes: Option<i32>,
+    pub deadline_date: Option<String>,
+    pub deadline_time_minutes: Option<i32>,
+    pub tag_ids: Vec<String>,
+    #[serde(default)]
+    pub status_id: Option<String>,
+    /// Custom field values as (field id, value); numbers and checkboxes are JSON numbers so
+    /// they keep their storage class
+    #[serde(default)]
+    pub custom_values: Vec<(String, serde_json::Value)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSection {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStatus {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotCustomField {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    /// Choices JSON of a single-select field, stored verbatim
+    pub options: Option<String>,
+    pub min_value: Option<f64>,
+    pub max_value: Option<f64>,
+    pub max_length: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSavedView {
+    pub id: String,
+    pub name: String,
+    /// Saved view filters JSON, stored verbatim
+    pub filters: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSeries {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_id: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub estimate_minutes: Option<i32>,
+    pub rrule: String,
+    pub dtstart_date: String,
+    pub dtstart_time_minutes: Option<i32>,
+    pub deadline_offset_minutes: i32,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub interval_days: Option<i32>,
+    #[serde(default)]
+    pub advance_on_abandon: bool,
+    #[serde(default)]
+    pub paused: bool,
+    /// Latest occurrence a task was created for, so the copy doesn't generate the captured
+    /// tasks' occurrences again
+    #[serde(default)]
+    pub materialized_through: Option<String>,
+    pub tag_ids: Vec<String>,
+}
+
+impl ProjectSnapshot {
+    /// Capture the workflow, custom fields, open tasks, saved views and recurring series of a
+    /// project
+    pub async fn capture(pool: &SqlitePool, project_id: &str) -> Result<Self, sqlx::Error> {
+        let (color, icon, enforce_dependencies) =
+            sqlx::query_as::<_, (Option<String>, Option<String>, bool)>(
+                "SELECT color, icon, enforce_dependencies FROM projects WHERE id = ?1",
+            )
+            .bind(project_id)
+            .fetch_one(pool)
+            .await?;
+
+        let sections = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, name FROM sections WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id, name)| SnapshotSection { id, name })
+        .collect();
+
+        let statuses = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT id, name, category FROM project_statuses WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id, name, category)| SnapshotStatus { id, name, category })
+        .collect();
+
+        let custom_fields = sqlx::query(
+            "SELECT id, name, kind, options, min_value, max_value, max_length FROM custom_fields \
+             WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| SnapshotCustomField {
+            id: row.get("id"),
+            name: row.get("name"),
+            kind: row.get("kind"),
+            options: row.get("options"),
+            min_value: row.get("min_value"),
+            max_value: row.get("max_value"),
+            max_length: row.get("max_length"),
+        })
+        .collect();
+
+        // Custom values of all open tasks, quoted as JSON to keep numbers apart from text
+        let mut task_values: HashMap<String, Vec<(String, serde_json::Value)>> = HashMap::new();
+        let rows = sqlx::query(
+            "SELECT v.task_id, v.field_id, json_quote(v.value) AS value FROM task_custom_values v \
+             INNER JOIN tasks t ON t.id = v.task_id \
+             WHERE t.project_id = ?1 AND t.status = 'todo' AND t.deleted_at IS NULL",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            let Ok(value) = serde_json::from_str(row.get::<&str, _>("value")) else {
+                continue;
+            };
+            task_values
+                .entry(row.get("task_id"))
+                .or_default()
+                .push((row.get("field_id"), value));
+        }
+
+        // Task tags for all open tasks in one go
+        let mut task_tags: HashMap<String, Vec<String>> = HashMap::new();
+        let rows = sqlx::query(
+            "SELECT tt.task_id, tt.tag_id FROM task_tags tt \
+             INNER JOIN tasks t ON t.id = tt.task_id \
+             WHERE t.project_id = ?1 AND t.status = 'todo' AND t.deleted_at IS NULL",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            task_tags
+                .entry(row.get("task_id"))
+                .or_default()
+                .push(row.get("tag_id"));
+        }
+
+        // In manual order, so the new project's tasks are ranked the same way
+        let sql = format!(
+            "SELECT id, series_id, occurrence_date, section_id, parent_task_id, title, description, assignee_id, priority, estimate_minutes, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, status_id \
+             FROM tasks t WHERE project_id = ?1 AND status = 'todo' AND deleted_at IS NULL ORDER BY {}",
+            MANUAL_ORDER
+        );
+        let rows = sqlx::query(&sql).bind(project_id).fetch_all(pool).await?;
+        let tasks = rows
+            .into_iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                let tag_ids = task_tags.remove(&id).unwrap_or_default();
+                let custom_values = task_values.remove(&id).unwrap_or_default();
+                SnapshotTask {
+                    id,
+                    series_id: row.get("series_id"),
+                    occurrence_date: row.get("occurrence_date"),
+                    section_id: row.get("section_id"),
+                    parent_task_id: row.get("parent_task_id"),
+                    title: row.get("title"),
+                    description: row.get("description"),
+                    assignee_id: row.get("assignee_id"),
+                    priority: row.get("priority"),
+                    estimate_minutes: row.get("estimate_minutes"),
+                    scheduled_date: row.get("scheduled_date"),
+                    scheduled_time_minutes: row.get("scheduled_time_minutes"),
+                    deadline_date: row.get("deadline_date"),
+                    deadline_time_minutes: row.get("deadline_time_minutes"),
+                    tag_ids,
+                    status_id: row.get("status_id"),
+                    custom_values,
+                }
+            })
+            .collect();
+
+        let saved_views = sqlx::query_as::<_, (String, String, String)>(
+            "SELECT id, name, filters FROM saved_views WHERE project_id = ?1 ORDER BY name",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(id, name, filters)| SnapshotSavedView { id, name, filters })
+        .collect();
+
+        let default_saved_view_id = sqlx::query_as::<_, (Option<String>,)>(
+            "SELECT saved_view_id FROM project_default_view WHERE project_id = ?1",
+        )
+        .bind(project_id)
+        .fetch_optional(pool)
+        .await?
+        .and_then(|r| r.0);
+
+        let mut series_tags: HashMap<String, Vec<String>> = HashMap::new();
+        let rows = sqlx::query(
+            "SELECT rst.series_id, rst.tag_id FROM recurring_series_tags rst \
+             INNER JOIN recurring_series rs ON rs.id = rst.series_id \
+             WHERE rs.project_id = ?1",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+        for row in rows {
+            series_tags
+                .entry(row.get("series_id"))
+                .or_default()
+                .push(row.get("tag_id"));
+        }
+
+        let rows = sqlx::query(
+            "SELECT id, title, description, assignee_id, priority, estimate_minutes, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, \
+             mode, interval_days, advance_on_abandon, paused_at, materialized_through \
+             FROM recurring_series WHERE project_id = ?1 ORDER BY created_at ASC",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+        let series = rows
+            .into_iter()
+            .map(|row| {
+                let id: String = row.get("id");
+                let tag_ids = series_tags.remove(&id).unwrap_or_default();
+                SnapshotSeries {
+                    id,
+                    title: row.get("title"),
+                    description: row.get("description"),
+                    assignee_id: row.get("assignee_id"),
+                    priority: row.get("priority"),
+                    estimate_minutes: row.get("estimate_minutes"),
+                    rrule: row.get("rrule"),
+                    dtstart_date: row.get("dtstart_date"),
+                    dtstart_time_minutes: row.get("dtstart_time_minutes"),
+                    deadline_offset_minutes: row.get("deadline_offset_minutes"),
+                    timezone: row.get("timezone"),
+                    mode: row.get("mode"),
+                    interval_days: row.get("interval_days"),
+                    advance_on_abandon: row.get("advance_on_abandon"),
+                    paused: row.get::<Option<String>, _>("paused_at").is_some(),
+                    materialized_through: row.get("materialized_through"),
+                    tag_ids,
+                }
+            })
+            .collect();
+
+        // Only relations where both ends are open tasks survive the copy
+        let dependencies = sqlx::query_as::<_, (String, String)>(
+            "SELECT d.task_id, d.blocked_by_task_id FROM task_dependencies d \
+             INNER JOIN tasks t ON t.id = d.task_id \
+             INNER JOIN tasks b ON b.id = d.blocked_by_task_id \
+             WHERE t.project_id = ?1 AND t.status = 'todo' AND b.status = 'todo' \
+             AND t.deleted_at IS NULL AND b.deleted_at IS NULL",
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut snapshot = ProjectSnapshot {
+            color,
+            icon,
+            enforce_dependencies,
+            anchor_date: None,
+            sections,
+            statuses,
+            custom_fields,
+            tasks,
+            saved_views,
+            default_saved_view_id,
+            series,
+            dependencies,
+        };
+        snapshot.anchor_date = snapshot
+            .earliest_date()
+            .map(|d| d.format("%Y-%m-%d").to_string());
+        Ok(snapshot)
+    }
+
+    fn earliest_date(&self) -> Option<NaiveDate> {
+        let task_dates = self.tasks.iter().flat_map(|t| {
+            [t.scheduled_date.as_deref(), t.deadline_date.as_deref()]
+                .into_iter()
+                .flatten()
+        });
+        let series_dates = self.series.iter().map(|s| s.dtstart_date.as_str());
+        task_dates
+            .chain(series_dates)
+            .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .min()
+    }
+
+    /// Shift every date so that the snapshot's anchor date lands on `anchor`.
+    /// Snapshots without any dates are left unchanged.
+    pub fn shift_to(&mut self, anchor: NaiveDate) {
+        let Some(current) = self
+            .anchor_date
+            .as_deref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        else {
+            return;
+        };
+        let delta = anchor.signed_duration_since(current);
+        let shift = |date: &mut String| {
+            if let Ok(d) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                *date = (d + delta).format("%Y-%m-%d").to_string();
+            }
+        };
+
+        for task in &mut self.tasks {
+            if let Some(d) = task.scheduled_date.as_mut() {
+                shift(d);
+            }
+            if let Some(d) = task.deadline_date.as_mut() {
+                shift(d);
+            }
+            if let Some(d) = task.occurrence_date.as_mut() {
+                shift(d);
+            }
+        }
+        for series in &mut self.series {
+            shift(&mut series.dtstart_date);
+            if let Some(d) = series.materialized_through.as_mut() {
+                shift(d);
+            }
+        }
+        self.anchor_date = Some(anchor.format("%Y-%m-%d").to_string());
+    }
+
+    /// Create a new project owned by `owner_id` from this snapshot and return its id.
+    ///
+    /// The new project starts with only its owner, so assignees other than the owner are cleared.
+    /// Paused series stay paused, and every task gets a "created" activity entry by the owner.
+    pub async fn instantiate(
+        &self,
+        conn: &mut SqliteConnection,
+        owner_id: &str,
+        name: &str,
+    ) -> Result<String, sqlx::Error> {
+        let project_id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO projects (id, name, owner_id, color, icon, enforce_dependencies) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .bind(&project_id)
+        .bind(name)
+        .bind(owner_id)
+        .bind(&self.color)
+        .bind(&self.icon)
+        .bind(self.enforce_dependencies)
+        .execute(&mut *conn)
+        .await?;
+
+        let keep_assignee = |assignee: &Option<String>| -> Option<String> {
+            assignee.clone().filter(|a| a == owner_id)
+        };
+
+        // The captured workflow replaces the default one the project was created with
+        let mut status_ids: HashMap<&str, String> = HashMap::new();
+        if !self.statuses.is_empty() {
+            sqlx::query("DELETE FROM project_statuses WHERE project_id = ?1")
+                .bind(&project_id)
+                .execute(&mut *conn)
+                .await?;
+        }
+        for (position, status) in self.statuses.iter().enumerate() {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO project_statuses (id, project_id, name, category, position) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(&status.name)
+            .bind(&status.category)
+            .bind(position as i32)
+            .execute(&mut *conn)
+            .await?;
+            status_ids.insert(status.id.as_str(), id);
+        }
+
+        let mut field_ids: HashMap<&str, String> = HashMap::new();
+        for (position, field) in self.custom_fields.iter().enumerate() {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO custom_fields (id, project_id, name, kind, options, min_value, max_value, max_length, position) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(&field.name)
+            .bind(&field.kind)
+            .bind(&field.options)
+            .bind(field.min_value)
+            .bind(field.max_value)
+            .bind(field.max_length)
+            .bind(position as i32)
+            .execute(&mut *conn)
+            .await?;
+            field_ids.insert(field.id.as_str(), id);
+        }
+
+        // Recurring series and their default tags
+        let mut series_ids: HashMap<&str, String> = HashMap::new();
+        for series in &self.series {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO recurring_series \
+                 (id, project_id, created_by, title, description, assignee_id, priority, estimate_minutes, rrule, dtstart_date, dtstart_time_minutes, deadline_offset_minutes, timezone, mode, interval_days, advance_on_abandon, paused_at, materialized_through) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, COALESCE(?13, 'UTC'), COALESCE(?14, 'calendar'), ?15, ?16, \
+                 CASE WHEN ?17 THEN CURRENT_TIMESTAMP END, ?18)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(owner_id)
+            .bind(&series.title)
+            .bind(&series.description)
+            .bind(keep_assignee(&series.assignee_id))
+            .bind(series.priority)
+            .bind(series.estimate_minutes)
+            .bind(&series.rrule)
+            .bind(&series.dtstart_date)
+            .bind(series.dtstart_time_minutes)
+            .bind(series.deadline_offset_minutes)
+            .bind(&series.timezone)
+            .bind(&series.mode)
+            .bind(series.interval_days)
+            .bind(series.advance_on_abandon)
+            .bind(series.paused)
+            .bind(&series.materialized_through)
+            .execute(&mut *conn)
+            .await?;
+            for tag_id in &series.tag_ids {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO recurring_series_tags (series_id, tag_id) SELECT ?1, id FROM tags WHERE id = ?2",
+                )
+                .bind(&id)
+                .bind(tag_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            series_ids.insert(series.id.as_str(), id);
+        }
+
+        // Sections keep their order
+        let mut section_ids: HashMap<&str, String> = HashMap::new();
+        for (position, section) in self.sections.iter().enumerate() {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO sections (id, project_id, name, position) VALUES (?1, ?2, ?3, ?4)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(&section.name)
+            .bind(position as i32)
+            .execute(&mut *conn)
+            .await?;
+            section_ids.insert(section.id.as_str(), id);
+        }
+
+        // Saved views and the default view
+        let mut view_ids: HashMap<&str, String> = HashMap::new();
+        for view in &self.saved_views {
+            let id = uuid::Uuid::new_v4().to_string();
+            sqlx::query(
+                "INSERT INTO saved_views (id, project_id, name, filters, created_by) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(&view.name)
+            .bind(remap_view_filters(&view.filters, &section_ids, &field_ids))
+            .bind(owner_id)
+            .execute(&mut *conn)
+            .await?;
+            view_ids.insert(view.id.as_str(), id);
+        }
+        if let Some(default_id) = self
+            .default_saved_view_id
+            .as_deref()
+            .and_then(|id| view_ids.get(id))
+        {
+            sqlx::query(
+                "INSERT INTO project_default_view (project_id, saved_view_id) VALUES (?1, ?2)",
+            )
+            .bind(&project_id)
+            .bind(default_id)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        // Tasks and their tags
+        let mut task_ids: HashMap<&str, String> = HashMap::new();
+        for task in &self.tasks {
+            let id = uuid::Uuid::new_v4().to_string();
+            let series_id = task.series_id.as_deref().and_then(|s| series_ids.get(s));
+            let occurrence_date = series_id.and(task.occurrence_date.as_ref());
+            let section_id = task.section_id.as_deref().and_then(|s| section_ids.get(s));
+            // Tasks whose status wasn't captured go to the first open status
+            let status_id = task.status_id.as_deref().and_then(|s| status_ids.get(s));
+            let position = append_position(&mut *conn, &project_id).await?;
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, assignee_id, series_id, section_id, title, description, status, priority, estimate_minutes, scheduled_date, scheduled_time_minutes, deadline_date, deadline_time_minutes, position, occurrence_date, status_id) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'todo', ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            )
+            .bind(&id)
+            .bind(&project_id)
+            .bind(owner_id)
+            .bind(keep_assignee(&task.assignee_id))
+            .bind(series_id)
+            .bind(section_id)
+            .bind(&task.title)
+            .bind(&task.description)
+            .bind(task.priority)
+            .bind(task.estimate_minutes)
+            .bind(&task.scheduled_date)
+            .bind(task.scheduled_time_minutes)
+            .bind(&task.deadline_date)
+            .bind(task.deadline_time_minutes)
+            .bind(&position)
+            .bind(occurrence_date)
+            .bind(status_id)
+            .execute(&mut *conn)
+            .await?;
+            for tag_id in &task.tag_ids {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO task_tags (task_id, tag_id) SELECT ?1, id FROM tags WHERE id = ?2",
+                )
+                .bind(&id)
+                .bind(tag_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+            for (field_id, value) in &task.custom_values {
+                let Some(field_id) = field_ids.get(field_id.as_str()) else {
+                    continue;
+                };
+                sqlx::query(
+                    "INSERT INTO task_custom_values (task_id, field_id, value) VALUES (?1, ?2, json_extract(?3, '$'))",
+                )
+                .bind(&id)
+                .bind(field_id)
+                .bind(value.to_string())
+                .execute(&mut *conn)
+                .await?;
+            }
+            task_ids.insert(task.id.as_str(), id);
+        }
+
+        // Subtasks are linked once every task exists; parents that were not captured
+        // (closed tasks) leave their subtasks at the top level
+        for task in &self.tasks {
+            let parent_id = task.parent_task_id.as_deref().and_then(|p| task_ids.get(p));
+            if let (Some(parent_id), Some(id)) = (parent_id, task_ids.get(task.id.as_str())) {
+                sqlx::query("UPDATE tasks SET parent_task_id = ?1 WHERE id = ?2")
+                    .bind(parent_id)
+                    .bind(id)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+
+        for (task_id, blocked_by_task_id) in &self.dependencies {
+            if let (Some(task_id), Some(blocked_by_task_id)) = (
+                task_ids.get(task_id.as_str()),
+                task_ids.get(blocked_by_task_id.as_str()),
+            ) {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO task_dependencies (task_id, blocked_by_task_id, created_by) VALUES (?1, ?2, ?3)",
+                )
+                .bind(task_id)
+                .bind(blocked_by_task_id)
+                .bind(owner_id)
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+
+        for id in task_ids.values() {
+            activity::record(
+                &mut *conn,
+                id,
+                owner_id,
+                ActivityAction::Created,
+                &Default::default(),
+            )
+            .await?;
+        }
+
+        Ok(project_id)
+    }
+}
+
+/// Point a saved view's section and custom field filters at the newly created sections and
+/// fields, dropping any that were not copied. Filters that are not valid JSON are kept
+/// verbatim.
+fn remap_view_filters(
+    filters: &str,
+    section_ids: &HashMap<&str, String>,
+    field_ids: &HashMap<&str, String>,
+) -> String {
+    let Ok(mut value) = serde_json::from_str::<serde_json::Value>(filters) else {
+        return filters.to_string();
+    };
+    let mut changed = false;
+    if let Some(ids) = value.get_mut("sectionIds").and_then(|v| v.as_array_mut()) {
+        *ids = ids
+            .iter()
+            .filter_map(|id| id.as_str().and_then(|id| section_ids.get(id)))
+            .map(|id| serde_json::Value::String(id.clone()))
+            .collect();
+        changed = true;
+    }
+    if let Some(predicates) = value.get_mut("customFields").and_then(|v| v.as_array_mut()) {
+        predicates.retain_mut(|predicate| {
+            let new_id = predicate
+                .get("fieldId")
+                .and_then(|id| id.as_str())
+                .and_then(|id| field_ids.get(id));
+            match (new_id, predicate.as_object_mut()) {
+                (Some(new_id), Some(object)) => {
+                    object.insert("fieldId".to_string(), new_id.clone().into());
+                    true
+                }
+                _ => false,
+            }
+        });
+        changed = true;
+    }
+    if changed {
+        value.to_string()
+    } else {
+        filters.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(scheduled: Option<&str>, deadline: Option<&str>) -> SnapshotTask {
+        SnapshotTask {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: "Task".to_string(),
+            scheduled_date: scheduled.map(str::to_string),
+            deadline_date: deadline.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_earliest_date_spans_tasks_and_series() {
+        let snapshot = ProjectSnapshot {
+            tasks: vec![
+                task(Some("2025-03-10"), Some("2025-03-12")),
+                task(None, None),
+            ],
+            series: vec![SnapshotSeries {
+                dtstart_date: "2025-03-08".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            snapshot.earliest_date(),
+            NaiveDate::from_ymd_opt(2025, 3, 8)
+        );
+    }
+
+    #[test]
+    fn test_shift_to_moves_all_dates_by_same_delta() {
+        let mut snapshot = ProjectSnapshot {
+            anchor_date: Some("2025-03-08".to_string()),
+            tasks: vec![
+                task(Some("2025-03-10"), Some("2025-03-12")),
+                task(None, None),
+            ],
+            series: vec![SnapshotSeries {
+                dtstart_date: "2025-03-08".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        snapshot.shift_to(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+
+        assert_eq!(snapshot.anchor_date.as_deref(), Some("2025-07-01"));
+        assert_eq!(snapshot.series[0].dtstart_date, "2025-07-01");
+        assert_eq!(
+            snapshot.tasks[0].scheduled_date.as_deref(),
+            Some("2025-07-03")
+        );
+        assert_eq!(
+            snapshot.tasks[0].deadline_date.as_deref(),
+            Some("2025-07-05")
+        );
+        assert_eq!(snapshot.tasks[1].scheduled_date, None);
+    }
+
+    #[test]
+    fn test_remap_view_filters() {
+        let mut ids = HashMap::new();
+        ids.insert("old1", "new1".to_string());
+        let mut fields = HashMap::new();
+        fields.insert("f1", "field1".to_string());
+        let remapped = remap_view_filters(
+            r#"{"statuses":["todo"],"sectionIds":["old1","gone"]}"#,
+            &ids,
+            &fields,
+        );
+        let value: serde_json::Value = serde_json::from_str(&remapped).unwrap();
+        assert_eq!(value["sectionIds"], serde_json::json!(["new1"]));
+        assert_eq!(value["statuses"], serde_json::json!(["todo"]));
+
+        // Views without a section filter are untouched
+        let filters = r#"{"statuses":["todo"]}"#;
+        assert_eq!(remap_view_filters(filters, &ids, &fields), filters);
+
+        let remapped = remap_view_filters(
+            r#"{"statuses":["todo"],"customFields":[{"fieldId":"f1","op":"is_set","value":null},{"fieldId":"gone","op":"is_set","value":null}]}"#,
+            &ids,
+            &fields,
+        );
+        let value: serde_json::Value = serde_json::from_str(&remapped).unwrap();
+        assert_eq!(
+            value["customFields"],
+            serde_json::json!([{"fieldId":"field1","op":"is_set","value":null}])
+        );
+    }
+
+    #[test]
+    fn test_shift_to_without_dates_is_noop() {
+        let mut snapshot = ProjectSnapshot {
+            tasks: vec![task(None, None)],
+            ..Default::default()
+        };
+        snapshot.shift_to(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(snapshot.anchor_date, None);
+    }
+}
@@ -0,0 +1,246 @@
+//! Time spent on tasks.
+//!
+//! Entries store UTC timestamps in the same format as the other timestamp columns. An entry
+//! without `ended_at` is a running timer; a partial unique index keeps that to one per user.
+
+use std::collections::BTreeMap;
+
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection};
+
+use super::time_utils::{self, parse_db, to_db};
+
+pub const TIME_ENTRY_COLUMNS: &str =
+    "id, task_id, user_id, started_at, ended_at, note, created_at, updated_at";
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct TimeEntry {
+    pub id: String,
+    #[graphql(name = "taskId")]
+    pub task_id: String,
+    #[graphql(name = "userId")]
+    pub user_id: String,
+    #[graphql(name = "startedAt")]
+    pub started_at: String,
+    /// Unset while the timer is running
+    #[graphql(name = "endedAt")]
+    pub ended_at: Option<String>,
+    pub note: Option<String>,
+    /// Length of the entry, or time so far for a running timer
+    #[graphql(name = "durationMinutes")]
+    pub duration_minutes: i32,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl TimeEntry {
+    /// Build a `TimeEntry` from a row selected with `TIME_ENTRY_COLUMNS`
+    pub fn from_row(row: &SqliteRow) -> Self {
+        let started_at: String = row.get("started_at");
+        let ended_at: Option<String> = row.get("ended_at");
+        let seconds = entry_seconds(&started_at, ended_at.as_deref());
+        TimeEntry {
+            id: row.get("id"),
+            task_id: row.get("task_id"),
+            user_id: row.get("user_id"),
+            started_at,
+            ended_at,
+            note: row.get("note"),
+            duration_minutes: to_minutes(seconds),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// Fetch a single time entry
+pub async fn fetch_time_entry(pool: &sqlx::SqlitePool, id: &str) -> Result<TimeEntry, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM time_entries WHERE id = ?1",
+        TIME_ENTRY_COLUMNS
+    );
+    let row = sqlx::query(&sql).bind(id).fetch_one(pool).await?;
+    Ok(TimeEntry::from_row(&row))
+}
+
+/// Length of an entry in seconds, counting a running timer up to now
+pub fn entry_seconds(started_at: &str, ended_at: Option<&str>) -> i64 {
+    let seconds = match (parse_db(started_at), ended_at.map(parse_db)) {
+        (Some(start), Some(Some(end))) => (end - start).num_seconds(),
+        (Some(start), None) => (Utc::now() - start).num_seconds(),
+        _ => 0,
+    };
+    seconds.max(0)
+}
+
+/// Whole minutes, rounded to the nearest
+pub fn to_minutes(seconds: i64) -> i32 {
+    ((seconds + 30) / 60) as i32
+}
+
+/// Check a recorded period; `ended_at` is unset for a running timer
+pub fn validate_period(
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+) -> Result<(), &'static str> {
+    // Allow for clocks that run a little ahead of ours
+    let latest = Utc::now() + Duration::minutes(1);
+    if started_at > latest || ended_at.is_some_and(|end| end > latest) {
+        return Err("Time entries can't be in the future");
+    }
+    if ended_at.is_some_and(|end| end <= started_at) {
+        return Err("endedAt must be after startedAt");
+    }
+    Ok(())
+}
+
+/// Stop the user's running timer, if any, returning its id
+pub async fn stop_running(
+    conn: &mut SqliteConnection,
+    user_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let running = sqlx::query_as::<_, (String, String)>(
+        "SELECT id, started_at FROM time_entries WHERE user_id = ?1 AND ended_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let Some((id, started_at)) = running else {
+        return Ok(None);
+    };
+
+    // ended_at must come after started_at, even for a timer stopped straight away
+    let now = Utc::now();
+    let ended_at = match parse_db(&started_at) {
+        Some(start) if now <= start => start + Duration::milliseconds(1),
+        _ => now,
+    };
+    sqlx::query("UPDATE time_entries SET ended_at = ?1 WHERE id = ?2")
+        .bind(to_db(ended_at))
+        .bind(&id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(Some(id))
+}
+
+/// UTC bounds of the local dates `from` to `to` inclusive
+pub fn local_date_range(
+    from: NaiveDate,
+    to: NaiveDate,
+    tz: Tz,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = time_utils::resolve_local_time(&from.format("%Y-%m-%d").to_string(), None, tz)?;
+    let end_date = to.succ_opt()?;
+    let end = time_utils::resolve_local_time(&end_date.format("%Y-%m-%d").to_string(), None, tz)?;
+    Some((start.with_timezone(&Utc), end.with_timezone(&Utc)))
+}
+
+/// A time entry as counted by a report
+pub struct ReportEntry {
+    pub user_id: String,
+    pub tag_ids: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+/// Seconds tracked within a period, in total and per user and tag. An entry spanning the
+/// period's edges only counts its time inside the period; time on a task with several tags
+/// counts towards each of them.
+#[derive(Default, Debug, PartialEq)]
+pub struct ReportTotals {
+    pub total: i64,
+    pub untagged: i64,
+    pub by_user: BTreeMap<String, i64>,
+    pub by_tag: BTreeMap<String, i64>,
+}
+
+pub fn aggregate(entries: &[ReportEntry], from: DateTime<Utc>, to: DateTime<Utc>) -> ReportTotals {
+    let mut totals = ReportTotals::default();
+    for entry in entries {
+        let start = entry.started_at.max(from);
+        let end = entry.ended_at.min(to);
+        let seconds = (end - start).num_seconds();
+        if seconds <= 0 {
+            continue;
+        }
+        totals.total += seconds;
+        *totals.by_user.entry(entry.user_id.clone()).or_default() += seconds;
+        if entry.tag_ids.is_empty() {
+            totals.untagged += seconds;
+        }
+        for tag_id in &entry.tag_ids {
+            *totals.by_tag.entry(tag_id.clone()).or_default() += seconds;
+        }
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::time_utils::parse_instant;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_instant(value).unwrap()
+    }
+
+    #[test]
+    fn instants_parse_from_rfc3339_and_db_format() {
+        assert_eq!(
+            to_db(at("2025-03-30T09:15:00+02:00")),
+            "2025-03-30 07:15:00.000"
+        );
+        assert_eq!(
+            to_db(at("2025-03-30 07:15:00.250")),
+            "2025-03-30 07:15:00.250"
+        );
+        assert!(parse_instant("yesterday").is_none());
+        assert_eq!(to_minutes(89), 1);
+        assert_eq!(to_minutes(90), 2);
+    }
+
+    #[test]
+    fn report_periods_follow_local_days() {
+        // The clocks go forward in Amsterdam on 30 March 2025, so that day is 23 hours long
+        let tz: Tz = "Europe/Amsterdam".parse().unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+        let (from, to) = local_date_range(day, day, tz).unwrap();
+        assert_eq!(to_db(from), "2025-03-29 23:00:00.000");
+        assert_eq!(to_db(to), "2025-03-30 22:00:00.000");
+
+        let entries = vec![
+            // Started the evening before, counts from local midnight
+            ReportEntry {
+                user_id: "ann".into(),
+                tag_ids: vec!["garden".into(), "diy".into()],
+                started_at: at("2025-03-29T22:30:00Z"),
+                ended_at: at("2025-03-29T23:30:00Z"),
+            },
+            ReportEntry {
+                user_id: "bob".into(),
+                tag_ids: vec![],
+                started_at: at("2025-03-30T10:00:00Z"),
+                ended_at: at("2025-03-30T10:45:00Z"),
+            },
+            // Entirely the next local day
+            ReportEntry {
+                user_id: "bob".into(),
+                tag_ids: vec![],
+                started_at: at("2025-03-30T22:00:00Z"),
+                ended_at: at("2025-03-30T23:00:00Z"),
+            },
+        ];
+        let totals = aggregate(&entries, from, to);
+        assert_eq!(totals.total, 75 * 60);
+        assert_eq!(totals.untagged, 45 * 60);
+        assert_eq!(totals.by_user["ann"], 30 * 60);
+        assert_eq!(totals.by_user["bob"], 45 * 60);
+        assert_eq!(totals.by_tag["garden"], 30 * 60);
+        assert_eq!(totals.by_tag["diy"], 30 * 60);
+    }
+}
@@ -0,0 +1,228 @@
+//! Soft deletion of tasks.
+//!
+//! Deleting a task moves it and its subtasks into the project's trash by stamping them with
+//! the same `deleted_at`. Restoring brings back everything deleted together; anything left in
+//! the trash longer than the retention period is purged for good.
+
+use std::path::Path;
+
+use sqlx::{SqliteConnection, SqlitePool};
+
+use super::attachments::remove_if_unreferenced;
+use super::versions;
+
+/// Move a task and its live subtasks into the trash
+pub async fn soft_delete(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    user_id: &str,
+) -> Result<(), sqlx::Error> {
+    // One statement, so every row gets the same timestamp
+    sqlx::query(
+        "WITH RECURSIVE descendants(id) AS ( \
+             SELECT id FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+             WHERE t.deleted_at IS NULL \
+         ) \
+         UPDATE tasks SET deleted_at = (strftime('%Y-%m-%d %H:%M:%f','now')), deleted_by = ?2 \
+         WHERE id IN (SELECT id FROM descendants) AND deleted_at IS NULL",
+    )
+    .bind(task_id)
+    .bind(user_id)
+    .execute(&mut *conn)
+    .await?;
+    for id in deleted_together(&mut *conn, task_id).await? {
+        versions::save_current(&mut *conn, &id).await?;
+    }
+    Ok(())
+}
+
+/// A deleted task and the subtasks that went into the trash with it
+async fn deleted_together(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let ids = sqlx::query_as::<_, (String,)>(
+        "WITH RECURSIVE descendants(id, deleted_at) AS ( \
+             SELECT id, deleted_at FROM tasks WHERE id = ?1 \
+             UNION ALL \
+             SELECT t.id, t.deleted_at FROM tasks t INNER JOIN descendants d ON t.parent_task_id = d.id \
+             WHERE t.deleted_at = d.deleted_at \
+         ) SELECT id FROM descendants",
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+    Ok(ids.into_iter().map(|(id,)| id).collect())
+}
+
+/// Take a task out of the trash along with the subtasks that were deleted with it
+pub async fn restore(conn: &mut SqliteConnection, task_id: &str) -> Result<(), sqlx::Error> {
+    let ids = deleted_together(&mut *conn, task_id).await?;
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let sql = format!(
+        "UPDATE tasks SET deleted_at = NULL, deleted_by = NULL WHERE id IN ({})",
+        placeholders
+    );
+    let mut query = sqlx::query(&sql);
+    for id in &ids {
+        query = query.bind(id);
+    }
+    query.execute(&mut *conn).await?;
+    for id in &ids {
+        versions::save_current(&mut *conn, id).await?;
+    }
+    Ok(())
+}
+
+/// Permanently delete tasks that have been in the trash for more than `retention_days`.
+/// Tags, comments, attachments and dependencies go with them; attachment files no longer
+/// used by any task are removed from `attachments_root`. Returns the number of tasks purged.
+pub async fn purge_expired(
+    pool: &SqlitePool,
+    attachments_root: &Path,
+    retention_days: i64,
+) -> Result<u64, sqlx::Error> {
+    let cutoff = format!("-{} days", retention_days);
+    let expired = "deleted_at IS NOT NULL AND deleted_at < strftime('%Y-%m-%d %H:%M:%f','now', ?1)";
+
+    let mut tx = pool.begin().await?;
+
+    // Counted up front: subtasks are removed by the cascade, not by the DELETE itself
+    let (purged,): (i64,) =
+        sqlx::query_as(&format!("SELECT COUNT(*) FROM tasks WHERE {}", expired))
+            .bind(&cutoff)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    let hashes = sqlx::query_as::<_, (String,)>(&format!(
+        "SELECT DISTINCT sha256 FROM task_attachments \
+         WHERE task_id IN (SELECT id FROM tasks WHERE {})",
+        expired
+    ))
+    .bind(&cutoff)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    sqlx::query(&format!("DELETE FROM tasks WHERE {}", expired))
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    for (sha256,) in hashes {
+        remove_if_unreferenced(pool, attachments_root, &sha256).await?;
+    }
+
+    Ok(purged as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_test_db() -> SqlitePool {
+        let pool = SqlitePool::connect(":memory:").await.unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+        sqlx::query("INSERT INTO users (id, username, password) VALUES ('user1', 'testuser', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO projects (id, name, owner_id) VALUES ('p1', 'House', 'user1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO tags (id, name) VALUES ('tag1', 'diy')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, parent) in [
+            ("a", None),
+            ("a1", Some("a")),
+            ("a2", Some("a")),
+            ("b", None),
+        ] {
+            sqlx::query(
+                "INSERT INTO tasks (id, project_id, author_id, parent_task_id, title, status) VALUES (?1, 'p1', 'user1', ?2, ?1, 'todo')",
+            )
+            .bind(id)
+            .bind(parent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("INSERT INTO task_tags (task_id, tag_id) VALUES ('a', 'tag1'), ('b', 'tag1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        pool
+    }
+
+    async fn deleted(pool: &SqlitePool) -> Vec<String> {
+        sqlx::query_as::<_, (String,)>(
+            "SELECT id FROM tasks WHERE deleted_at IS NOT NULL ORDER BY id",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.0)
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn restore_brings_back_what_was_deleted_together() {
+        let pool = setup_test_db().await;
+
+        // a2 is deleted on its own first, then a with the rest of its subtree
+        soft_delete(&mut *pool.acquire().await.unwrap(), "a2", "user1")
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET deleted_at = '2000-01-01 00:00:00.000' WHERE id = 'a2'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        soft_delete(&mut *pool.acquire().await.unwrap(), "a", "user1")
+            .await
+            .unwrap();
+        assert_eq!(deleted(&pool).await, vec!["a", "a1", "a2"]);
+
+        restore(&mut *pool.acquire().await.unwrap(), "a")
+            .await
+            .unwrap();
+        assert_eq!(deleted(&pool).await, vec!["a2"]);
+    }
+
+    #[tokio::test]
+    async fn purge_removes_expired_tasks_and_their_tags() {
+        let pool = setup_test_db().await;
+        let root = std::env::temp_dir().join(format!("purge-{}", uuid::Uuid::new_v4()));
+
+        soft_delete(&mut *pool.acquire().await.unwrap(), "a", "user1")
+            .await
+            .unwrap();
+        soft_delete(&mut *pool.acquire().await.unwrap(), "b", "user1")
+            .await
+            .unwrap();
+        sqlx::query(
+            "UPDATE tasks SET deleted_at = '2000-01-01 00:00:00.000' WHERE id IN ('a', 'a1', 'a2')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let purged = purge_expired(&pool, &root, 30).await.unwrap();
+        assert_eq!(purged, 3);
+
+        let remaining = sqlx::query_as::<_, (String,)>("SELECT task_id FROM task_tags")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec![("b".to_string(),)]);
+        assert_eq!(deleted(&pool).await, vec!["b"]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Versions of tasks, for merging stale writes.
+//!
+//! Whenever `activity::record` logs a change it also stores the task's tracked fields under
+//! the task's new `updated_at`; housekeeping that touches a task without logging anything,
+//! like reordering, stores them with `save_current`. A client that edited an older version sends that version's
+//! `updated_at` along with its changes, which are then merged field by field with whatever
+//! changed since, using the stored version as the common base.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::SqliteConnection;
+
+use super::activity;
+use crate::config;
+
+/// A field both the client and someone else changed, to different values
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FieldConflict {
+    pub field: String,
+    /// Value in the version the client edited; null if that version is no longer kept
+    pub base: Option<Value>,
+    pub server: Value,
+    pub client: Value,
+}
+
+/// Outcome of merging a client's changes into the current task
+#[derive(Debug, Default, PartialEq)]
+pub struct Merge {
+    /// Fields to write: the ones only the client changed
+    pub apply: Vec<String>,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+/// Store a task's tracked fields as the version for its current `updated_at`, dropping the
+/// oldest versions beyond `TASK_VERSIONS_KEPT`
+pub async fn save(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    fields: &Map<String, Value>,
+) -> Result<(), sqlx::Error> {
+    let fields = serde_json::to_string(fields).map_err(|e| sqlx::Error::Protocol(e.to_string()))?;
+    sqlx::query(
+        "INSERT OR REPLACE INTO task_versions (task_id, updated_at, fields) \
+         SELECT id, updated_at, ?2 FROM tasks WHERE id = ?1",
+    )
+    .bind(task_id)
+    .bind(fields)
+    .execute(&mut *conn)
+    .await?;
+    sqlx::query(
+        "DELETE FROM task_versions WHERE task_id = ?1 AND updated_at NOT IN ( \
+             SELECT updated_at FROM task_versions WHERE task_id = ?1 ORDER BY updated_at DESC LIMIT ?2)",
+    )
+    .bind(task_id)
+    .bind(config::TASK_VERSIONS_KEPT)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Store the version for a task's current `updated_at` as it is now
+pub async fn save_current(conn: &mut SqliteConnection, task_id: &str) -> Result<(), sqlx::Error> {
+    let fields = activity::snapshot(&mut *conn, task_id).await?;
+    save(conn, task_id, &fields).await
+}
+
+/// The task's fields as of `updated_at`, if that version is still kept
+pub async fn fetch(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    updated_at: &str,
+) -> Result<Option<Map<String, Value>>, sqlx::Error> {
+    let row = sqlx::query_as::<_, (String,)>(
+        "SELECT fields FROM task_versions WHERE task_id = ?1 AND updated_at = ?2",
+    )
+    .bind(task_id)
+    .bind(updated_at)
+    .fetch_optional(conn)
+    .await?;
+    row.map(|(fields,)| serde_json::from_str(&fields).map_err(|e| sqlx::Error::Decode(Box::new(e))))
+        .transpose()
+}
+
+/// Tag order isn't meaningful
+fn normalized(field: &str, value: &Value) -> Value {
+    match (field, value) {
+        ("tagIds", Value::Array(tags)) => {
+            let mut tags = tags.clone();
+            tags.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            tags.dedup();
+            Value::Array(tags)
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Three-way merge of a client's `changes` with the `current` task, using the version the
+/// client edited as `base`. A field the client left at its base value isn't a change; one
+/// only the client changed is applied; one changed on both sides is a conflict unless both
+/// ended up with the same value. Without a base every differing field is a conflict.
+pub fn merge(
+    base: Option<&Map<String, Value>>,
+    current: &Map<String, Value>,
+    changes: &Map<String, Value>,
+) -> Merge {
+    let mut merge = Merge::default();
+    for (field, client) in changes {
+        let client = normalized(field, client);
+        let server = normalized(field, current.get(field).unwrap_or(&Value::Null));
+        let base = base.map(|base| normalized(field, base.get(field).unwrap_or(&Value::Null)));
+
+        if client == server || base.as_ref() == Some(&client) {
+            continue;
+        }
+        if base.as_ref() == Some(&server) {
+            merge.apply.push(field.clone());
+        } else {
+            merge.conflicts.push(FieldConflict {
+                field: field.clone(),
+                base,
+                server,
+                client,
+            });
+        }
+    }
+    merge.conflicts.sort_by(|a, b| a.field.cmp(&b.field));
+    merge
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn merges_edits_to_different_fields() {
+        let base = fields(json!({
+            "title": "Paint fence",
+            "assigneeId": null,
+            "deadlineDate": "2025-03-01",
+            "tagIds": ["a", "b"]
+        }));
+        // Someone else reassigned the task and moved the deadline
+        let current = fields(json!({
+            "title": "Paint fence",
+            "assigneeId": "user2",
+            "deadlineDate": "2025-03-08",
+            "tagIds": ["a", "b"]
+        }));
+
+        // Renaming merges cleanly; unchanged fields sent along are left alone, as is an
+        // edit that agrees with the server
+        let changes = fields(json!({
+            "title": "Paint the fence",
+            "assigneeId": null,
+            "deadlineDate": "2025-03-08",
+            "tagIds": ["b", "a", "c"]
+        }));
+        assert_eq!(
+            merge(Some(&base), &current, &changes),
+            Merge {
+                apply: vec!["tagIds".into(), "title".into()],
+                conflicts: vec![],
+            }
+        );
+
+        // Moving the deadline somewhere else conflicts
+        let changes = fields(json!({ "deadlineDate": "2025-03-02", "tagIds": ["b", "a"] }));
+        assert_eq!(
+            merge(Some(&base), &current, &changes),
+            Merge {
+                apply: vec![],
+                conflicts: vec![FieldConflict {
+                    field: "deadlineDate".into(),
+                    base: Some(json!("2025-03-01")),
+                    server: json!("2025-03-08"),
+                    client: json!("2025-03-02"),
+                }],
+            }
+        );
+
+        // Without the base, anything that differs from the server conflicts
+        let changes = fields(json!({ "title": "Paint fence", "assigneeId": null }));
+        let merged = merge(None, &current, &changes);
+        assert!(merged.apply.is_empty());
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].field, "assigneeId");
+        assert_eq!(merged.conflicts[0].base, None);
+    }
+}
@@ -0,0 +1,73 @@
+//! Users following a task.
+//!
+//! Authors and assignees are added by triggers on `tasks`, so every way of creating or
+//! reassigning a task is covered. Anyone else in the project adds or removes themselves.
+
+use async_graphql::SimpleObject;
+use sqlx::SqliteConnection;
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct TaskWatcher {
+    #[graphql(name = "userId")]
+    pub user_id: String,
+    pub username: String,
+    #[graphql(name = "firstName")]
+    pub first_name: Option<String>,
+    /// When they started watching
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+}
+
+/// Watchers of a task who are still in its project, longest watching first
+pub async fn list(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+) -> Result<Vec<TaskWatcher>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, String, Option<String>, String)>(
+        "SELECT u.id, u.username, u.first_name, w.created_at FROM task_watchers w \
+         INNER JOIN users u ON u.id = w.user_id \
+         INNER JOIN tasks t ON t.id = w.task_id \
+         INNER JOIN projects p ON p.id = t.project_id \
+         WHERE w.task_id = ?1 AND (p.owner_id = u.id OR EXISTS ( \
+             SELECT 1 FROM project_members pm WHERE pm.project_id = p.id AND pm.user_id = u.id)) \
+         ORDER BY w.created_at ASC, u.username ASC",
+    )
+    .bind(task_id)
+    .fetch_all(conn)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(user_id, username, first_name, created_at)| TaskWatcher {
+            user_id,
+            username,
+            first_name,
+            created_at,
+        })
+        .collect())
+}
+
+pub async fn watch(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    user_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO task_watchers (task_id, user_id) VALUES (?1, ?2)")
+        .bind(task_id)
+        .bind(user_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+pub async fn unwatch(
+    conn: &mut SqliteConnection,
+    task_id: &str,
+    user_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM task_watchers WHERE task_id = ?1 AND user_id = ?2")
+        .bind(task_id)
+        .bind(user_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Per-project workflow statuses.
+//!
+//! A project orders its own statuses ("To do", "Doing", "Waiting", "Done", ...), each in
+//! one of three categories. A task's `status` column holds the category of its `status_id`,
+//! so history, buckets, filters and the complete/abandon/restore rules all keep working on
+//! categories. Triggers put a task in its category's first status whenever something
+//! changes the category without choosing a status, and every project keeps at least one
+//! status per category.
+
+use async_graphql::{Enum, SimpleObject};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection};
+
+use super::TaskStatus;
+
+pub const PROJECT_STATUS_COLUMNS: &str =
+    "id, project_id, name, category, position, created_at, updated_at";
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum StatusCategory {
+    /// Still to be done; the task's `status` is TODO
+    Open,
+    Done,
+    /// The task's `status` is ABANDONED
+    Cancelled,
+}
+
+impl StatusCategory {
+    pub fn as_db(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Done => "done",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "done" => Self::Done,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Open,
+        }
+    }
+
+    /// The task status a task in this category has
+    pub fn task_status(self) -> TaskStatus {
+        match self {
+            Self::Open => TaskStatus::Todo,
+            Self::Done => TaskStatus::Done,
+            Self::Cancelled => TaskStatus::Abandoned,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct ProjectStatus {
+    pub id: String,
+    #[graphql(name = "projectId")]
+    pub project_id: String,
+    pub name: String,
+    pub category: StatusCategory,
+    pub position: i32,
+    #[graphql(name = "createdAt")]
+    pub created_at: String,
+    #[graphql(name = "updatedAt")]
+    pub updated_at: String,
+}
+
+impl ProjectStatus {
+    pub fn from_row(row: &SqliteRow) -> Self {
+        ProjectStatus {
+            id: row.get("id"),
+            project_id: row.get("project_id"),
+            name: row.get("name"),
+            category: StatusCategory::from_db(row.get::<String, _>("category").as_str()),
+            position: row.get("position"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// A project's statuses in workflow order
+pub async fn project_statuses(
+    conn: &mut SqliteConnection,
+    project_id: &str,
+) -> Result<Vec<ProjectStatus>, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM project_statuses WHERE project_id = ?1 ORDER BY position ASC, created_at ASC",
+        PROJECT_STATUS_COLUMNS
+    );
+    let rows = sqlx::query(&sql).bind(project_id).fetch_all(conn).await?;
+    Ok(rows.iter().map(ProjectStatus::from_row).collect())
+}
+
+pub async fn fetch_status(
+    conn: &mut SqliteConnection,
+    status_id: &str,
+) -> Result<ProjectStatus, sqlx::Error> {
+    let sql = format!(
+        "SELECT {} FROM project_statuses WHERE id = ?1",
+        PROJECT_STATUS_COLUMNS
+    );
+    let row = sqlx::query(&sql).bind(status_id).fetch_one(conn).await?;
+    Ok(ProjectStatus::from_row(&row))
+}